@@ -161,6 +161,25 @@ export const PNPM = () => {
   );
 };
 
+export const Deno = () => {
+  return (
+    <SvgIcon viewBox="0 0 32 32">
+      <circle cx="16" cy="16" r="15" fill="#000" />
+      <text
+        x="16"
+        y="21"
+        fill="#fff"
+        fontSize="14"
+        fontFamily="sans-serif"
+        fontWeight="bold"
+        textAnchor="middle"
+      >
+        D
+      </text>
+    </SvgIcon>
+  );
+};
+
 export const Yarn = () => {
   return (
     <SvgIcon viewBox="0 0 518 518">
@@ -1,5 +1,5 @@
 import type { PackageManager } from "@typeslayer/rust-types";
-import { Bun, NPM, PNPM, Yarn } from "../assets/icons";
+import { Bun, Deno, NPM, PNPM, Yarn } from "../assets/icons";
 
 export const PackageManagerIcon = ({
   packageManager,
@@ -15,6 +15,8 @@ export const PackageManagerIcon = ({
       return <PNPM />;
     case "bun":
       return <Bun />;
+    case "deno":
+      return <Deno />;
     default:
       throw new Error(`Unknown package manager: ${packageManager}`);
   }
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type PackageManager = "bun" | "npm" | "pnpm" | "yarn";
+export type PackageManager = "bun" | "deno" | "npm" | "pnpm" | "yarn";
@@ -1,4 +1,24 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { TypeScriptCompilerVariant } from "./TypeScriptCompilerVariant";
 
-export type Settings = { relativePaths: boolean, preferEditorOpen: boolean, preferredEditor: string | null, extraTscFlags: string, applyTscProjectFlag: boolean, maxOldSpaceSize: number | null, maxStackSize: number | null, typescriptCompilerVariant: TypeScriptCompilerVariant, maxNodes: number, analyticsConsent: Array<string>, };
+export type Settings = { relativePaths: boolean, preferEditorOpen: boolean, preferredEditor: string | null, extraTscFlags: string, applyTscProjectFlag: boolean, maxOldSpaceSize: number | null, maxStackSize: number | null, typescriptCompilerVariant: TypeScriptCompilerVariant, maxNodes: number, analyticsConsent: Array<string>, 
+/**
+ * Kill tsc runs that take longer than this many seconds
+ */
+tscTimeoutSeconds: number | null, 
+/**
+ * Kill tsc runs whose process tree uses more resident memory than this many MB
+ */
+tscMaxRssMb: number | null, 
+/**
+ * Keep at most this many project workspaces, least recently used are deleted first (`0` keeps all)
+ */
+maxWorkspaces: number, 
+/**
+ * Delete project workspaces that haven't been opened in this many days (`0` keeps them forever)
+ */
+workspaceRetentionDays: number, 
+/**
+ * Keep at most this many history snapshots per workspace, oldest are deleted first (`0` keeps all)
+ */
+maxSnapshots: number, };
@@ -78,7 +78,7 @@ impl TypeSlayerEvent for EventGenerateTraceFail {
             metadata: create_event_metadata(app_data).await,
             data: EventGenerateTraceFailData {
                 duration: args.duration,
-                package_manager: app_data.package_manager.clone(),
+                package_manager: app_data.package_manager.kind.clone(),
                 stdout: args.stdout,
                 stderr: args.stderr,
                 max_old_space_size: app_data.settings.max_old_space_size,
@@ -88,7 +88,7 @@ impl TypeSlayerEvent for EventGenerateTraceSuccess {
             metadata: create_event_metadata(app_data).await,
             data: EventGenerateTraceSuccessData {
                 duration: args.duration,
-                package_manager: app_data.package_manager.clone(),
+                package_manager: app_data.package_manager.kind.clone(),
                 stdout: args.stdout,
                 stderr: args.stderr,
                 trace_json_file_size: args.trace_json_file_size,
@@ -0,0 +1,163 @@
+use crate::{
+    analyze_trace::{CheckSite, EventSpan, EventSpanEvent},
+    validate::{trace_json::TraceEvent, utils::Position},
+};
+use std::collections::HashMap;
+
+/// `pos` and `end` of a node
+type NodeRange = (u64, u64);
+
+/// The file and node range a check event is about. Either can be missing: `checkSourceFile`
+/// has no range, and `checkExpression` doesn't always have a path.
+pub fn check_node(event: &TraceEvent) -> Option<(Option<&str>, Option<NodeRange>)> {
+    match event {
+        TraceEvent::CheckSourceFile { args, .. }
+        | TraceEvent::CheckSourceFileNodes { args, .. } => Some((Some(&args.path), None)),
+        TraceEvent::CheckExpression { args, .. } => {
+            Some((args.path.as_deref(), Some((args.pos, args.end))))
+        }
+        TraceEvent::CheckVariableDeclaration { args, .. }
+        | TraceEvent::CheckDeferredNode { args, .. } => {
+            Some((Some(&args.path), Some((args.pos, args.end))))
+        }
+        _ => None,
+    }
+}
+
+/// The innermost node of nested check events, given innermost first. The path can come from
+/// an outer event when the inner one doesn't have it. `start` is left for
+/// [`SourcePositions::locate`], since it means reading the file.
+pub fn innermost_site<'a>(events: impl IntoIterator<Item = &'a TraceEvent>) -> Option<CheckSite> {
+    let mut range = None;
+    for (path, node_range) in events.into_iter().filter_map(check_node) {
+        range = range.or(node_range);
+        if let Some(path) = path {
+            return Some(CheckSite {
+                path: path.to_string(),
+                pos: range.map(|(pos, _)| pos),
+                end: range.map(|(_, end)| end),
+                start: None,
+            });
+        }
+    }
+    None
+}
+
+/// The check spans of a trace, to find where the checker was at a point in time.
+pub struct CheckSpans<'a> {
+    /// Outer spans first when they start together
+    spans: Vec<&'a EventSpan>,
+}
+
+impl<'a> CheckSpans<'a> {
+    pub fn new(spans: &'a [EventSpan]) -> Self {
+        let mut spans: Vec<&EventSpan> = spans
+            .iter()
+            .filter(|span| {
+                matches!(&span.event, EventSpanEvent::TraceEvent(event) if check_node(event).is_some())
+            })
+            .collect();
+        spans.sort_by(|a, b| a.start.total_cmp(&b.start).then(b.end.total_cmp(&a.end)));
+        Self { spans }
+    }
+
+    /// Where the checker was at each of `times`, which is `None` outside of checking.
+    pub fn sites_at(&self, times: &[f64]) -> Vec<Option<CheckSite>> {
+        let mut order: Vec<usize> = (0..times.len()).collect();
+        order.sort_by(|a, b| times[*a].total_cmp(&times[*b]));
+
+        let mut sites = vec![None; times.len()];
+        let mut stack: Vec<&EventSpan> = Vec::new();
+        let mut next = 0;
+        for i in order {
+            let time = times[i];
+            while let Some(span) = self.spans.get(next).filter(|span| span.start <= time) {
+                while stack.last().is_some_and(|open| open.end <= span.start) {
+                    stack.pop();
+                }
+                stack.push(span);
+                next += 1;
+            }
+            while stack.last().is_some_and(|open| open.end <= time) {
+                stack.pop();
+            }
+            sites[i] = innermost_site(stack.iter().rev().filter_map(|span| match &span.event {
+                EventSpanEvent::TraceEvent(event) => Some(event),
+                EventSpanEvent::Root { .. } => None,
+            }));
+        }
+        sites
+    }
+}
+
+/// A source file with where each of its lines starts, as a UTF-16 offset (what node offsets
+/// count) and a byte index into `text`.
+struct SourceText {
+    text: String,
+    line_starts: Vec<(u64, usize)>,
+}
+
+impl SourceText {
+    fn new(text: String) -> Self {
+        let mut line_starts = vec![(0, 0)];
+        let mut units = 0u64;
+        for (index, c) in text.char_indices() {
+            units += c.len_utf16() as u64;
+            if c == '\n' {
+                line_starts.push((units, index + 1));
+            }
+        }
+        Self { text, line_starts }
+    }
+
+    fn position(&self, offset: u64) -> Position {
+        // the first entry starts at 0, so there's always a line at or before `offset`
+        let index = self
+            .line_starts
+            .partition_point(|(start, _)| *start <= offset)
+            - 1;
+        let (mut units, byte) = self.line_starts[index];
+        let mut line = index + 1;
+        let mut character = 1;
+        for c in self.text[byte..].chars() {
+            if units >= offset && !c.is_whitespace() {
+                break;
+            }
+            if c == '\n' {
+                line += 1;
+                character = 1;
+            } else {
+                character += c.len_utf16() as i64;
+            }
+            units += c.len_utf16() as u64;
+        }
+        Position { line, character }
+    }
+}
+
+/// Line and character of offsets in source files, reading each file once.
+#[derive(Default)]
+pub struct SourcePositions {
+    files: HashMap<String, Option<SourceText>>,
+}
+
+impl SourcePositions {
+    /// Where `offset` is in `path`, counting from 1. TypeScript's node offsets include the
+    /// whitespace before the node, so that's skipped. Offsets count UTF-16 code units.
+    pub fn position(&mut self, path: &str, offset: u64) -> Option<Position> {
+        let source = self
+            .files
+            .entry(path.to_string())
+            .or_insert_with(|| std::fs::read_to_string(path).ok().map(SourceText::new))
+            .as_ref()?;
+        Some(source.position(offset))
+    }
+
+    /// `site` with its `start` filled in.
+    pub fn locate(&mut self, site: CheckSite) -> CheckSite {
+        CheckSite {
+            start: site.pos.and_then(|pos| self.position(&site.path, pos)),
+            ..site
+        }
+    }
+}
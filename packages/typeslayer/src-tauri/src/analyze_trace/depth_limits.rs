@@ -1,12 +1,21 @@
-use crate::validate::trace_json::TraceEvent;
+use crate::{
+    analyze_trace::{
+        DepthLimitReport, DepthLimitType, ResolvedDepthLimit,
+        check_sites::{CheckSpans, SourcePositions},
+        create_spans,
+    },
+    validate::{trace_json::TraceEvent, types_json::ResolvedType},
+};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use strum::IntoEnumIterator;
 use strum::VariantArray;
 use strum_macros::EnumIter;
 use strum_macros::VariantArray;
 use ts_rs::TS;
 
+/// Places listed per depth limit
+const SITES_PER_DEPTH_LIMIT: usize = 10;
+
 #[derive(
     Eq, Hash, PartialEq, Clone, Copy, Debug, Deserialize, Serialize, EnumIter, VariantArray, TS,
 )]
@@ -44,99 +53,168 @@ impl DepthLimitKind {
             .map(|&kind| (kind, Vec::new()))
             .collect()
     }
+    /// The kind of depth limit `event` is, if it's one.
+    pub fn of_event(event: &TraceEvent) -> Option<DepthLimitKind> {
+        match event {
+            TraceEvent::CheckCrossProductUnionDepthLimit { .. } => {
+                Some(Self::CheckCrossProductUnion)
+            }
+            TraceEvent::CheckTypeRelatedToDepthLimit { .. } => Some(Self::CheckTypeRelatedTo),
+            TraceEvent::GetTypeAtFlowNodeDepthLimit { .. } => Some(Self::GetTypeAtFlowNode),
+            TraceEvent::InstantiateTypeDepthLimit { .. } => Some(Self::InstantiateType),
+            TraceEvent::RecursiveTypeRelatedToDepthLimit { .. } => {
+                Some(Self::RecursiveTypeRelatedTo)
+            }
+            TraceEvent::RemoveSubtypesDepthLimit { .. } => Some(Self::RemoveSubtypes),
+            TraceEvent::TraceUnionsOrIntersectionsTooLargeDepthLimit { .. } => {
+                Some(Self::TraceUnionsOrIntersectionsTooLarge)
+            }
+            TraceEvent::TypeRelatedToDiscriminatedTypeDepthLimit { .. } => {
+                Some(Self::TypeRelatedToDiscriminatedType)
+            }
+            _ => None,
+        }
+    }
 }
 
-pub fn create_depth_limits(trace_file: &[TraceEvent]) -> IndexMap<DepthLimitKind, Vec<TraceEvent>> {
-    let mut depth_limits: IndexMap<DepthLimitKind, Vec<TraceEvent>> =
-        DepthLimitKind::new_trace_event_vec_map();
-
-    fn kind_from_event_name(name: &str) -> Option<DepthLimitKind> {
-        for kind in DepthLimitKind::iter() {
-            if let Ok(serialized) = serde_plain::to_string(&kind)
-                && serialized == name
-            {
-                return Some(kind);
-            }
+/// What depth limits of each kind are sorted by, biggest first: the depth, size or number of
+/// combinations that went over the limit. `removeSubtypes` has nothing to sort by.
+pub fn magnitude(event: &TraceEvent) -> f64 {
+    match event {
+        TraceEvent::CheckCrossProductUnionDepthLimit { args, .. } => args.size as f64,
+        TraceEvent::CheckTypeRelatedToDepthLimit { args, .. } => args.depth as f64,
+        TraceEvent::GetTypeAtFlowNodeDepthLimit { args, .. } => args.flow_id as f64,
+        TraceEvent::InstantiateTypeDepthLimit { args, .. } => args.instantiation_depth as f64,
+        TraceEvent::RecursiveTypeRelatedToDepthLimit { args, .. } => args.depth as f64,
+        TraceEvent::TraceUnionsOrIntersectionsTooLargeDepthLimit { args, .. } => {
+            args.source_size as f64 * args.target_size as f64
         }
-        None
+        TraceEvent::TypeRelatedToDiscriminatedTypeDepthLimit { args, .. } => {
+            args.num_combinations as f64
+        }
+        _ => 0.0,
     }
+}
 
-    for kind in DepthLimitKind::iter() {
-        depth_limits.insert(kind, Vec::new());
+/// The types a depth limit was hit on, e.g. the source and target of a relation.
+pub fn type_ids(event: &TraceEvent) -> Vec<i64> {
+    match event {
+        TraceEvent::CheckCrossProductUnionDepthLimit { args, .. } => args.type_ids.clone(),
+        TraceEvent::RemoveSubtypesDepthLimit { args, .. } => args.type_ids.clone(),
+        TraceEvent::InstantiateTypeDepthLimit { args, .. } => vec![args.type_id],
+        TraceEvent::CheckTypeRelatedToDepthLimit { args, .. } => {
+            vec![args.source_id, args.target_id]
+        }
+        TraceEvent::RecursiveTypeRelatedToDepthLimit { args, .. } => {
+            vec![args.source_id, args.target_id]
+        }
+        TraceEvent::TraceUnionsOrIntersectionsTooLargeDepthLimit { args, .. } => {
+            vec![args.source_id, args.target_id]
+        }
+        TraceEvent::TypeRelatedToDiscriminatedTypeDepthLimit { args, .. } => {
+            vec![args.source_id, args.target_id]
+        }
+        _ => Vec::new(),
     }
+}
+
+pub fn create_depth_limits(trace_file: &[TraceEvent]) -> IndexMap<DepthLimitKind, Vec<TraceEvent>> {
+    let mut depth_limits: IndexMap<DepthLimitKind, Vec<TraceEvent>> =
+        DepthLimitKind::new_trace_event_vec_map();
 
     for ev in trace_file.iter() {
-        if let Some(kind) = kind_from_event_name(ev.name())
+        if let Some(kind) = DepthLimitKind::of_event(ev)
             && let Some(vec) = depth_limits.get_mut(&kind)
         {
             vec.push(ev.clone());
         }
     }
 
-    // Helpers to extract numeric args from specific event types
-    fn num_arg(ev: &TraceEvent, key: &str) -> f64 {
-        let val = serde_json::to_value(ev).ok();
-        match val.and_then(|v| v.get("args").and_then(|args| args.get(key).cloned())) {
-            Some(serde_json::Value::Number(n)) => n.as_f64().unwrap_or(0.0),
-            _ => 0.0,
-        }
+    // stable, so removeSubtypes stays in trace order
+    for events in depth_limits.values_mut() {
+        events.sort_by(|a, b| magnitude(b).total_cmp(&magnitude(a)));
     }
 
-    // Sort per bucket based on noted criteria
-    if let Some(v) = depth_limits.get_mut(&DepthLimitKind::CheckCrossProductUnion) {
-        v.sort_by(|a, b| {
-            num_arg(b, "size")
-                .partial_cmp(&num_arg(a, "size"))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-    }
-    if let Some(v) = depth_limits.get_mut(&DepthLimitKind::CheckTypeRelatedTo) {
-        v.sort_by(|a, b| {
-            num_arg(b, "depth")
-                .partial_cmp(&num_arg(a, "depth"))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-    }
-    if let Some(v) = depth_limits.get_mut(&DepthLimitKind::GetTypeAtFlowNode) {
-        v.sort_by(|a, b| {
-            num_arg(b, "flowId")
-                .partial_cmp(&num_arg(a, "flowId"))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-    }
-    if let Some(v) = depth_limits.get_mut(&DepthLimitKind::InstantiateType) {
-        v.sort_by(|a, b| {
-            num_arg(b, "instantiationDepth")
-                .partial_cmp(&num_arg(a, "instantiationDepth"))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-    }
-    if let Some(v) = depth_limits.get_mut(&DepthLimitKind::RecursiveTypeRelatedTo) {
-        v.sort_by(|a, b| {
-            num_arg(b, "depth")
-                .partial_cmp(&num_arg(a, "depth"))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-    }
+    depth_limits
+}
 
-    // RemoveSubtypes: not sorted
+/// Depth limits resolved to the types they were hit on and where the checker was, with the
+/// ones on the same types merged. `types` names the types when it's loaded.
+pub fn create_depth_limit_report(
+    trace_file: &[TraceEvent],
+    types: &[ResolvedType],
+) -> Result<DepthLimitReport, String> {
+    let events: Vec<(DepthLimitKind, &TraceEvent)> = trace_file
+        .iter()
+        .filter_map(|event| DepthLimitKind::of_event(event).map(|kind| (kind, event)))
+        .collect();
 
-    if let Some(v) = depth_limits.get_mut(&DepthLimitKind::TraceUnionsOrIntersectionsTooLarge) {
-        v.sort_by(|a, b| {
-            let a_prod = num_arg(a, "sourceSize") * num_arg(a, "targetSize");
-            let b_prod = num_arg(b, "sourceSize") * num_arg(b, "targetSize");
-            b_prod
-                .partial_cmp(&a_prod)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-    }
-    if let Some(v) = depth_limits.get_mut(&DepthLimitKind::TypeRelatedToDiscriminatedType) {
-        v.sort_by(|a, b| {
-            num_arg(b, "numCombinations")
-                .partial_cmp(&num_arg(a, "numCombinations"))
-                .unwrap_or(std::cmp::Ordering::Equal)
+    let parse_result = create_spans(trace_file)?;
+    let times: Vec<f64> = events.iter().map(|(_, event)| event.common().ts).collect();
+    let sites = CheckSpans::new(&parse_result.spans).sites_at(&times);
+
+    let mut report: DepthLimitReport = DepthLimitKind::VARIANTS
+        .iter()
+        .map(|&kind| (kind, Vec::new()))
+        .collect();
+    // `getTypeAtFlowNode` has no types, so it's told apart by flow node
+    let mut merged: IndexMap<(DepthLimitKind, Vec<i64>), usize> = IndexMap::new();
+    for ((kind, event), site) in events.into_iter().zip(sites) {
+        let key = match event {
+            TraceEvent::GetTypeAtFlowNodeDepthLimit { args, .. } => vec![args.flow_id as i64],
+            _ => type_ids(event),
+        };
+        let limits = report.entry(kind).or_default();
+        let index = *merged.entry((kind, key)).or_insert_with(|| {
+            limits.push(ResolvedDepthLimit {
+                kind,
+                event: event.clone(),
+                magnitude: magnitude(event),
+                count: 0,
+                types: type_ids(event)
+                    .into_iter()
+                    .map(|id| {
+                        let resolved = usize::try_from(id).ok().and_then(|id| types.get(id));
+                        DepthLimitType {
+                            id,
+                            name: resolved.map(|resolved| resolved.human_readable_name()),
+                            declaration: resolved
+                                .and_then(|resolved| resolved.first_declaration.clone()),
+                        }
+                    })
+                    .collect(),
+                sites: Vec::new(),
+            });
+            limits.len() - 1
         });
+
+        let limit = &mut limits[index];
+        limit.count += 1;
+        if magnitude(event) > limit.magnitude {
+            limit.magnitude = magnitude(event);
+            limit.event = event.clone();
+        }
+        if let Some(site) = site
+            && limit.sites.len() < SITES_PER_DEPTH_LIMIT
+            && !limit
+                .sites
+                .iter()
+                .any(|seen| seen.path == site.path && seen.pos == site.pos)
+        {
+            limit.sites.push(site);
+        }
     }
 
-    depth_limits
+    // only the sites that are kept are worth reading their files for
+    let mut positions = SourcePositions::default();
+    for limits in report.values_mut() {
+        limits.sort_by(|a, b| b.magnitude.total_cmp(&a.magnitude));
+        for limit in limits.iter_mut() {
+            limit.sites = std::mem::take(&mut limit.sites)
+                .into_iter()
+                .map(|site| positions.locate(site))
+                .collect();
+        }
+    }
+    Ok(report)
 }
@@ -1,3 +1,4 @@
+mod check_sites;
 pub mod constants;
 mod depth_limits;
 mod duplicate_node_modules;
@@ -7,18 +8,19 @@ mod node_module_paths;
 mod spans;
 mod types;
 
-pub use depth_limits::DepthLimitKind;
+pub use check_sites::{SourcePositions, check_node, innermost_site};
+pub use depth_limits::{DepthLimitKind, create_depth_limit_report};
 pub use file_statistics::FileStatistics;
-pub use spans::create_spans;
+pub use hotspots::get_hotspots;
+pub use node_module_paths::package_name;
+pub use spans::{create_span_tree, create_spans};
 pub use types::*;
 
 use crate::analyze_trace::constants::ANALYZE_TRACE_FILENAME;
 use crate::analyze_trace::depth_limits::create_depth_limits;
 use crate::analyze_trace::duplicate_node_modules::get_duplicate_node_modules;
 use crate::analyze_trace::file_statistics::create_file_statistics;
-use crate::analyze_trace::hotspots::get_hotspots;
 use crate::analyze_trace::node_module_paths::get_node_module_paths;
-use crate::analyze_trace::spans::create_span_tree;
 use crate::validate::trace_json::{TRACE_JSON_FILENAME, TraceEvent};
 use std::fs::{self, File};
 use std::io::{self, BufReader};
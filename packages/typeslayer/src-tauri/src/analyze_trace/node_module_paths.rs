@@ -11,6 +11,16 @@ fn package_name_regex() -> &'static Regex {
     })
 }
 
+/// The package a file belongs to, e.g. `@types/react` for
+/// `.../node_modules/@types/react/index.d.ts`. For nested `node_modules`, the innermost one.
+pub fn package_name(path: &str) -> Option<&str> {
+    package_name_regex()
+        .captures_iter(path)
+        .last()
+        .and_then(|captures| captures.get(1))
+        .map(|package_name| package_name.as_str())
+}
+
 pub fn get_node_module_paths(trace_json: &[TraceEvent]) -> NodeModulePaths {
     let mut node_module_paths: NodeModulePaths = NodeModulePaths::new();
     let regex = package_name_regex();
@@ -126,3 +126,41 @@ pub fn create_span_tree(parse_result: ParseResult, options: &AnalyzeTraceOptions
 
     root
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn unknown_begin_and_end_events_make_a_span() {
+        let trace: Vec<TraceEvent> = serde_json::from_value(json!([
+            { "name": "checkSomethingNew", "cat": "check", "ph": "B", "pid": 1, "tid": 1, "ts": 100.0, "args": { "path": "/a.ts" } },
+            { "name": "checkSomethingNew", "cat": "check", "ph": "E", "pid": 1, "tid": 1, "ts": 350.0 },
+        ]))
+        .unwrap();
+
+        let parse_result = create_spans(&trace).unwrap();
+        assert!(parse_result.unclosed_stack.is_empty());
+        let [span] = parse_result.spans.as_slice() else {
+            panic!("expected one span, got {:?}", parse_result.spans);
+        };
+        assert!(matches!(
+            &span.event,
+            EventSpanEvent::TraceEvent(TraceEvent::Unknown { name, .. }) if name == "checkSomethingNew"
+        ));
+        assert_eq!((span.start, span.end, span.duration), (100.0, 350.0, 250.0));
+    }
+
+    #[test]
+    fn unknown_complete_events_make_a_span() {
+        let trace: Vec<TraceEvent> = serde_json::from_value(json!([
+            { "name": "somethingNew", "ph": "X", "pid": 1, "tid": 1, "ts": 10.0, "dur": 5.0 },
+        ]))
+        .unwrap();
+
+        let parse_result = create_spans(&trace).unwrap();
+        assert_eq!(parse_result.spans.len(), 1);
+        assert_eq!(parse_result.spans[0].duration, 5.0);
+    }
+}
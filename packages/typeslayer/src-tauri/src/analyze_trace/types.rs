@@ -1,5 +1,11 @@
 use crate::analyze_trace::file_statistics::FileStatistics;
-use crate::{analyze_trace::depth_limits::DepthLimitKind, validate::trace_json::TraceEvent};
+use crate::{
+    analyze_trace::depth_limits::DepthLimitKind,
+    validate::{
+        trace_json::TraceEvent,
+        utils::{Location, Position},
+    },
+};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -130,3 +136,44 @@ impl AnalyzeTraceResult {
         counts
     }
 }
+
+/// A node the checker was working on, from a `checkSourceFile`, `checkExpression`,
+/// `checkVariableDeclaration` or `checkDeferredNode` span.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckSite {
+    pub path: String,
+    /// Offsets into the file. `None` for a whole file.
+    pub pos: Option<u64>,
+    pub end: Option<u64>,
+    /// Line and character of `pos`, counting from 1, when the file could be read
+    pub start: Option<Position>,
+}
+
+/// A type a depth limit was hit on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthLimitType {
+    pub id: i64,
+    /// From `types.json`, when it's loaded
+    pub name: Option<String>,
+    pub declaration: Option<Location>,
+}
+
+/// The times one kind of depth limit was hit on the same types, e.g. the same source and target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedDepthLimit {
+    pub kind: DepthLimitKind,
+    /// The time with the largest magnitude
+    pub event: TraceEvent,
+    /// What the kind is sorted by, e.g. the depth, size or number of combinations
+    pub magnitude: f64,
+    pub count: usize,
+    pub types: Vec<DepthLimitType>,
+    /// Where the checker was each time, innermost check span first
+    pub sites: Vec<CheckSite>,
+}
+
+/// Depth limits by kind, biggest magnitude first.
+pub type DepthLimitReport = IndexMap<DepthLimitKind, Vec<ResolvedDepthLimit>>;
@@ -1,6 +1,7 @@
-use std::fmt::Display;
+use std::{fmt::Display, path::Path};
 
 use serde::{Deserialize, Serialize};
+use tokio::process::Command;
 use ts_rs::TS;
 
 pub struct TSCCommand {
@@ -9,6 +10,46 @@ pub struct TSCCommand {
     pub command: String,
 }
 
+impl TSCCommand {
+    /// Wrap a command line in the platform's shell
+    pub fn in_shell(command: String) -> Self {
+        if cfg!(target_os = "windows") {
+            Self {
+                shell: "cmd".to_string(),
+                shell_arg: "/c".to_string(),
+                command,
+            }
+        } else {
+            Self {
+                shell: "sh".to_string(),
+                shell_arg: "-c".to_string(),
+                command,
+            }
+        }
+    }
+
+    /// The process to spawn, running in `cwd`
+    pub fn to_command(&self, cwd: &Path) -> Command {
+        let mut cmd = Command::new(&self.shell);
+        cmd.arg(&self.shell_arg);
+
+        // raw_arg makes sure a command like `npx tsc --project "foo bar"` is interpreted by the shell.
+        // The default with `.arg`, on Windows, is more like `npx tsc --project "\"foo bar\""` but shell quoting is more complicated.
+        #[cfg(target_os = "windows")]
+        {
+            cmd.raw_arg(format!("\"{}\"", self.command));
+            // Set CREATE_NO_WINDOW https://learn.microsoft.com/en-us/windows/win32/procthread/process-creation-flags
+            cmd.creation_flags(0x08000000);
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        cmd.arg(&self.command);
+
+        cmd.current_dir(cwd);
+        cmd
+    }
+}
+
 impl Display for TSCCommand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&self.command)
@@ -20,6 +61,7 @@ impl Display for TSCCommand {
 #[ts(export)]
 pub enum PackageManager {
     Bun,
+    Deno,
     NPM,
     PNPM,
     Yarn,
@@ -30,4 +72,6 @@ pub enum PackageManager {
 #[serde(rename_all = "camelCase")]
 pub struct PackageJSON {
     pub package_manager: Option<String>,
+    /// Only checked for presence, which marks a workspace root
+    pub workspaces: Option<serde_json::Value>,
 }
@@ -0,0 +1,117 @@
+use crate::utils::AVAILABLE_EDITORS;
+use serde_json::{Value, json};
+
+/// Written next to typeslayer.toml, which points at it with a `#:schema` directive
+/// (understood by Taplo, and so by the Even Better TOML extension).
+pub const CONFIG_SCHEMA_FILENAME: &str = "typeslayer.schema.json";
+
+/// The first line of a written typeslayer.toml.
+pub const CONFIG_SCHEMA_DIRECTIVE: &str = "#:schema ./typeslayer.schema.json";
+
+fn integer(description: &str, minimum: i64, maximum: Option<i64>) -> Value {
+    let mut schema = json!({
+        "type": "integer",
+        "description": description,
+        "minimum": minimum,
+    });
+    if let Some(maximum) = maximum {
+        schema["maximum"] = json!(maximum);
+    }
+    schema
+}
+
+fn boolean(description: &str) -> Value {
+    json!({ "type": "boolean", "description": description })
+}
+
+fn number(description: &str) -> Value {
+    json!({ "type": "number", "description": description, "minimum": 0 })
+}
+
+fn string(description: &str) -> Value {
+    json!({ "type": "string", "description": description })
+}
+
+/// Written by TypeSlayer to record runtime state; setting it has no effect.
+fn written(description: &str) -> Value {
+    json!({ "description": format!("{description} (written by TypeSlayer, not read)") })
+}
+
+/// JSON Schema (draft 2020-12) for typeslayer.toml.
+///
+/// Every key here is one `LayerCake` resolves from the file; the matching env var
+/// (`TYPESLAYER_` + the key in SCREAMING_SNAKE_CASE) and `--kebab-case` flag take precedence over it.
+/// The same schema covers a project's own typeslayer.toml, which may only set some of these.
+pub fn config_schema() -> Value {
+    let editors: Vec<&str> = AVAILABLE_EDITORS.iter().map(|(cmd, _)| *cmd).collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "typeslayer.toml",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "project_root": string("Directory of the package.json to investigate"),
+            "verbose": boolean("Log debug output"),
+            "mcp_http_port": integer("Port for the MCP streamable HTTP transport, 0 disables it", 0, Some(65535)),
+            "session_id": string("Identifies this session in analytics"),
+            "outputs_dir": written("Where the current workspace's outputs are"),
+            "platform": written("The platform TypeSlayer is running on"),
+            "version": written("The TypeSlayer version that wrote this file"),
+            "outputs": written("When each output file was last written"),
+            "settings": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "relativePaths": boolean("Show paths relative to the project root"),
+                    "preferEditorOpen": boolean("Open files in an editor rather than the system default app"),
+                    "preferredEditor": {
+                        "description": "Editor command to open files with",
+                        "enum": editors,
+                    },
+                    "extraTscFlags": string("Flags added to every tsc run, e.g. `--noEmit --incremental false`"),
+                    "applyTscProjectFlag": boolean("Pass the selected tsconfig to tsc with `--project`"),
+                    "tsconfig": string("Path of the tsconfig to select, as discovered under the project root"),
+                    "maxOldSpaceSize": integer("Node's --max-old-space-size for tsc, in MB", 1, None),
+                    "maxStackSize": integer("Node's --stack-size for tsc, in KB", 1, None),
+                    "tscTimeoutSeconds": integer("Kill tsc runs that take longer than this many seconds", 1, None),
+                    "tscMaxRssMb": integer("Kill tsc runs whose process tree uses more resident memory than this many MB", 1, None),
+                    "typescriptCompilerVariant": {
+                        "description": "Compiler to run",
+                        "enum": ["tsc", "vue-tsc", "tsgo"],
+                    },
+                    "maxNodes": integer("Most nodes to render in the type graph", 0, Some(10_000_000)),
+                    "maxWorkspaces": integer("Keep at most this many project workspaces (0 keeps all)", 0, None),
+                    "workspaceRetentionDays": integer("Delete workspaces that haven't been opened in this many days (0 keeps them forever)", 0, None),
+                    "maxSnapshots": integer("Keep at most this many history snapshots per workspace (0 keeps all)", 0, None),
+                    "disableAnalytics": boolean("Send no analytics at all"),
+                    "analyticsConsent": {
+                        "type": "array",
+                        "description": "Analytics events you agree to send",
+                        "items": { "type": "string" },
+                        "uniqueItems": true,
+                    },
+                },
+            },
+            "thresholds": {
+                "type": "object",
+                "description": "What the trace analysis reports as a hot spot",
+                "additionalProperties": false,
+                "properties": {
+                    "forceMillis": number("Spans at least this many milliseconds long are always hot spots (at least 100)"),
+                    "minSpanParentPercentage": number("Shorter spans are only hot spots when they take at least this fraction of their parent, e.g. 0.6"),
+                },
+            },
+            "budgets": {
+                "type": "object",
+                "description": "Limits checked against the generated outputs. 0 or unset means no limit",
+                "additionalProperties": false,
+                "properties": {
+                    "totalCheckTimeMs": integer("Total checkSourceFile time, in milliseconds", 0, None),
+                    "typeCount": integer("Types in types.json", 0, None),
+                    "hotSpotCount": integer("Hot spots found by the trace analysis", 0, None),
+                    "depthLimitHits": integer("Depth limits hit, of every kind", 0, None),
+                },
+            },
+        },
+    })
+}
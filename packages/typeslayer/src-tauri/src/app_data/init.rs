@@ -4,13 +4,16 @@ use crate::{
     layercake::{
         LayerCake, ResolveArrayOfStringsArgs, ResolveBoolArgs, ResolveNumberArgs, ResolveStringArgs,
     },
+    run_metadata::{RUN_METADATA_FILENAME, RunMetadata},
+    tsconfig::resolve::{EFFECTIVE_TSCONFIG_FILENAME, EffectiveTsconfig},
     type_graph::{TYPE_GRAPH_FILENAME, TypeGraph},
     utils::{
-        AVAILABLE_EDITORS, CONFIG_FILENAME, OUTPUTS_DIRECTORY, TSCONFIG_FILENAME,
-        default_extra_tsc_flags, detect_project_root_from_cwd, validate_project_root_path,
+        AVAILABLE_EDITORS, CONFIG_FILENAME, DEFAULT_MCP_HTTP_PORT, OUTPUTS_DIRECTORY,
+        TSCONFIG_FILENAME, default_extra_tsc_flags, detect_project_root_from_cwd,
+        validate_project_root_path,
     },
     validate::{
-        trace_json::{TRACE_JSON_FILENAME, TraceEvent, load_trace_json},
+        trace_json::{ParseWarnings, TRACE_JSON_FILENAME, TraceEvent, load_trace_json},
         types_json::{TYPES_JSON_FILENAME, TypesJsonSchema, load_types_json},
         utils::CPU_PROFILE_FILENAME,
     },
@@ -28,7 +31,10 @@ struct ConfigVersion {
     version: String,
 }
 
-pub async fn init_version_and_maybe_clear_outputs(data_dir: &Path) -> Result<String, String> {
+/// Reads the version that last wrote the config. Outputs are no longer wiped on upgrade:
+/// they live in per-project workspaces that record their own version, and anything a new
+/// version can't parse is simply skipped when loading.
+pub async fn init_version(data_dir: &Path) -> Result<String, String> {
     let current_version = env!("CARGO_PKG_VERSION").to_string();
     let config_path = data_dir.join(CONFIG_FILENAME);
     let config_version = if let Ok(contents) = fs::read_to_string(&config_path).await {
@@ -41,35 +47,52 @@ pub async fn init_version_and_maybe_clear_outputs(data_dir: &Path) -> Result<Str
         None
     };
 
-    let versions_match = matches!(config_version, Some(ref v) if v == &current_version);
-
-    if !versions_match {
-        let outputs_dir = data_dir.join(OUTPUTS_DIRECTORY);
-        debug!(
-            "[init_version_and_maybe_clear_outputs] version mismatch or no config found (found: {:?}, current: {}), clearing outputs at {}",
-            config_version,
-            current_version,
-            outputs_dir.display()
+    if config_version.as_ref() != Some(&current_version) {
+        info!(
+            "[init_version] upgraded from {:?} to {}, keeping existing workspaces",
+            config_version, current_version
         );
+    }
+    Ok(current_version)
+}
 
-        if outputs_dir.exists()
-            && let Ok(mut entries) = fs::read_dir(&outputs_dir).await
-        {
-            while let Ok(Some(entry)) = entries.next_entry().await {
-                let path = entry.path();
-                if path.is_file()
-                    && let Err(e) = fs::remove_file(&path).await
-                {
-                    info!(
-                        "[init_version] failed to remove file {}: {}",
-                        path.display(),
-                        e
-                    );
-                }
-            }
+/// Outputs used to live directly in `data_dir/outputs`. Move any such loose files into
+/// `workspace_dir` (the workspace being opened) unless it already has its own copy.
+pub async fn migrate_legacy_outputs(data_dir: &Path, workspace_dir: &Path) {
+    let legacy_dir = data_dir.join(OUTPUTS_DIRECTORY);
+    let Ok(mut entries) = fs::read_dir(&legacy_dir).await else {
+        return;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        if let Err(e) = fs::create_dir_all(workspace_dir).await {
+            info!(
+                "[migrate_legacy_outputs] failed to create {}: {e}",
+                workspace_dir.display()
+            );
+            return;
+        }
+        let target = workspace_dir.join(entry.file_name());
+        let result = if target.exists() {
+            fs::remove_file(&path).await
+        } else {
+            fs::rename(&path, &target).await
+        };
+        match result {
+            Ok(()) => debug!(
+                "[migrate_legacy_outputs] moved {} into {}",
+                path.display(),
+                workspace_dir.display()
+            ),
+            Err(e) => info!(
+                "[migrate_legacy_outputs] failed to migrate {}: {e}",
+                path.display()
+            ),
         }
     }
-    Ok(current_version)
 }
 
 pub fn init_project_root(cake: &mut LayerCake) -> PathBuf {
@@ -134,7 +157,10 @@ pub async fn init_types_json(outputs_dir: &Path, project_root: &Path) -> TypesJs
     Vec::new()
 }
 
-pub async fn init_trace_json(outputs_dir: &Path, project_root: &Path) -> Vec<TraceEvent> {
+pub async fn init_trace_json(
+    outputs_dir: &Path,
+    project_root: &Path,
+) -> (Vec<TraceEvent>, ParseWarnings) {
     let trace_paths = [
         outputs_dir.join(TRACE_JSON_FILENAME.trim_start_matches('/')),
         project_root.join(TRACE_JSON_FILENAME.trim_start_matches('/')),
@@ -161,7 +187,7 @@ pub async fn init_trace_json(outputs_dir: &Path, project_root: &Path) -> Vec<Tra
         "[init_trace_json] [{}] No valid file found at startup",
         TRACE_JSON_FILENAME
     );
-    Vec::new()
+    (Vec::new(), ParseWarnings::default())
 }
 
 pub async fn init_analyze_trace(outputs_dir: &Path) -> Option<AnalyzeTraceResult> {
@@ -195,6 +221,16 @@ pub async fn init_cpu_profile(outputs_dir: &Path) -> Option<String> {
     }
 }
 
+pub async fn init_effective_tsconfig(outputs_dir: &Path) -> Option<EffectiveTsconfig> {
+    let path = outputs_dir.join(EFFECTIVE_TSCONFIG_FILENAME);
+    read_file(EFFECTIVE_TSCONFIG_FILENAME, path).await
+}
+
+pub async fn init_run_metadata(outputs_dir: &Path) -> Option<RunMetadata> {
+    let path = outputs_dir.join(RUN_METADATA_FILENAME);
+    read_file(RUN_METADATA_FILENAME, path).await
+}
+
 async fn read_file<T: Send + serde::de::DeserializeOwned + 'static>(
     name: &'static str,
     path: PathBuf,
@@ -266,6 +302,99 @@ pub fn init_settings(cake: &mut LayerCake) -> Settings {
             }
         },
     }));
+    let max_nodes = cake.resolve_number(ResolveNumberArgs {
+        env: "MAX_NODES",
+        flag: "--max-nodes",
+        file: "settings.maxNodes",
+        default: || Settings::default().max_nodes,
+        validate: |n| {
+            if *n <= 10_000_000 {
+                Ok(*n)
+            } else {
+                Err("maxNodes must not exceed 10,000,000".to_string())
+            }
+        },
+    });
+
+    let max_workspaces = cake.resolve_number(ResolveNumberArgs {
+        env: "MAX_WORKSPACES",
+        flag: "--max-workspaces",
+        file: "settings.maxWorkspaces",
+        default: || Settings::default().max_workspaces,
+        validate: |n| {
+            if *n >= 0 {
+                Ok(*n)
+            } else {
+                Err("maxWorkspaces must not be negative".to_string())
+            }
+        },
+    });
+
+    let workspace_retention_days = cake.resolve_number(ResolveNumberArgs {
+        env: "WORKSPACE_RETENTION_DAYS",
+        flag: "--workspace-retention-days",
+        file: "settings.workspaceRetentionDays",
+        default: || Settings::default().workspace_retention_days,
+        validate: |n| {
+            if *n >= 0 {
+                Ok(*n)
+            } else {
+                Err("workspaceRetentionDays must not be negative".to_string())
+            }
+        },
+    });
+
+    let max_snapshots = cake.resolve_number(ResolveNumberArgs {
+        env: "MAX_SNAPSHOTS",
+        flag: "--max-snapshots",
+        file: "settings.maxSnapshots",
+        default: || Settings::default().max_snapshots,
+        validate: |n| {
+            if *n >= 0 {
+                Ok(*n)
+            } else {
+                Err("maxSnapshots must not be negative".to_string())
+            }
+        },
+    });
+
+    let disable_analytics = cake.resolve_bool(ResolveBoolArgs {
+        env: "DISABLE_ANALYTICS",
+        flag: "--disable-analytics",
+        file: "settings.disableAnalytics",
+        default: || false,
+    });
+
+    let analytics_consent = if disable_analytics {
+        Vec::new()
+    } else {
+        cake.resolve_array_of_strings(ResolveArrayOfStringsArgs {
+            env: "ANALYTICS_CONSENT",
+            flag: "--analytics-consent",
+            file: "settings.analyticsConsent",
+            default: || Settings::default().analytics_consent,
+            validate: |items| Ok(items.to_vec()),
+        })
+    };
+
+    let mut settings = Settings {
+        relative_paths,
+        prefer_editor_open,
+        preferred_editor,
+        max_nodes,
+        analytics_consent,
+        max_workspaces,
+        workspace_retention_days,
+        max_snapshots,
+        ..Settings::default()
+    };
+    init_project_settings(cake, &mut settings);
+    settings
+}
+
+/// The settings a project-level typeslayer.toml may set (see `is_project_key`).
+/// Resolved again whenever the project changes.
+pub fn init_project_settings(cake: &mut LayerCake, settings: &mut Settings) {
     let extra_tsc_flags = cake.resolve_string(ResolveStringArgs {
         env: "EXTRA_TSC_FLAGS",
         flag: "--extra-tsc-flags",
@@ -324,6 +453,56 @@ pub fn init_settings(cake: &mut LayerCake) -> Settings {
             Some(val.parse::<i32>().unwrap())
         }
     };
+    let tsc_timeout_seconds = {
+        let val = cake.resolve_string(ResolveStringArgs {
+            env: "TSC_TIMEOUT_SECONDS",
+            flag: "--tsc-timeout-seconds",
+            file: "settings.tscTimeoutSeconds",
+            default: || "".to_string(),
+            validate: |s| {
+                if s.is_empty() {
+                    Ok(s.to_string())
+                } else {
+                    match s.parse::<i32>() {
+                        Ok(n) if n > 0 => Ok(s.to_string()),
+                        Ok(_) => Err(format!("tscTimeoutSeconds must be positive, got {s:?}")),
+                        Err(e) => Err(format!("Invalid tscTimeoutSeconds value {s:?}: {e}")),
+                    }
+                }
+            },
+        });
+        if val.is_empty() {
+            None
+        } else {
+            Some(val.parse::<i32>().unwrap())
+        }
+    };
+
+    let tsc_max_rss_mb = {
+        let val = cake.resolve_string(ResolveStringArgs {
+            env: "TSC_MAX_RSS_MB",
+            flag: "--tsc-max-rss-mb",
+            file: "settings.tscMaxRssMb",
+            default: || "".to_string(),
+            validate: |s| {
+                if s.is_empty() {
+                    Ok(s.to_string())
+                } else {
+                    match s.parse::<i32>() {
+                        Ok(n) if n > 0 => Ok(s.to_string()),
+                        Ok(_) => Err(format!("tscMaxRssMb must be positive, got {s:?}")),
+                        Err(e) => Err(format!("Invalid tscMaxRssMb value {s:?}: {e}")),
+                    }
+                }
+            },
+        });
+        if val.is_empty() {
+            None
+        } else {
+            Some(val.parse::<i32>().unwrap())
+        }
+    };
+
     let typescript_compiler_variant = {
         let variant_str = cake.resolve_string(ResolveStringArgs {
             env: "TYPESCRIPT_COMPILER_VARIANT",
@@ -340,51 +519,14 @@ pub fn init_settings(cake: &mut LayerCake) -> Settings {
             .parse::<TypeScriptCompilerVariant>()
             .unwrap_or_else(|_| TypeScriptCompilerVariant::default())
     };
-    let max_nodes = cake.resolve_number(ResolveNumberArgs {
-        env: "MAX_NODES",
-        flag: "--max-nodes",
-        file: "settings.maxNodes",
-        default: || Settings::default().max_nodes,
-        validate: |n| {
-            if *n <= 10_000_000 {
-                Ok(*n)
-            } else {
-                Err("maxNodes must not exceed 10,000,000".to_string())
-            }
-        },
-    });
-
-    let disable_analytics = cake.resolve_bool(ResolveBoolArgs {
-        env: "DISABLE_ANALYTICS",
-        flag: "--disable-analytics",
-        file: "settings.disableAnalytics",
-        default: || false,
-    });
-
-    let analytics_consent = if disable_analytics {
-        Vec::new()
-    } else {
-        cake.resolve_array_of_strings(ResolveArrayOfStringsArgs {
-            env: "ANALYTICS_CONSENT",
-            flag: "--analytics-consent",
-            file: "settings.analyticsConsent",
-            default: || Settings::default().analytics_consent,
-            validate: |items| Ok(items.to_vec()),
-        })
-    };
 
-    Settings {
-        relative_paths,
-        prefer_editor_open,
-        preferred_editor,
-        extra_tsc_flags,
-        apply_tsc_project_flag,
-        max_old_space_size,
-        max_stack_size,
-        typescript_compiler_variant,
-        max_nodes,
-        analytics_consent,
-    }
+    settings.extra_tsc_flags = extra_tsc_flags;
+    settings.apply_tsc_project_flag = apply_tsc_project_flag;
+    settings.max_old_space_size = max_old_space_size;
+    settings.max_stack_size = max_stack_size;
+    settings.tsc_timeout_seconds = tsc_timeout_seconds;
+    settings.tsc_max_rss_mb = tsc_max_rss_mb;
+    settings.typescript_compiler_variant = typescript_compiler_variant;
 }
 
 pub fn init_verbose(cake: &mut LayerCake) -> bool {
@@ -396,8 +538,28 @@ pub fn init_verbose(cake: &mut LayerCake) -> bool {
     })
 }
 
+/// Port for the MCP streamable HTTP transport. `0` disables it.
+pub fn init_mcp_http_port(cake: &mut LayerCake) -> u16 {
+    let port = cake.resolve_number(ResolveNumberArgs {
+        env: "MCP_HTTP_PORT",
+        flag: "--mcp-http-port",
+        file: "mcp_http_port",
+        default: || DEFAULT_MCP_HTTP_PORT as i32,
+        validate: |n| {
+            if (0..=u16::MAX as i32).contains(n) {
+                Ok(*n)
+            } else {
+                Err(format!("mcp_http_port {n} is not a valid port"))
+            }
+        },
+    });
+    port as u16
+}
+
+/// A relative `tsconfig` (as a project-level typeslayer.toml would have it) is relative to `project_root`.
 pub fn init_selected_tsconfig_with(
     cake: &mut LayerCake,
+    project_root: &Path,
     tsconfig_paths: &[PathBuf],
 ) -> Option<PathBuf> {
     let auto_detect = || {
@@ -422,11 +584,15 @@ pub fn init_selected_tsconfig_with(
         file: "settings.tsconfig",
         default: auto_detect,
         validate: |s| {
-            if s.is_empty() || tsconfig_paths.iter().any(|p| p.to_string_lossy() == s) {
-                Ok(s.to_string())
-            } else {
-                Err(format!("tsconfig {s:?} not found in discovered paths"))
+            if s.is_empty() {
+                return Ok(s.to_string());
             }
+            let wanted = project_root.join(s);
+            tsconfig_paths
+                .iter()
+                .find(|p| p.to_string_lossy() == s || **p == wanted)
+                .map(|p| p.to_string_lossy().to_string())
+                .ok_or_else(|| format!("tsconfig {s:?} not found in discovered paths"))
         },
     });
 
@@ -1,40 +1,53 @@
 pub mod command;
+pub mod config_schema;
 pub mod init;
+pub mod package_manager;
+pub mod project_config;
 pub mod settings;
+pub mod workspaces;
 
 use crate::{
     analytics::{
         TypeSlayerEvent,
         event_app_started_success::{EventAppStartedSuccess, EventAppStartedSuccessArgs},
     },
-    analyze_trace::{AnalyzeTraceResult, constants::ANALYZE_TRACE_FILENAME},
+    analyze_trace::{AnalyzeTraceOptions, AnalyzeTraceResult, constants::ANALYZE_TRACE_FILENAME},
     app_data::{
-        command::{PackageJSON, PackageManager, TSCCommand},
+        command::{PackageManager, TSCCommand},
+        config_schema::{CONFIG_SCHEMA_DIRECTIVE, CONFIG_SCHEMA_FILENAME, config_schema},
         init::{
-            init_analyze_trace, init_cpu_profile, init_project_root, init_selected_tsconfig_with,
-            init_session_id, init_settings, init_trace_json, init_type_graph, init_types_json,
-            init_verbose, init_version_and_maybe_clear_outputs,
+            init_analyze_trace, init_cpu_profile, init_effective_tsconfig, init_mcp_http_port,
+            init_project_root, init_project_settings, init_run_metadata,
+            init_selected_tsconfig_with, init_session_id, init_settings, init_trace_json,
+            init_type_graph, init_types_json, init_verbose, init_version, migrate_legacy_outputs,
         },
+        package_manager::{DetectedPackageManager, detect_package_manager},
+        project_config::{Budgets, init_budgets, init_thresholds, is_project_key},
         settings::Settings,
+        workspaces::{RetentionPolicy, WorkspaceRegistry, workspace_id, workspace_outputs_dir},
     },
+    commands::tasks::TaskId,
+    cpu_profile::CpuProfileAnalysis,
+    crash::CrashDiagnosis,
     layercake::{LayerCake, LayerCakeInitArgs, Source},
-    process_controller::{CommandOutput, ProcessController},
-    type_graph::{TYPE_GRAPH_FILENAME, TypeGraph},
-    utils::{
-        CONFIG_FILENAME, OUTPUTS_DIRECTORY, TSCONFIG_FILENAME, file_mtime_iso, get_platform,
-        quote_if_needed,
+    process_controller::{CommandOutput, ProcessController, ProgressReporter, RunLimits},
+    run_metadata::RunMetadata,
+    treemap::{TreemapNode, build_treemap_from_trace},
+    tsconfig::{
+        discover::{TsconfigNode, discover_tsconfigs},
+        resolve::{EffectiveTsconfig, resolve_tsconfig},
     },
+    type_graph::{TYPE_GRAPH_FILENAME, TypeGraph},
+    utils::{CONFIG_FILENAME, TSCONFIG_FILENAME, file_mtime_iso, get_platform, quote_if_needed},
     validate::{
-        trace_json::{TRACE_JSON_FILENAME, TraceEvent},
+        trace_json::{ParseWarnings, TRACE_JSON_FILENAME, TraceEvent},
         types_json::{TYPES_JSON_FILENAME, TypesJsonSchema},
         utils::CPU_PROFILE_FILENAME,
     },
 };
 use serde::{Deserialize, Serialize};
-use std::{cmp::Ordering, path::PathBuf};
-use std::{fs::File, io::BufReader};
+use std::{path::PathBuf, time::Duration};
 use tokio::fs;
-use tokio::process::Command;
 use tracing::{debug, error, info};
 use ts_rs::TS;
 
@@ -51,46 +64,66 @@ pub struct AppData {
     pub project_root: PathBuf,
     pub types_json: TypesJsonSchema,
     pub trace_json: Vec<TraceEvent>,
+    /// What parsing `trace_json` couldn't fully read
+    pub trace_warnings: ParseWarnings,
+    /// Check time per file, built from `trace_json` on first use; set both with [`AppData::set_trace_json`]
+    pub treemap: Option<Vec<TreemapNode>>,
     pub analyze_trace: Option<AnalyzeTraceResult>,
     pub cpu_profile: Option<String>,
+    /// Parsed from `cpu_profile` on first use; set both with [`AppData::set_cpu_profile`]
+    pub cpu_profile_analysis: Option<CpuProfileAnalysis>,
     pub tsconfig_paths: Vec<PathBuf>,
+    /// The discovered tsconfigs arranged by project `references`, with their owning packages
+    pub tsconfig_tree: Vec<TsconfigNode>,
     pub selected_tsconfig: Option<PathBuf>,
-    pub package_manager: PackageManager,
+    pub package_manager: DetectedPackageManager,
     pub settings: Settings,
+    /// What `analyze_trace` counts as a hot spot, unless a caller passes its own options
+    pub thresholds: AnalyzeTraceOptions,
+    pub budgets: Budgets,
     pub verbose: bool,
     pub cake: LayerCake,
     pub type_graph: Option<TypeGraph>,
+    /// The resolved tsconfig the loaded outputs were generated with
+    pub effective_tsconfig: Option<EffectiveTsconfig>,
+    /// The tsc run the loaded outputs came from
+    pub run_metadata: Option<RunMetadata>,
+    /// What the last trace generation was doing when it crashed, until one succeeds
+    pub crash_diagnosis: Option<CrashDiagnosis>,
     pub data_dir: PathBuf,
     pub platform: String,
     pub version: String,
     pub session_id: String,
     pub mode: AppMode,
+    /// Port for the MCP streamable HTTP transport in GUI mode (`0` when disabled).
+    pub mcp_http_port: u16,
+    /// The workspace (project root + tsconfig) whose outputs are currently loaded
+    pub workspace_id: String,
 }
 
 impl AppData {
     pub async fn new(data_dir: PathBuf, mode: AppMode) -> Result<Self, String> {
         info!("[AppData::new] using base data_dir: {}", data_dir.display());
-        let version = init_version_and_maybe_clear_outputs(&data_dir).await?;
+        let version = init_version(&data_dir).await?;
 
         // Build a single LayerCake and reuse it across init functions
         let mut cake = LayerCake::new(LayerCakeInitArgs {
             config_filename: CONFIG_FILENAME,
-            precedence: [Source::Env, Source::Flag, Source::File],
+            precedence: [Source::Env, Source::Flag, Source::Project, Source::File],
             env_prefix: "TYPESLAYER_",
         });
         cake.load_config_in_dir(data_dir.to_string_lossy().to_string())
             .await?;
 
         let project_root = init_project_root(&mut cake);
-        let outputs_dir = data_dir.join(OUTPUTS_DIRECTORY);
-        let types_json = init_types_json(&outputs_dir, &project_root).await;
-        let trace_json = init_trace_json(&outputs_dir, &project_root).await;
-        let analyze_trace = init_analyze_trace(&outputs_dir).await;
-        let type_graph = init_type_graph(&outputs_dir).await;
-        let cpu_profile = init_cpu_profile(&outputs_dir).await;
+        cake.load_project_config(&project_root, is_project_key)
+            .await?;
         let settings = init_settings(&mut cake);
+        let thresholds = init_thresholds(&mut cake);
+        let budgets = init_budgets(&mut cake);
         let verbose = init_verbose(&mut cake);
         let session_id = init_session_id(&mut cake);
+        let mcp_http_port = init_mcp_http_port(&mut cake);
 
         let package_manager = Self::find_package_manager(project_root.clone()).await?;
 
@@ -99,37 +132,162 @@ impl AppData {
         let mut app = Self {
             project_root,
             package_manager,
-            types_json,
-            trace_json,
-            analyze_trace,
-            cpu_profile,
+            types_json: Vec::new(),
+            trace_json: Vec::new(),
+            trace_warnings: ParseWarnings::default(),
+            treemap: None,
+            analyze_trace: None,
+            crash_diagnosis: None,
+            cpu_profile: None,
+            cpu_profile_analysis: None,
             tsconfig_paths: Vec::new(),
+            tsconfig_tree: Vec::new(),
             selected_tsconfig: None,
             settings,
+            thresholds,
+            budgets,
             verbose,
             cake,
-            type_graph,
+            type_graph: None,
+            effective_tsconfig: None,
+            run_metadata: None,
             data_dir,
             platform,
             version,
             session_id,
             mode,
+            mcp_http_port,
+            workspace_id: String::new(),
         };
         app.discover_tsconfigs().await?;
-        app.selected_tsconfig = init_selected_tsconfig_with(&mut app.cake, &app.tsconfig_paths);
+        app.selected_tsconfig =
+            init_selected_tsconfig_with(&mut app.cake, &app.project_root, &app.tsconfig_paths);
+
+        let startup_workspace = workspace_id(&app.project_root, app.selected_tsconfig.as_deref());
+        migrate_legacy_outputs(
+            &app.data_dir,
+            &workspace_outputs_dir(&app.data_dir, &startup_workspace),
+        )
+        .await;
+        app.activate_workspace().await?;
+
         app.update_typeslayer_config_toml().await;
         EventAppStartedSuccess::send(&app, EventAppStartedSuccessArgs {}).await;
         Ok(app)
     }
 
     pub fn outputs_dir(&self) -> PathBuf {
-        self.data_dir.join(OUTPUTS_DIRECTORY)
+        workspace_outputs_dir(&self.data_dir, &self.workspace_id)
+    }
+
+    /// A copy that runs tsc against another checkout of this project (e.g. a git worktree),
+    /// writing outputs under `data_dir`. None of the loaded outputs are carried over.
+    pub fn detached(&self, project_root: PathBuf, data_dir: PathBuf) -> AppData {
+        let rebase = |path: &PathBuf| match path.strip_prefix(&self.project_root) {
+            Ok(relative) => project_root.join(relative),
+            Err(_) => path.clone(),
+        };
+        AppData {
+            tsconfig_paths: self.tsconfig_paths.iter().map(rebase).collect(),
+            tsconfig_tree: Vec::new(),
+            selected_tsconfig: self.selected_tsconfig.as_ref().map(rebase),
+            project_root,
+            types_json: Vec::new(),
+            trace_json: Vec::new(),
+            trace_warnings: ParseWarnings::default(),
+            treemap: None,
+            analyze_trace: None,
+            crash_diagnosis: None,
+            cpu_profile: None,
+            cpu_profile_analysis: None,
+            package_manager: self.package_manager.clone(),
+            settings: self.settings.clone(),
+            thresholds: self.thresholds.clone(),
+            budgets: self.budgets.clone(),
+            verbose: self.verbose,
+            cake: self.cake.clone(),
+            type_graph: None,
+            effective_tsconfig: None,
+            run_metadata: None,
+            data_dir,
+            platform: self.platform.clone(),
+            version: self.version.clone(),
+            session_id: self.session_id.clone(),
+            mode: self.mode.clone(),
+            mcp_http_port: self.mcp_http_port,
+            workspace_id: self.workspace_id.clone(),
+        }
+    }
+
+    pub fn retention_policy(&self) -> RetentionPolicy {
+        RetentionPolicy {
+            max_workspaces: self.settings.max_workspaces.max(0) as usize,
+            retention_days: self.settings.workspace_retention_days.max(0) as i64,
+            max_snapshots: self.settings.max_snapshots.max(0) as usize,
+        }
+    }
+
+    /// Point outputs at the workspace for the current project root + tsconfig, registering it if
+    /// it's new and loading whatever it already has. Also applies the retention policy.
+    pub async fn activate_workspace(&mut self) -> Result<(), String> {
+        let mut registry = WorkspaceRegistry::load(&self.data_dir).await;
+        let id = registry.touch(
+            &self.project_root,
+            self.selected_tsconfig.as_deref(),
+            &self.version,
+        );
+        registry
+            .prune(&self.data_dir, self.retention_policy(), &id)
+            .await?;
+        registry.save(&self.data_dir).await?;
+
+        if id != self.workspace_id {
+            info!("[activate_workspace] switching to workspace {id}");
+            self.workspace_id = id;
+            self.load_outputs().await;
+        }
+        Ok(())
+    }
+
+    /// Replace the in-memory outputs with whatever the current workspace has on disk.
+    pub async fn load_outputs(&mut self) {
+        let outputs_dir = self.outputs_dir();
+        self.types_json = init_types_json(&outputs_dir, &self.project_root).await;
+        let (trace, warnings) = init_trace_json(&outputs_dir, &self.project_root).await;
+        self.set_trace_json(trace, warnings);
+        self.analyze_trace = init_analyze_trace(&outputs_dir).await;
+        self.type_graph = init_type_graph(&outputs_dir).await;
+        self.set_cpu_profile(init_cpu_profile(&outputs_dir).await);
+        self.effective_tsconfig = init_effective_tsconfig(&outputs_dir).await;
+        self.run_metadata = init_run_metadata(&outputs_dir).await;
+    }
+
+    /// Replace the trace, dropping the treemap of the old one.
+    pub fn set_trace_json(&mut self, trace: Vec<TraceEvent>, warnings: ParseWarnings) {
+        self.trace_json = trace;
+        self.trace_warnings = warnings;
+        self.treemap = None;
+    }
+
+    /// The loaded trace's check time per file, slowest first, built the first time it's needed.
+    pub fn ensure_treemap(&mut self) -> Result<&[TreemapNode], String> {
+        if self.treemap.is_none() {
+            self.treemap = Some(build_treemap_from_trace(&self.trace_json)?);
+        }
+        Ok(self.treemap.as_deref().unwrap_or_default())
+    }
+
+    /// Replace the raw CPU profile, dropping the analysis of the old one.
+    pub fn set_cpu_profile(&mut self, contents: Option<String>) {
+        self.cpu_profile = contents;
+        self.cpu_profile_analysis = None;
     }
 
     pub async fn set_project_root(&mut self, new_root: PathBuf) -> Result<(), String> {
         if !new_root.exists() {
             self.selected_tsconfig = None;
             self.tsconfig_paths = vec![];
+            self.tsconfig_tree = vec![];
             self.project_root = PathBuf::from(".");
             return Err(format!(
                 "project_root path does not exist: {}",
@@ -138,6 +296,7 @@ impl AppData {
         }
 
         self.project_root = new_root;
+        self.load_project_config().await?;
 
         let package_manager = Self::find_package_manager(self.project_root.clone()).await?;
         self.package_manager = package_manager;
@@ -146,119 +305,143 @@ impl AppData {
 
         self.discover_tsconfigs().await?;
 
-        // Try to keep previous selection if it still exists
-        if let Some(prev) = previous_selection
-            && self.tsconfig_paths.contains(&prev)
-        {
-            self.selected_tsconfig = Some(prev);
-            return Ok(());
-        }
+        // Try to keep previous selection if it still exists (and the project doesn't pick one),
+        // otherwise auto-detect
+        self.selected_tsconfig = match previous_selection {
+            Some(prev)
+                if self.tsconfig_paths.contains(&prev)
+                    && !self.cake.project_sets("settings.tsconfig") =>
+            {
+                Some(prev)
+            }
+            _ => init_selected_tsconfig_with(
+                &mut self.cake,
+                &self.project_root,
+                &self.tsconfig_paths,
+            ),
+        };
 
-        // Otherwise, auto-detect
-        self.selected_tsconfig = init_selected_tsconfig_with(&mut self.cake, &self.tsconfig_paths);
+        self.activate_workspace().await?;
+        self.update_typeslayer_config_toml().await;
 
         Ok(())
     }
 
-    pub async fn discover_tsconfigs(&mut self) -> Result<(), String> {
-        self.tsconfig_paths.clear();
+    /// Load the project-level typeslayer.toml for the current project root, and resolve again
+    /// everything it may set. The global config is reread too, since it's rewritten as settings change.
+    pub async fn load_project_config(&mut self) -> Result<(), String> {
+        self.cake
+            .load_config_in_dir(self.data_dir.to_string_lossy().to_string())
+            .await?;
+        self.cake
+            .load_project_config(&self.project_root, is_project_key)
+            .await?;
+        init_project_settings(&mut self.cake, &mut self.settings);
+        self.thresholds = init_thresholds(&mut self.cake);
+        self.budgets = init_budgets(&mut self.cake);
+        Ok(())
+    }
 
-        // Search for tsconfig*.json files in the project directory and subdirectories
-        let mut entries = fs::read_dir(&self.project_root)
-            .await
-            .map_err(|e| format!("Could not read dir {e}"))?;
-        loop {
-            let entry = entries
-                .next_entry()
+    /// Find every tsconfig under the project root, including those of monorepo packages and
+    /// anything reachable through `references` or `extends`.
+    pub async fn discover_tsconfigs(&mut self) -> Result<(), String> {
+        let project_root = self.project_root.clone();
+        let discovery =
+            tauri::async_runtime::spawn_blocking(move || discover_tsconfigs(&project_root))
                 .await
-                .map_err(|e| format!("Could not read dir {e}"))?;
-            let Some(entry) = entry else {
-                break;
-            };
+                .map_err(|e| e.to_string())??;
+        self.tsconfig_paths = discovery.paths;
+        self.tsconfig_tree = discovery.tree;
+        Ok(())
+    }
 
-            if let Ok(file_name) = entry.file_name().into_string()
-                && file_name.starts_with("tsconfig")
-                && file_name.ends_with(".json")
-            {
-                self.tsconfig_paths.push(entry.path());
-            }
+    /// The config tsc will read: the selected tsconfig when it's passed with `--project`,
+    /// otherwise the `tsconfig.json` tsc finds in the project root by itself.
+    pub fn tsc_tsconfig_path(&self) -> Option<PathBuf> {
+        if self.settings.apply_tsc_project_flag
+            && let Some(tsconfig) = &self.selected_tsconfig
+        {
+            return Some(tsconfig.clone());
         }
+        Some(self.project_root.join(TSCONFIG_FILENAME)).filter(|path| path.is_file())
+    }
 
-        // Sort with tsconfig.json first
-        self.tsconfig_paths.sort_by(|a, b| {
-            let a_is_main = a.file_name().is_some_and(|name| name == TSCONFIG_FILENAME);
-            let b_is_main = b.file_name().is_some_and(|name| name == TSCONFIG_FILENAME);
-            match (a_is_main, b_is_main) {
-                (true, false) => Ordering::Less,
-                (false, true) => Ordering::Greater,
-                _ => a.cmp(b),
-            }
-        });
-
-        Ok(())
+    /// Resolve the config tsc will read, with the extra tsc flags applied.
+    pub async fn resolve_effective_tsconfig(&self) -> Result<EffectiveTsconfig, String> {
+        let path = self.tsc_tsconfig_path();
+        let flags = self.settings.extra_tsc_flags.clone();
+        tauri::async_runtime::spawn_blocking(move || resolve_tsconfig(path.as_deref(), &flags))
+            .await
+            .map_err(|e| e.to_string())?
     }
 
     pub fn get_tsc_call(&self, user_flags: &str) -> TSCCommand {
-        let shell;
-        let shell_arg;
-        if cfg!(target_os = "windows") {
-            shell = "cmd".to_string();
-            shell_arg = "/c";
-        } else {
-            shell = "sh".to_string();
-            shell_arg = "-c";
-        };
-
         let mut args = vec![];
-        match self.package_manager {
-            // Yarn PNP may be enabled which puts dependencies in a .zip file.
-            // `require` is patched by Yarn to support this.
-            // This means it's critically important Node is run through Yarn.
-            PackageManager::Yarn => args.extend(["yarn", "node"]),
-
-            // Everything else is unlikely to be different but better safe than sorry.
-            PackageManager::PNPM => args.extend(["pnpm", "exec", "node"]),
-            PackageManager::Bun => args.extend(["bun", "run", "node"]),
-            PackageManager::NPM => args.extend(["node"]),
-        };
+        let compiler_variant = self.settings.typescript_compiler_variant;
 
-        // Only here to satisfy lifetimes. Super let when?
-        let max_stack_size_string: String;
-        if let Some(size) = self.settings.max_stack_size {
-            // important: if you don't use the equals sign thingy you'll get
-            //   Error: illegal value for flag --stack-size of type int
-            max_stack_size_string = format!("--stack-size={size}");
-            args.push(&max_stack_size_string);
-        }
+        // important: if you don't use the equals sign thingy you'll get
+        //   Error: illegal value for flag --stack-size of type int
+        //   Error: illegal value for flag --max-old-space-size of type size_t
+        let v8_flags: Vec<String> = [
+            self.settings
+                .max_stack_size
+                .map(|size| format!("--stack-size={size}")),
+            self.settings
+                .max_old_space_size
+                .map(|size| format!("--max-old-space-size={size}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
 
         // Only here to satisfy lifetimes. Super let when?
-        let max_old_space_size_string: String;
-        if let Some(max_old_space_size) = self.settings.max_old_space_size {
-            // important: if you don't use the equals sign thingy you'll get
-            //   Error: illegal value for flag --max-old-space-size of type size_t
-            max_old_space_size_string = format!("--max-old-space-size={max_old_space_size}");
-            args.push(&max_old_space_size_string);
+        let deno_v8_flags: String;
+        let compiler_entry: String;
+        if let PackageManager::Deno = self.package_manager.kind {
+            // Deno runs a package's bin script directly, which leaves `process.argv` the way tsc
+            // expects, so neither `--eval` nor the dummy arg below are needed.
+            args.extend(["deno", "run", "--allow-all"]);
+            if !v8_flags.is_empty() {
+                deno_v8_flags = format!("--v8-flags={}", v8_flags.join(","));
+                args.push(&deno_v8_flags);
+            }
+            compiler_entry = format!(
+                "npm:{}/bin/{}",
+                compiler_variant.npm_package(),
+                compiler_variant.as_str()
+            );
+            args.push(&compiler_entry);
+        } else {
+            match self.package_manager.kind {
+                // Yarn PNP may be enabled which puts dependencies in a .zip file.
+                // `require` is patched by Yarn to support this.
+                // This means it's critically important Node is run through Yarn.
+                PackageManager::Yarn => args.extend(["yarn", "node"]),
+
+                // Everything else is unlikely to be different but better safe than sorry.
+                PackageManager::PNPM => args.extend(["pnpm", "exec", "node"]),
+                PackageManager::Bun => args.extend(["bun", "run", "node"]),
+                PackageManager::NPM | PackageManager::Deno => args.extend(["node"]),
+            };
+            args.extend(v8_flags.iter().map(String::as_str));
+
+            args.push("--eval");
+
+            compiler_entry = format!(
+                r#""require('{}/bin/{}')""#,
+                compiler_variant.npm_package(),
+                compiler_variant.as_str()
+            );
+            args.push(&compiler_entry);
+
+            // This "dummy-arg" `slay-gurrrl-slay` is seemingly unavoidable for three reasons:
+            // 1) TypeScript itself expects to be called as `node path/to/tsc.js` which results in the first two args to `process.argv` being set to something like `["path/to-node", "path/to/tsc.js", ...]`.
+            //    Therefore it ignores the first two args but when `node --eval` is called `process.argv` would ordinarily be set to just `["path/to/node", ...]`.
+            // 2) Something like `node --eval 'require("typescript/bin/tsc")' --project ...` will interpret `--project` as a flag to Node and error with "node: bad option: --project".
+            // 3) The `--` pattern that is more idiomatic is unfortunately always completely stripped by `yarn node` in 1.0, even if there are multiple. Though even if it didn't it'd need to be `-- --` because Node will strip the first `--`, correctly, as that indicates the end of its args leaving TypeScript still needing a dummy arg.
+            args.push("slay-gurrrl-slay");
         }
 
-        args.push("--eval");
-
-        let compiler_variant = self.settings.typescript_compiler_variant;
-
-        // Only here to satisfy lifetimes. Super let when?
-        let compiler_require = format!(
-            r#""require('{}/bin/{}')""#,
-            compiler_variant.npm_package(),
-            compiler_variant.as_str()
-        );
-        args.push(&compiler_require);
-
-        // This "dummy-arg" `slay-gurrrl-slay` is seemingly unavoidable for three reasons:
-        // 1) TypeScript itself expects to be called as `node path/to/tsc.js` which results in the first two args to `process.argv` being set to something like `["path/to-node", "path/to/tsc.js", ...]`.
-        //    Therefore it ignores the first two args but when `node --eval` is called `process.argv` would ordinarily be set to just `["path/to/node", ...]`.
-        // 2) Something like `node --eval 'require("typescript/bin/tsc")' --project ...` will interpret `--project` as a flag to Node and error with "node: bad option: --project".
-        // 3) The `--` pattern that is more idiomatic is unfortunately always completely stripped by `yarn node` in 1.0, even if there are multiple. Though even if it didn't it'd need to be `-- --` because Node will strip the first `--`, correctly, as that indicates the end of its args leaving TypeScript still needing a dummy arg.
-        args.push("slay-gurrrl-slay");
-
         if !self.settings.extra_tsc_flags.is_empty() {
             args.push(&self.settings.extra_tsc_flags);
         }
@@ -278,11 +461,7 @@ impl AppData {
             args.push(&tsconfig_string);
         }
 
-        TSCCommand {
-            shell,
-            shell_arg: shell_arg.to_string(),
-            command: args.join(" "),
-        }
+        TSCCommand::in_shell(args.join(" "))
     }
 
     pub fn get_example_tsc_call(&self) -> String {
@@ -291,10 +470,15 @@ impl AppData {
         self.get_tsc_call(&flag).to_string()
     }
 
+    /// Run tsc for `task_id`, enforcing the configured timeout and memory limit.
+    ///
+    /// A run that was killed still returns its output, with `CommandOutput::killed` set.
     pub async fn call_typescript(
         &self,
         process_controller: &ProcessController,
+        task_id: TaskId,
         flag: String,
+        reporter: &dyn ProgressReporter,
     ) -> Result<CommandOutput, String> {
         let outputs_dir = self.outputs_dir().to_string_lossy().to_string();
 
@@ -310,30 +494,21 @@ impl AppData {
         info!("[call_typescript] Working directory: {:?}", cwd);
         info!("[call_typescript] Outputs directory: {}", outputs_dir);
 
-        let mut cmd = Command::new(tsc_command.shell);
-        cmd.arg(tsc_command.shell_arg);
-
-        // raw_arg makes sure a command like `npx tsc --project "foo bar"` is interpreted by the shell.
-        // The default with `.arg`, on Windows, is more like `npx tsc --project "\"foo bar\""` but shell quoting is more complicated.
-        #[cfg(target_os = "windows")]
-        {
-            cmd.raw_arg(format!("\"{}\"", tsc_command.command));
-            // Set CREATE_NO_WINDOW https://learn.microsoft.com/en-us/windows/win32/procthread/process-creation-flags
-            cmd.creation_flags(0x08000000);
-        }
-
-        #[cfg(not(target_os = "windows"))]
-        cmd.arg(tsc_command.command);
+        let cmd = tsc_command.to_command(cwd);
 
-        cmd.current_dir(cwd);
-
-        let output = process_controller.run_command(cmd).await?;
-
-        let Some(output) = output else {
-            return Err("Command canceled".to_string());
-        };
+        process_controller
+            .run_command(cmd, task_id, self.run_limits(), reporter)
+            .await
+    }
 
-        Ok(output)
+    pub fn run_limits(&self) -> RunLimits {
+        RunLimits {
+            timeout: self
+                .settings
+                .tsc_timeout_seconds
+                .map(|secs| Duration::from_secs(secs.max(0) as u64)),
+            max_rss_mb: self.settings.tsc_max_rss_mb.map(|mb| mb.max(0) as u64),
+        }
     }
 }
 
@@ -351,6 +526,7 @@ struct TypeSlayerConfig<'a> {
     project_root: &'a str,
     outputs_dir: &'a str,
     verbose: bool,
+    mcp_http_port: u16,
     platform: &'a str,
     version: &'a str,
     settings: &'a Settings,
@@ -380,18 +556,45 @@ impl AppData {
             project_root: &self.project_root.to_string_lossy(),
             outputs_dir: &outputs_dir.to_string_lossy(),
             verbose: self.verbose,
+            mcp_http_port: self.mcp_http_port,
             platform: &self.platform,
             version: &self.version,
             settings: &self.settings,
             outputs,
         };
 
-        match toml::to_string(&cfg) {
+        let cfg = toml::Value::try_from(&cfg).map(|mut cfg| {
+            // what a project's own typeslayer.toml set stays in that file
+            self.cake.restore_file_values(&mut cfg);
+            // these are only ever set by hand, so keep whatever is there
+            for table in ["thresholds", "budgets"] {
+                if let (Some(cfg), Some(value)) = (
+                    cfg.as_table_mut(),
+                    self.cake.cfg.as_ref().and_then(|global| global.get(table)),
+                ) {
+                    cfg.insert(table.to_string(), value.clone());
+                }
+            }
+            cfg
+        });
+        match cfg.and_then(|cfg| toml::to_string(&cfg)) {
             Ok(s) => {
                 if let Err(e) = fs::create_dir_all(&outputs_dir).await {
                     error!("Failed to create temp dir for config: {}", e);
                     return;
                 }
+                // lets editors validate the file, and users find the keys they can set
+                let schema_path = data_dir.join(CONFIG_SCHEMA_FILENAME);
+                if let Ok(schema) = serde_json::to_string_pretty(&config_schema())
+                    && let Err(e) = fs::write(&schema_path, schema).await
+                {
+                    error!(
+                        "Failed to write config schema {}: {}",
+                        schema_path.display(),
+                        e
+                    );
+                }
+                let s = format!("{CONFIG_SCHEMA_DIRECTIVE}\n{s}");
                 if let Err(e) = fs::write(&config_path, s).await {
                     error!(
                         "Failed to write config file {}: {}",
@@ -441,10 +644,12 @@ impl AppData {
         }
 
         self.types_json.clear();
-        self.trace_json.clear();
+        self.set_trace_json(Vec::new(), ParseWarnings::default());
         self.analyze_trace = None;
-        self.cpu_profile = None;
+        self.crash_diagnosis = None;
+        self.set_cpu_profile(None);
         self.type_graph = None;
+        self.run_metadata = None;
         debug!(
             "[clear_outputs_dir] Cleared outputs directory: {}",
             outputs_dir.display()
@@ -453,57 +658,17 @@ impl AppData {
         Ok(())
     }
 
-    pub async fn find_package_manager(project_root: PathBuf) -> Result<PackageManager, String> {
-        tauri::async_runtime::spawn_blocking(move || {
-            let package_json_path = project_root.join("package.json");
-
-            // If package.json doesn't exist, default to npm
-            let package_json = match File::open(&package_json_path) {
-                Ok(file) => file,
-                Err(_) => {
-                    info!(
-                        "[find_package_manager] No package.json found at {}, defaulting to npm",
-                        package_json_path.display()
-                    );
-                    return Ok(PackageManager::NPM);
-                }
-            };
-
-            let package_json: PackageJSON =
-                serde_json::from_reader(BufReader::new(package_json))
-                    .map_err(|e| format!("Failed to parse package.json: {e}"))?;
-
-            match package_json.package_manager {
-                Some(package_manager) => {
-                    let package_manager = package_manager
-                        .split("@")
-                        .next()
-                        .unwrap_or(&package_manager);
-                    let result = match package_manager {
-                        "npm" => Ok(PackageManager::NPM),
-                        "pnpm" => Ok(PackageManager::PNPM),
-                        "yarn" => Ok(PackageManager::Yarn),
-                        "bun" => Ok(PackageManager::Bun),
-                        _ => Err(format!("Unsupported packageManager {package_manager}")),
-                    };
-                    debug!(
-                        "[find_package_manager] Detected packageManager '{}' in {:?}, using {:?}",
-                        package_manager,
-                        package_json_path,
-                        result.as_ref().ok()
-                    );
-                    result
-                }
-                None => {
-                    info!(
-                        "[find_package_manager] No packageManager field in {}, defaulting to npm",
-                        package_json_path.display()
-                    );
-                    Ok(PackageManager::NPM)
-                }
-            }
-        })
-        .await
-        .map_err(|e| format!("could not run thread: {e}"))?
+    pub async fn find_package_manager(
+        project_root: PathBuf,
+    ) -> Result<DetectedPackageManager, String> {
+        let detected =
+            tauri::async_runtime::spawn_blocking(move || detect_package_manager(&project_root))
+                .await
+                .map_err(|e| format!("could not run thread: {e}"))?;
+        debug!(
+            "[find_package_manager] using {:?} (yarn berry: {}, pnp: {}) from {:?}",
+            detected.kind, detected.yarn_berry, detected.yarn_pnp, detected.evidence
+        );
+        Ok(detected)
     }
 }
@@ -0,0 +1,278 @@
+use crate::app_data::command::{PackageJSON, PackageManager};
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::{info, warn};
+
+/// Lockfiles in the order they win when a directory has more than one.
+const LOCKFILES: [(&str, PackageManager); 7] = [
+    ("pnpm-lock.yaml", PackageManager::PNPM),
+    ("yarn.lock", PackageManager::Yarn),
+    ("bun.lock", PackageManager::Bun),
+    ("bun.lockb", PackageManager::Bun),
+    ("deno.lock", PackageManager::Deno),
+    ("package-lock.json", PackageManager::NPM),
+    ("npm-shrinkwrap.json", PackageManager::NPM),
+];
+
+/// One thing found on disk that informed the detection.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectionEvidence {
+    pub path: PathBuf,
+    pub finding: String,
+}
+
+/// The package manager a project is installed with, and how that was decided.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedPackageManager {
+    pub kind: PackageManager,
+    /// From the `packageManager` field, e.g. `4.1.0` for `yarn@4.1.0+sha512...`
+    pub version: Option<String>,
+    /// Yarn 2+ (berry) rather than yarn 1 (classic)
+    pub yarn_berry: bool,
+    /// Yarn Plug'n'Play: there's no node_modules, so node only resolves packages when run through yarn
+    pub yarn_pnp: bool,
+    /// The directory the deciding `packageManager` field or lockfile is in
+    pub root: Option<PathBuf>,
+    pub evidence: Vec<DetectionEvidence>,
+}
+
+impl DetectedPackageManager {
+    pub fn npm() -> Self {
+        Self {
+            kind: PackageManager::NPM,
+            version: None,
+            yarn_berry: false,
+            yarn_pnp: false,
+            root: None,
+            evidence: Vec::new(),
+        }
+    }
+
+    /// Installs exactly what the lockfile describes
+    pub fn install_command(&self) -> &'static str {
+        match self.kind {
+            PackageManager::Bun => "bun install --frozen-lockfile",
+            PackageManager::Deno => "deno install --frozen",
+            PackageManager::NPM => "npm ci",
+            PackageManager::PNPM => "pnpm install --frozen-lockfile",
+            PackageManager::Yarn if self.yarn_berry => "yarn install --immutable",
+            PackageManager::Yarn => "yarn install --frozen-lockfile",
+        }
+    }
+}
+
+/// Detect the package manager for `project_root`.
+///
+/// Each directory from `project_root` up is checked for a `packageManager` field, then for a
+/// lockfile, stopping at the first hit. The search also stops at the workspace root (a
+/// package.json with `workspaces`, or a pnpm-workspace.yaml) or the repository root, since a
+/// lockfile above those belongs to some other project. Nothing found means npm.
+///
+/// Never fails: an unrecognized or unreadable value is recorded as evidence and skipped.
+pub fn detect_package_manager(project_root: &Path) -> DetectedPackageManager {
+    let mut evidence = Vec::new();
+
+    for dir in project_root.ancestors() {
+        let package_json_path = dir.join("package.json");
+        let package_json = read_package_json(&package_json_path, &mut evidence);
+
+        if let Some(field) = package_json
+            .as_ref()
+            .and_then(|package_json| package_json.package_manager.as_deref())
+        {
+            let (name, version) = match field.split_once('@') {
+                Some((name, version)) => (name, Some(version)),
+                None => (field, None),
+            };
+            // corepack appends a hash, e.g. `yarn@4.1.0+sha512.abc`
+            let version = version.map(|v| v.split('+').next().unwrap_or(v).to_string());
+            match parse_package_manager(name) {
+                Some(kind) => {
+                    evidence.push(DetectionEvidence {
+                        path: package_json_path,
+                        finding: format!("packageManager is {field:?}"),
+                    });
+                    return finish(kind, version, dir, evidence);
+                }
+                None => {
+                    warn!("[find_package_manager] ignoring unsupported packageManager {field:?}");
+                    evidence.push(DetectionEvidence {
+                        path: package_json_path,
+                        finding: format!("packageManager {field:?} is not supported, ignored"),
+                    });
+                }
+            }
+        }
+
+        let lockfiles: Vec<_> = LOCKFILES
+            .iter()
+            .filter(|(filename, _)| dir.join(filename).is_file())
+            .collect();
+        if let Some((filename, kind)) = lockfiles.first() {
+            for (other, _) in lockfiles.iter().skip(1) {
+                evidence.push(DetectionEvidence {
+                    path: dir.join(other),
+                    finding: format!("also found {other}, {filename} takes precedence"),
+                });
+            }
+            evidence.push(DetectionEvidence {
+                path: dir.join(filename),
+                finding: format!("found {filename}"),
+            });
+            return finish(kind.clone(), None, dir, evidence);
+        }
+
+        // a deno project without a lockfile is still a deno project
+        for filename in ["deno.json", "deno.jsonc"] {
+            if dir.join(filename).is_file() {
+                evidence.push(DetectionEvidence {
+                    path: dir.join(filename),
+                    finding: format!("found {filename}"),
+                });
+                return finish(PackageManager::Deno, None, dir, evidence);
+            }
+        }
+
+        let is_workspace_root = package_json
+            .as_ref()
+            .is_some_and(|package_json| package_json.workspaces.is_some())
+            || dir.join("pnpm-workspace.yaml").is_file();
+        if is_workspace_root || dir.join(".git").exists() {
+            break;
+        }
+    }
+
+    info!(
+        "[find_package_manager] no packageManager field or lockfile found above {}, defaulting to npm",
+        project_root.display()
+    );
+    evidence.push(DetectionEvidence {
+        path: project_root.to_path_buf(),
+        finding: "no packageManager field or lockfile found, defaulting to npm".to_string(),
+    });
+    DetectedPackageManager {
+        evidence,
+        ..DetectedPackageManager::npm()
+    }
+}
+
+fn parse_package_manager(name: &str) -> Option<PackageManager> {
+    match name {
+        "npm" => Some(PackageManager::NPM),
+        "pnpm" => Some(PackageManager::PNPM),
+        "yarn" => Some(PackageManager::Yarn),
+        "bun" => Some(PackageManager::Bun),
+        "deno" => Some(PackageManager::Deno),
+        _ => None,
+    }
+}
+
+fn read_package_json(path: &Path, evidence: &mut Vec<DetectionEvidence>) -> Option<PackageJSON> {
+    let contents = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&contents) {
+        Ok(package_json) => Some(package_json),
+        Err(e) => {
+            evidence.push(DetectionEvidence {
+                path: path.to_path_buf(),
+                finding: format!("could not parse: {e}"),
+            });
+            None
+        }
+    }
+}
+
+fn finish(
+    kind: PackageManager,
+    version: Option<String>,
+    root: &Path,
+    mut evidence: Vec<DetectionEvidence>,
+) -> DetectedPackageManager {
+    let (yarn_berry, yarn_pnp) = if matches!(kind, PackageManager::Yarn) {
+        detect_yarn_flavor(version.as_deref(), root, &mut evidence)
+    } else {
+        (false, false)
+    };
+    DetectedPackageManager {
+        kind,
+        version,
+        yarn_berry,
+        yarn_pnp,
+        root: Some(root.to_path_buf()),
+        evidence,
+    }
+}
+
+/// Tell yarn classic from berry, and whether Plug'n'Play is on.
+fn detect_yarn_flavor(
+    version: Option<&str>,
+    root: &Path,
+    evidence: &mut Vec<DetectionEvidence>,
+) -> (bool, bool) {
+    let yarnrc_path = root.join(".yarnrc.yml");
+    let yarnrc = fs::read_to_string(&yarnrc_path).ok();
+    let lockfile_path = root.join("yarn.lock");
+    let lockfile_header: Option<String> = fs::read_to_string(&lockfile_path)
+        .ok()
+        .map(|contents| contents.lines().take(10).collect::<Vec<_>>().join("\n"));
+
+    let major = version
+        .and_then(|version| version.split('.').next())
+        .and_then(|major| major.parse::<u32>().ok());
+    let berry = if let Some(major) = major {
+        major >= 2
+    } else if let Some(header) = &lockfile_header {
+        // classic lockfiles start with `# yarn lockfile v1`; berry ones are YAML with a `__metadata` key
+        let berry = header.contains("__metadata:") || !header.contains("yarn lockfile v1");
+        evidence.push(DetectionEvidence {
+            path: lockfile_path,
+            finding: format!(
+                "lockfile format is {}",
+                if berry { "berry" } else { "classic (v1)" }
+            ),
+        });
+        berry
+    } else {
+        yarnrc.is_some()
+    };
+    if yarnrc.is_some() {
+        evidence.push(DetectionEvidence {
+            path: yarnrc_path.clone(),
+            finding: "found .yarnrc.yml".to_string(),
+        });
+    }
+
+    let node_linker = yarnrc.as_deref().and_then(|yarnrc| {
+        yarnrc.lines().find_map(|line| {
+            let value = line.trim().strip_prefix("nodeLinker:")?;
+            Some(value.trim().trim_matches(['"', '\'']).to_string())
+        })
+    });
+    let pnp_file = [".pnp.cjs", ".pnp.js"]
+        .into_iter()
+        .map(|filename| root.join(filename))
+        .find(|path| path.is_file());
+    let pnp = match (&pnp_file, &node_linker) {
+        (Some(path), _) => {
+            evidence.push(DetectionEvidence {
+                path: path.clone(),
+                finding: "Plug'n'Play loader present".to_string(),
+            });
+            true
+        }
+        (None, Some(linker)) => {
+            evidence.push(DetectionEvidence {
+                path: yarnrc_path,
+                finding: format!("nodeLinker is {linker:?}"),
+            });
+            linker == "pnp"
+        }
+        // berry defaults to Plug'n'Play
+        (None, None) => berry,
+    };
+    (berry, pnp)
+}
@@ -0,0 +1,272 @@
+use crate::{
+    analyze_trace::AnalyzeTraceOptions,
+    app_data::AppData,
+    layercake::{LayerCake, ResolveNumberArgs, ResolveStringArgs, Source},
+    repeated_runs::RepeatedRunsResult,
+    stats::SampleStats,
+    treemap::build_treemap_from_trace,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// `settings` keys a committed, project-level typeslayer.toml may set: how to build the
+/// project, not how someone likes their app to behave.
+const PROJECT_SETTINGS: [&str; 8] = [
+    "settings.tsconfig",
+    "settings.typescriptCompilerVariant",
+    "settings.extraTscFlags",
+    "settings.applyTscProjectFlag",
+    "settings.maxOldSpaceSize",
+    "settings.maxStackSize",
+    "settings.tscTimeoutSeconds",
+    "settings.tscMaxRssMb",
+];
+
+/// Whether a project-level typeslayer.toml may set `key` (a dotted key path).
+pub fn is_project_key(key: &str) -> bool {
+    PROJECT_SETTINGS.contains(&key) || key.starts_with("thresholds.") || key.starts_with("budgets.")
+}
+
+/// Limits a project sets for itself, e.g. to fail CI when type checking gets slower.
+/// Unset budgets aren't checked.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Budgets {
+    /// Sum of every `checkSourceFile` span, in milliseconds
+    pub total_check_time_ms: Option<i32>,
+    /// Types in types.json
+    pub type_count: Option<i32>,
+    pub hot_spot_count: Option<i32>,
+    /// Depth limits hit, of every kind
+    pub depth_limit_hits: Option<i32>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetCheck {
+    /// The key in the `budgets` table, e.g. `typeCount`
+    pub budget: String,
+    pub limit: i32,
+    /// `None` when the output it's measured from hasn't been generated. For a timing with
+    /// repeated runs, their mean.
+    pub actual: Option<f64>,
+    /// 95% confidence interval of a timing over repeated runs. The budget is only exceeded when
+    /// the whole interval is above the limit, so noise alone doesn't fail it.
+    pub ci_low: Option<f64>,
+    pub ci_high: Option<f64>,
+    /// A timing judged from the loaded trace alone because there are no repeated runs, which
+    /// noise can push either side of the limit
+    pub single_sample: bool,
+    pub exceeded: bool,
+}
+
+/// The project-level typeslayer.toml and what it set.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectConfig {
+    pub path: Option<PathBuf>,
+    /// Key paths whose value came from this file (env vars and flags still take precedence)
+    pub applied: Vec<String>,
+    pub thresholds: AnalyzeTraceOptions,
+    pub budgets: Budgets,
+}
+
+impl ProjectConfig {
+    pub fn of(app_data: &AppData) -> Self {
+        Self {
+            path: app_data.cake.project_config_path.clone(),
+            applied: app_data.cake.project_overrides.clone(),
+            thresholds: app_data.thresholds.clone(),
+            budgets: app_data.budgets.clone(),
+        }
+    }
+}
+
+fn resolve_f64(cake: &mut LayerCake, env: &str, flag: &str, file: &str, default: f64) -> f64 {
+    let value = cake.resolve_string(ResolveStringArgs {
+        env,
+        flag,
+        file,
+        default: || default.to_string(),
+        validate: |s| match s.trim().parse::<f64>() {
+            Ok(n) if n.is_finite() && n >= 0.0 => Ok(s.trim().to_string()),
+            Ok(_) => Err(format!("{file} must not be negative, got {s:?}")),
+            Err(e) => Err(format!("Invalid {file} value {s:?}: {e}")),
+        },
+    });
+    value.parse().unwrap_or(default)
+}
+
+/// The analysis thresholds `analyze_trace` uses unless a caller passes its own.
+pub fn init_thresholds(cake: &mut LayerCake) -> AnalyzeTraceOptions {
+    let defaults = AnalyzeTraceOptions::default();
+    let skip_millis = defaults.skip_millis;
+    let force_millis = resolve_f64(
+        cake,
+        "THRESHOLDS_FORCE_MILLIS",
+        "--thresholds-force-millis",
+        "thresholds.forceMillis",
+        defaults.force_millis,
+    );
+    let min_span_parent_percentage = resolve_f64(
+        cake,
+        "THRESHOLDS_MIN_SPAN_PARENT_PERCENTAGE",
+        "--thresholds-min-span-parent-percentage",
+        "thresholds.minSpanParentPercentage",
+        defaults.min_span_parent_percentage,
+    );
+
+    // `analyze_trace` refuses options like this, so fall back rather than fail every analysis
+    let force_millis = if force_millis < skip_millis {
+        let source = cake
+            .source_of("THRESHOLDS_FORCE_MILLIS")
+            .unwrap_or(Source::File);
+        let location = cake.file_location(&source, "thresholds.forceMillis");
+        cake.reject(
+            "THRESHOLDS_FORCE_MILLIS",
+            source,
+            &location,
+            force_millis,
+            format!("forceMillis cannot be less than skipMillis ({skip_millis})"),
+        );
+        defaults.force_millis
+    } else {
+        force_millis
+    };
+
+    AnalyzeTraceOptions {
+        force_millis,
+        min_span_parent_percentage,
+        ..defaults
+    }
+}
+
+fn resolve_budget(cake: &mut LayerCake, env: &str, flag: &str, file: &str) -> Option<i32> {
+    let value = cake.resolve_number(ResolveNumberArgs {
+        env,
+        flag,
+        file,
+        default: || 0,
+        validate: |n| {
+            if *n >= 0 {
+                Ok(*n)
+            } else {
+                Err(format!("{file} must not be negative"))
+            }
+        },
+    });
+    // 0 is the same as unset: no budget
+    Some(value).filter(|n| *n > 0)
+}
+
+pub fn init_budgets(cake: &mut LayerCake) -> Budgets {
+    Budgets {
+        total_check_time_ms: resolve_budget(
+            cake,
+            "BUDGETS_TOTAL_CHECK_TIME_MS",
+            "--budgets-total-check-time-ms",
+            "budgets.totalCheckTimeMs",
+        ),
+        type_count: resolve_budget(
+            cake,
+            "BUDGETS_TYPE_COUNT",
+            "--budgets-type-count",
+            "budgets.typeCount",
+        ),
+        hot_spot_count: resolve_budget(
+            cake,
+            "BUDGETS_HOT_SPOT_COUNT",
+            "--budgets-hot-spot-count",
+            "budgets.hotSpotCount",
+        ),
+        depth_limit_hits: resolve_budget(
+            cake,
+            "BUDGETS_DEPTH_LIMIT_HITS",
+            "--budgets-depth-limit-hits",
+            "budgets.depthLimitHits",
+        ),
+    }
+}
+
+fn check_budget(
+    budget: &str,
+    limit: Option<i32>,
+    measure: impl FnOnce() -> Option<f64>,
+) -> Option<BudgetCheck> {
+    let limit = limit?;
+    let actual = measure();
+    Some(BudgetCheck {
+        budget: budget.to_string(),
+        limit,
+        actual,
+        ci_low: None,
+        ci_high: None,
+        single_sample: false,
+        exceeded: actual.is_some_and(|actual| actual > limit as f64),
+    })
+}
+
+/// Check a timing against its budget with the spread of repeated runs, or with the one sample in
+/// the loaded trace when there aren't any.
+fn check_time_budget(
+    budget: &str,
+    limit: Option<i32>,
+    samples: Option<&SampleStats>,
+    measure: impl FnOnce() -> Option<f64>,
+) -> Option<BudgetCheck> {
+    match samples.filter(|samples| samples.count() > 0) {
+        Some(samples) => {
+            let limit = limit?;
+            Some(BudgetCheck {
+                budget: budget.to_string(),
+                limit,
+                actual: Some(samples.mean),
+                ci_low: samples.ci_low,
+                ci_high: samples.ci_high,
+                single_sample: samples.count() < 2,
+                exceeded: samples.exceeds(limit as f64),
+            })
+        }
+        None => check_budget(budget, limit, measure).map(|check| BudgetCheck {
+            single_sample: check.actual.is_some(),
+            ..check
+        }),
+    }
+}
+
+/// Check the loaded outputs against every budget that's set. Timings use `repeated_runs` when
+/// there are any.
+pub fn check_budgets(
+    app_data: &AppData,
+    repeated_runs: Option<&RepeatedRunsResult>,
+) -> Vec<BudgetCheck> {
+    let budgets = &app_data.budgets;
+    let analysis = app_data.analyze_trace.as_ref();
+    [
+        check_time_budget(
+            "totalCheckTimeMs",
+            budgets.total_check_time_ms,
+            repeated_runs.map(|runs| &runs.total_check_time),
+            || {
+                if app_data.trace_json.is_empty() {
+                    return None;
+                }
+                build_treemap_from_trace(&app_data.trace_json)
+                    .ok()
+                    .map(|nodes| nodes.iter().map(|node| node.value).sum::<f64>() / 1000.0)
+            },
+        ),
+        check_budget("typeCount", budgets.type_count, || {
+            Some(app_data.types_json.len() as f64).filter(|n| *n > 0.0)
+        }),
+        check_budget("hotSpotCount", budgets.hot_spot_count, || {
+            analysis.map(|analysis| analysis.total_hotspots() as f64)
+        }),
+        check_budget("depthLimitHits", budgets.depth_limit_hits, || {
+            analysis.map(|analysis| analysis.depth_limit_counts().values().sum::<usize>() as f64)
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
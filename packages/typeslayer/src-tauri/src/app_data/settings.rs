@@ -58,6 +58,16 @@ pub struct Settings {
     pub typescript_compiler_variant: TypeScriptCompilerVariant,
     pub max_nodes: i32,
     pub analytics_consent: Vec<String>,
+    /// Kill tsc runs that take longer than this many seconds
+    pub tsc_timeout_seconds: Option<i32>,
+    /// Kill tsc runs whose process tree uses more resident memory than this many MB
+    pub tsc_max_rss_mb: Option<i32>,
+    /// Keep at most this many project workspaces, least recently used are deleted first (`0` keeps all)
+    pub max_workspaces: i32,
+    /// Delete project workspaces that haven't been opened in this many days (`0` keeps them forever)
+    pub workspace_retention_days: i32,
+    /// Keep at most this many history snapshots per workspace, oldest are deleted first (`0` keeps all)
+    pub max_snapshots: i32,
 }
 
 impl Default for Settings {
@@ -82,6 +92,11 @@ impl Default for Settings {
                 EventTypeGraphFail::event_id().to_string(),
                 EventTypeGraphSuccess::event_id().to_string(),
             ],
+            tsc_timeout_seconds: None,
+            tsc_max_rss_mb: None,
+            max_workspaces: 20,
+            workspace_retention_days: 90,
+            max_snapshots: 100,
         }
     }
 }
@@ -0,0 +1,233 @@
+use crate::{
+    experiments::experiments_dir,
+    history::{history_dir, prune_snapshots},
+    utils::OUTPUTS_DIRECTORY,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::{debug, info};
+
+pub const WORKSPACES_FILENAME: &str = "workspaces.json";
+
+/// The outputs for one project + tsconfig pair.
+///
+/// Each workspace gets its own directory under `data_dir/outputs`, so switching
+/// between repos (or between tsconfigs of the same repo) never mixes or clobbers results.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    pub id: String,
+    pub project_root: PathBuf,
+    pub tsconfig: Option<PathBuf>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    /// The TypeSlayer version that last used this workspace
+    pub version: String,
+}
+
+/// Every workspace TypeSlayer knows about, persisted as `data_dir/workspaces.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct WorkspaceRegistry {
+    pub workspaces: Vec<Workspace>,
+}
+
+/// When workspaces are deleted automatically. `0` disables a limit.
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionPolicy {
+    /// Keep at most this many workspaces, dropping the least recently used first
+    pub max_workspaces: usize,
+    /// Drop workspaces that haven't been used in this many days
+    pub retention_days: i64,
+    /// Keep at most this many snapshots per workspace, dropping the oldest first
+    pub max_snapshots: usize,
+}
+
+/// A stable, filesystem-safe id for a project + tsconfig pair, e.g. `my-app-tsconfig-1f3a9c20`.
+///
+/// The readable prefix is for humans browsing the data dir; the hash keeps
+/// two checkouts of the same repo name apart.
+pub fn workspace_id(project_root: &Path, tsconfig: Option<&Path>) -> String {
+    let project_name = project_root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+    let tsconfig_name = tsconfig
+        .and_then(|p| p.file_stem())
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "default".to_string());
+
+    let key = format!(
+        "{}\0{}",
+        project_root.to_string_lossy(),
+        tsconfig.map(|p| p.to_string_lossy()).unwrap_or_default()
+    );
+
+    format!(
+        "{}-{}-{:08x}",
+        slugify(&project_name),
+        slugify(&tsconfig_name),
+        fnv1a(key.as_bytes()) as u32
+    )
+}
+
+/// Workspace ids can come from the frontend and are joined onto paths, so only allow what
+/// `workspace_id` produces.
+pub fn check_workspace_id(id: &str) -> Result<(), String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("Invalid workspace id {id:?}"));
+    }
+    Ok(())
+}
+
+pub fn workspace_outputs_dir(data_dir: &Path, id: &str) -> PathBuf {
+    data_dir.join(OUTPUTS_DIRECTORY).join(id)
+}
+
+fn slugify(s: &str) -> String {
+    let slug: String = s
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "x".to_string()
+    } else {
+        slug.chars().take(40).collect()
+    }
+}
+
+/// std's `DefaultHasher` isn't guaranteed stable across Rust releases, and these ids are persisted.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl WorkspaceRegistry {
+    pub async fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(WORKSPACES_FILENAME);
+        match fs::read_to_string(&path).await {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                info!(
+                    "[WorkspaceRegistry::load] ignoring unreadable {}: {e}",
+                    path.display()
+                );
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub async fn save(&self, data_dir: &Path) -> Result<(), String> {
+        let path = data_dir.join(WORKSPACES_FILENAME);
+        fs::create_dir_all(data_dir)
+            .await
+            .map_err(|e| format!("Failed to create data directory: {e}"))?;
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize workspaces: {e}"))?;
+        fs::write(&path, contents)
+            .await
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Workspace> {
+        self.workspaces.iter().find(|w| w.id == id)
+    }
+
+    /// Register (or mark as just used) the workspace for a project + tsconfig pair, returning its id.
+    pub fn touch(&mut self, project_root: &Path, tsconfig: Option<&Path>, version: &str) -> String {
+        let id = workspace_id(project_root, tsconfig);
+        let now = Utc::now();
+        match self.workspaces.iter_mut().find(|w| w.id == id) {
+            Some(workspace) => {
+                workspace.last_used_at = now;
+                workspace.version = version.to_string();
+            }
+            None => self.workspaces.push(Workspace {
+                id: id.clone(),
+                project_root: project_root.to_path_buf(),
+                tsconfig: tsconfig.map(Path::to_path_buf),
+                created_at: now,
+                last_used_at: now,
+                version: version.to_string(),
+            }),
+        }
+        id
+    }
+
+    /// Most recently used first
+    pub fn sorted(&self) -> Vec<Workspace> {
+        let mut workspaces = self.workspaces.clone();
+        workspaces.sort_by_key(|w| std::cmp::Reverse(w.last_used_at));
+        workspaces
+    }
+
+    /// Remove a workspace, its outputs, its run history and its experiments. Only registered
+    /// workspaces are removed, since the id is joined onto paths that get deleted.
+    pub async fn remove(&mut self, data_dir: &Path, id: &str) -> Result<(), String> {
+        check_workspace_id(id)?;
+        if self.get(id).is_none() {
+            return Err(format!("Unknown workspace {id:?}"));
+        }
+        for dir in [
+            workspace_outputs_dir(data_dir, id),
+            history_dir(data_dir, id),
+            experiments_dir(data_dir, id),
+        ] {
+            if dir.exists() {
+                fs::remove_dir_all(&dir)
+                    .await
+                    .map_err(|e| format!("failed to remove directory {dir:?}: {e}"))?;
+            }
+        }
+        self.workspaces.retain(|w| w.id != id);
+        debug!("[WorkspaceRegistry::remove] removed workspace {id}");
+        Ok(())
+    }
+
+    /// Delete workspaces the retention policy no longer allows, never touching `keep`, and trim
+    /// the history of those that are left. Returns the ids of the removed workspaces.
+    pub async fn prune(
+        &mut self,
+        data_dir: &Path,
+        policy: RetentionPolicy,
+        keep: &str,
+    ) -> Result<Vec<String>, String> {
+        let cutoff = Utc::now() - Duration::days(policy.retention_days);
+        let mut expired = Vec::new();
+
+        for (index, workspace) in self.sorted().iter().enumerate() {
+            if workspace.id == keep {
+                continue;
+            }
+            let too_old = policy.retention_days > 0 && workspace.last_used_at < cutoff;
+            let too_many = policy.max_workspaces > 0 && index >= policy.max_workspaces;
+            if too_old || too_many {
+                expired.push(workspace.id.clone());
+            }
+        }
+
+        for id in &expired {
+            self.remove(data_dir, id).await?;
+        }
+        for workspace in &self.workspaces {
+            prune_snapshots(data_dir, &workspace.id, policy.max_snapshots).await?;
+        }
+        if !expired.is_empty() {
+            info!(
+                "[WorkspaceRegistry::prune] removed {} workspace(s): {:?}",
+                expired.len(),
+                expired
+            );
+        }
+        Ok(expired)
+    }
+}
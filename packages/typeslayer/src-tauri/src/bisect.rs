@@ -0,0 +1,408 @@
+use crate::{
+    analyze_trace::{DepthLimitKind, analyze_trace},
+    app_data::{AppData, command::TSCCommand, init::generate_session_id},
+    commands::tasks::TaskId,
+    process_controller::{OutputStream, ProcessController, ProgressReporter},
+    stats::SampleStats,
+    treemap::build_treemap_from_trace,
+    utils::make_cli_arg,
+    validate::{
+        trace_json::{TRACE_JSON_FILENAME, load_trace_json},
+        types_json::{TYPES_JSON_FILENAME, load_types_json},
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::{fs, process::Command, sync::Mutex};
+use tracing::{info, warn};
+
+/// What to measure at each commit. Check times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum BisectMetric {
+    /// Sum of every `checkSourceFile` span
+    TotalCheckTime,
+    /// Check time of the file whose path ends with `path` (e.g. `src/routes.ts`)
+    FileCheckTime { path: String },
+    /// Number of times a depth limit was hit
+    DepthLimitCount { depth_limit: DepthLimitKind },
+    /// Number of types in types.json
+    TypeCount,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectRequest {
+    /// A revision where the metric is at or under `threshold`
+    pub good: String,
+    /// A later revision where the metric is over `threshold`
+    pub bad: String,
+    pub metric: BisectMetric,
+    pub threshold: f64,
+    /// Times to measure each commit. With more than one, a commit is only bad when the whole 95%
+    /// confidence interval is over `threshold`, so one slow run can't derail the search.
+    #[serde(default = "default_repetitions")]
+    pub repetitions: usize,
+}
+
+fn default_repetitions() -> usize {
+    1
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectStep {
+    pub commit: String,
+    pub subject: String,
+    /// The mean over `stats.samples`
+    pub value: f64,
+    pub stats: SampleStats,
+    pub bad: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectResult {
+    /// The first commit (following first parents from `good`) whose metric is over the threshold
+    pub first_bad_commit: String,
+    pub first_bad_subject: String,
+    /// Number of candidate commits between `good` (exclusive) and `bad` (inclusive)
+    pub candidates: usize,
+    /// Every commit that was measured, in the order it was measured
+    pub steps: Vec<BisectStep>,
+}
+
+/// Run `git` in `dir`, returning its trimmed stdout.
+async fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// A detached worktree of the project's repo, removed again on [`Worktree::remove`].
+struct Worktree {
+    repo_root: PathBuf,
+    path: PathBuf,
+    /// Where `project_root` lives inside the worktree (it may be a package inside a monorepo)
+    project_root: PathBuf,
+}
+
+impl Worktree {
+    async fn add(project_root: &Path, path: PathBuf, commit: &str) -> Result<Self, String> {
+        let repo_root = PathBuf::from(git(project_root, &["rev-parse", "--show-toplevel"]).await?);
+        let prefix = git(project_root, &["rev-parse", "--show-prefix"]).await?;
+        git(
+            &repo_root,
+            &[
+                "worktree",
+                "add",
+                "--detach",
+                &path.to_string_lossy(),
+                commit,
+            ],
+        )
+        .await?;
+        Ok(Self {
+            project_root: path.join(prefix),
+            repo_root,
+            path,
+        })
+    }
+
+    /// Where `dir`, a directory of the user's checkout, is inside the worktree, or `None` when
+    /// it's outside the repo.
+    fn map_dir(&self, dir: &Path) -> Option<PathBuf> {
+        let relative = dir
+            .strip_prefix(&self.repo_root)
+            .map(Path::to_path_buf)
+            .or_else(|_| {
+                // git reports the toplevel with symlinks resolved
+                let dir = std::fs::canonicalize(dir).map_err(|_| ())?;
+                let repo_root = std::fs::canonicalize(&self.repo_root).map_err(|_| ())?;
+                dir.strip_prefix(repo_root)
+                    .map(Path::to_path_buf)
+                    .map_err(|_| ())
+            });
+        relative.ok().map(|relative| self.path.join(relative))
+    }
+
+    async fn checkout(&self, commit: &str) -> Result<(), String> {
+        git(&self.path, &["checkout", "--detach", "--force", commit]).await?;
+        Ok(())
+    }
+
+    async fn remove(self) {
+        let path = self.path.to_string_lossy().to_string();
+        if let Err(e) = git(&self.repo_root, &["worktree", "remove", "--force", &path]).await {
+            warn!("[bisect] failed to remove worktree {path}: {e}");
+        }
+    }
+}
+
+/// Find the first commit between `request.good` and `request.bad` where the metric crosses the threshold.
+///
+/// Commits are checked out one at a time into a temporary worktree, so the user's checkout is never touched.
+/// Only first-parent history is searched, which means the answer for a merge-based workflow is the merge of the PR.
+pub async fn run_bisect(
+    app_data: &'static Mutex<AppData>,
+    process_controller: &ProcessController,
+    request: BisectRequest,
+    reporter: &dyn ProgressReporter,
+) -> Result<BisectResult, String> {
+    if request.repetitions == 0 {
+        return Err("repetitions must be at least 1".to_string());
+    }
+    let scratch = std::env::temp_dir().join(format!("typeslayer-bisect-{}", generate_session_id()));
+    let project_root = app_data.lock().await.project_root.clone();
+
+    let good = git(&project_root, &["rev-parse", "--verify", &request.good]).await?;
+    let bad = git(&project_root, &["rev-parse", "--verify", &request.bad]).await?;
+    let range = format!("{good}..{bad}");
+    let commits: Vec<String> = git(
+        &project_root,
+        &["rev-list", "--first-parent", "--reverse", &range],
+    )
+    .await?
+    .lines()
+    .map(str::to_string)
+    .collect();
+    if commits.is_empty() {
+        return Err(format!(
+            "{} is not an ancestor of {}, or they are the same commit",
+            request.good, request.bad
+        ));
+    }
+
+    let worktree = Worktree::add(&project_root, scratch.join("worktree"), &good).await?;
+    // Only the settings are copied, so the app stays usable during a bisect that takes hours
+    let probe = app_data
+        .lock()
+        .await
+        .detached(worktree.project_root.clone(), scratch.join("data"));
+
+    let mut bisector = Bisector {
+        probe,
+        worktree: &worktree,
+        process_controller,
+        request: &request,
+        reporter,
+        steps: Vec::new(),
+    };
+    let result = bisector.bisect(&good, &commits).await;
+    let steps = bisector.steps;
+
+    worktree.remove().await;
+    if let Err(e) = fs::remove_dir_all(&scratch).await {
+        warn!("[bisect] failed to remove {}: {e}", scratch.display());
+    }
+
+    let index = result?;
+    let first_bad_commit = commits[index].clone();
+    let first_bad_subject = steps
+        .iter()
+        .find(|step| step.commit == first_bad_commit)
+        .map(|step| step.subject.clone())
+        .unwrap_or_default();
+    Ok(BisectResult {
+        first_bad_commit,
+        first_bad_subject,
+        candidates: commits.len(),
+        steps,
+    })
+}
+
+struct Bisector<'a> {
+    probe: AppData,
+    worktree: &'a Worktree,
+    process_controller: &'a ProcessController,
+    request: &'a BisectRequest,
+    reporter: &'a dyn ProgressReporter,
+    steps: Vec<BisectStep>,
+}
+
+impl Bisector<'_> {
+    fn log(&self, message: &str) {
+        info!("[bisect] {message}");
+        self.reporter
+            .output_line(OutputStream::Stdout, &format!("[bisect] {message}"));
+    }
+
+    /// Binary search over `commits` (oldest first), returning the index of the first bad one.
+    async fn bisect(&mut self, good: &str, commits: &[String]) -> Result<usize, String> {
+        // confirm the endpoints before spending hours on the middle
+        if self.measure(good).await?.bad {
+            return Err(format!(
+                "the good revision {} is already over the threshold",
+                self.request.good
+            ));
+        }
+        let last = commits.len() - 1;
+        if !self.measure(&commits[last]).await?.bad {
+            return Err(format!(
+                "the bad revision {} is not over the threshold",
+                self.request.bad
+            ));
+        }
+
+        // invariant: everything before `low` is good, `high` is bad
+        let (mut low, mut high) = (0, last);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            self.log(&format!(
+                "{} candidate(s) left, roughly {} step(s) to go",
+                high - low + 1,
+                (high - low + 1).ilog2() + 1
+            ));
+            if self.measure(&commits[mid]).await?.bad {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Ok(high)
+    }
+
+    async fn measure(&mut self, commit: &str) -> Result<&BisectStep, String> {
+        let subject = git(&self.worktree.path, &["log", "-1", "--format=%s", commit]).await?;
+        self.log(&format!("checking out {} {subject}", short(commit)));
+        self.worktree.checkout(commit).await?;
+
+        // in a monorepo the lockfile, and so the install, is at the workspace root
+        let install_dir = self
+            .probe
+            .package_manager
+            .root
+            .as_deref()
+            .and_then(|root| self.worktree.map_dir(root))
+            .unwrap_or_else(|| self.worktree.project_root.clone());
+        self.log(&format!(
+            "installing dependencies in {} with {:?}",
+            install_dir.display(),
+            self.probe.package_manager
+        ));
+        let install =
+            TSCCommand::in_shell(self.probe.package_manager.install_command().to_string());
+        let output = self
+            .process_controller
+            .run_command(
+                install.to_command(&install_dir),
+                TaskId::Bisect,
+                self.probe.run_limits(),
+                self.reporter,
+            )
+            .await?;
+        if let Some(reason) = output.killed {
+            return Err(format!("installing dependencies {reason}"));
+        }
+        if !output.status.success() {
+            return Err(format!(
+                "installing dependencies failed at {}:\n{}",
+                short(commit),
+                output.stderr
+            ));
+        }
+
+        let mut samples = Vec::with_capacity(self.request.repetitions);
+        for _ in 0..self.request.repetitions {
+            samples.push(self.run_metric(commit).await?);
+        }
+        let stats = SampleStats::from_samples(samples);
+        let bad = stats.exceeds(self.request.threshold);
+        let spread = match (stats.ci_low, stats.ci_high) {
+            (Some(low), Some(high)) => format!(" [95% CI {low:.2}..{high:.2}]"),
+            _ => String::new(),
+        };
+        self.log(&format!(
+            "{} measured {:.2}{spread} ({})",
+            short(commit),
+            stats.mean,
+            if bad { "bad" } else { "good" }
+        ));
+        self.steps.push(BisectStep {
+            commit: commit.to_string(),
+            subject,
+            value: stats.mean,
+            stats,
+            bad,
+        });
+        Ok(self.steps.last().expect("a step was just pushed"))
+    }
+
+    async fn run_metric(&self, commit: &str) -> Result<f64, String> {
+        let outputs_dir = self.probe.outputs_dir();
+        if outputs_dir.exists() {
+            fs::remove_dir_all(&outputs_dir)
+                .await
+                .map_err(|e| format!("Failed to clear {}: {e}", outputs_dir.display()))?;
+        }
+
+        let flag = make_cli_arg("--generateTrace", &outputs_dir.to_string_lossy());
+        let output = self
+            .probe
+            .call_typescript(self.process_controller, TaskId::Bisect, flag, self.reporter)
+            .await?;
+        if let Some(reason) = output.killed {
+            return Err(format!("tsc {reason} at {}", short(commit)));
+        }
+        // type errors are expected mid-history, but no trace means there's nothing to measure
+        let trace_path = outputs_dir.join(TRACE_JSON_FILENAME.trim_start_matches('/'));
+        if !trace_path.exists() {
+            return Err(format!(
+                "tsc produced no trace at {}:\n{}",
+                short(commit),
+                output.stderr
+            ));
+        }
+
+        match &self.request.metric {
+            BisectMetric::TotalCheckTime => {
+                let (trace, _) = load_trace_json(trace_path).await?;
+                let total: f64 = build_treemap_from_trace(&trace)?
+                    .iter()
+                    .map(|node| node.value)
+                    .sum();
+                Ok(total / 1000.0)
+            }
+            BisectMetric::FileCheckTime { path } => {
+                let (trace, _) = load_trace_json(trace_path).await?;
+                let duration: f64 = build_treemap_from_trace(&trace)?
+                    .iter()
+                    .filter(|node| node.path.as_ref().is_some_and(|p| p.ends_with(path)))
+                    .map(|node| node.value)
+                    .sum();
+                Ok(duration / 1000.0)
+            }
+            BisectMetric::DepthLimitCount { depth_limit } => {
+                let trace_dir = outputs_dir.to_string_lossy().to_string();
+                let result =
+                    tauri::async_runtime::spawn_blocking(move || analyze_trace(&trace_dir, None))
+                        .await
+                        .map_err(|e| e.to_string())??;
+                Ok(result
+                    .depth_limit_counts()
+                    .get(depth_limit)
+                    .copied()
+                    .unwrap_or(0) as f64)
+            }
+            BisectMetric::TypeCount => {
+                let types_path = outputs_dir.join(TYPES_JSON_FILENAME.trim_start_matches('/'));
+                Ok(load_types_json(types_path).await?.len() as f64)
+            }
+        }
+    }
+}
+
+fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(10)]
+}
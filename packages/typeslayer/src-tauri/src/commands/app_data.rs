@@ -1,14 +1,28 @@
 use crate::{
-    analyze_trace::{AnalyzeTraceResult, constants::ANALYZE_TRACE_FILENAME},
-    app_data::AppData,
+    analyze_trace::{
+        AnalyzeTraceResult, DepthLimitReport, constants::ANALYZE_TRACE_FILENAME,
+        create_depth_limit_report,
+    },
+    app_data::{
+        AppData,
+        package_manager::DetectedPackageManager,
+        project_config::{BudgetCheck, ProjectConfig, check_budgets},
+    },
+    layercake::ConfigError,
     process_controller::ProcessController,
+    repeated_runs::load_repeated_runs,
+    run_metadata::RunMetadata,
+    tsconfig::{discover::TsconfigNode, resolve::EffectiveTsconfig},
     type_graph::{CountAndMax, LinkKind, LinkKindData, NodeStatKind, NodeStatKindData},
     utils::{compute_window_title, set_window_title},
-    validate::{trace_json::TraceEvent, utils::TypeId},
+    validate::{
+        trace_json::{ParseWarnings, TraceEvent},
+        utils::TypeId,
+    },
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use tauri::{AppHandle, State};
 use tokio::sync::Mutex;
 use tracing::debug;
@@ -47,32 +61,56 @@ pub async fn get_trace_json(state: State<'_, &Mutex<AppData>>) -> Result<Vec<Tra
     Ok(app_data.trace_json.clone())
 }
 
+/// Events in the loaded trace this version doesn't know, by name, most first. Unknown ones are
+/// still loaded, and count towards spans, but nothing reads their args. Skipped ones couldn't
+/// be read at all and were dropped.
+#[tauri::command]
+pub async fn get_unknown_trace_events(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<ParseWarnings, String> {
+    Ok(state.lock().await.trace_warnings.clone())
+}
+
 #[tauri::command]
 pub async fn get_analyze_trace(
     state: State<'_, &Mutex<AppData>>,
 ) -> Result<AnalyzeTraceResult, String> {
     let mut app_data = state.lock().await;
-    // Serve cached value if present
-    if let Some(result) = &app_data.analyze_trace {
-        return Ok(result.clone());
+    ensure_analyze_trace(&mut app_data).await.cloned()
+}
+
+/// Depth limits merged by the types they were hit on, with the types' names and declarations
+/// and where the checker was when it hit them, biggest first.
+#[tauri::command]
+pub async fn get_depth_limit_report(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<DepthLimitReport, String> {
+    let app_data = state.lock().await;
+    if app_data.trace_json.is_empty() {
+        return Err("No trace available. Please generate one.".to_string());
     }
+    create_depth_limit_report(&app_data.trace_json, &app_data.types_json)
+}
 
-    // Read from disk and cache
-    let path = {
-        let outputs_dir = app_data.outputs_dir().to_string_lossy().to_string();
-        Path::new(&outputs_dir).join(ANALYZE_TRACE_FILENAME)
-    };
-    let contents = tokio::fs::read_to_string(&path)
-        .await
-        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
-    let parsed: AnalyzeTraceResult = serde_json::from_str(&contents)
-        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
-    app_data.analyze_trace = Some(parsed.clone());
-    debug!(
-        "[get_analyze_trace] loaded analyze trace from disk with size {} bytes",
-        contents.len()
-    );
-    Ok(parsed)
+/// Serves the cached analyze-trace result, reading (and caching) it from disk if needed.
+pub async fn ensure_analyze_trace(app_data: &mut AppData) -> Result<&AnalyzeTraceResult, String> {
+    if app_data.analyze_trace.is_none() {
+        let path = app_data.outputs_dir().join(ANALYZE_TRACE_FILENAME);
+        let contents = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let parsed: AnalyzeTraceResult = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        app_data.analyze_trace = Some(parsed);
+        debug!(
+            "[ensure_analyze_trace] loaded analyze trace from disk with size {} bytes",
+            contents.len()
+        );
+    }
+    app_data
+        .analyze_trace
+        .as_ref()
+        .ok_or_else(|| "analyze trace unavailable".to_string())
 }
 
 #[tauri::command]
@@ -99,6 +137,72 @@ pub async fn get_tsconfig_paths(state: State<'_, &Mutex<AppData>>) -> Result<Vec
         .collect())
 }
 
+#[tauri::command]
+pub async fn get_tsconfig_tree(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Vec<TsconfigNode>, String> {
+    let app_data = state.lock().await;
+    Ok(app_data.tsconfig_tree.clone())
+}
+
+/// Settings from env, flags or typeslayer.toml that were invalid and fell back to the next layer.
+#[tauri::command]
+pub async fn get_config_errors(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Vec<ConfigError>, String> {
+    Ok(state.lock().await.cake.errors.clone())
+}
+
+/// The project-level typeslayer.toml, if the project has one, and the values it set.
+#[tauri::command]
+pub async fn get_project_config(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<ProjectConfig, String> {
+    Ok(ProjectConfig::of(&*state.lock().await))
+}
+
+/// The loaded outputs (and the last repeated runs, for timings) measured against each budget that's set.
+#[tauri::command]
+pub async fn get_budget_checks(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Vec<BudgetCheck>, String> {
+    let app_data = state.lock().await;
+    let repeated_runs = load_repeated_runs(&app_data).await;
+    Ok(check_budgets(&app_data, repeated_runs.as_ref()))
+}
+
+/// The package manager tsc is run through, with what it was detected from.
+#[tauri::command]
+pub async fn get_package_manager(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<DetectedPackageManager, String> {
+    Ok(state.lock().await.package_manager.clone())
+}
+
+/// The resolved tsconfig the loaded outputs were generated with, if they recorded one.
+#[tauri::command]
+pub async fn get_effective_tsconfig(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Option<EffectiveTsconfig>, String> {
+    Ok(state.lock().await.effective_tsconfig.clone())
+}
+
+/// The tsc run the loaded outputs came from: when it started, the compiler version and flags.
+#[tauri::command]
+pub async fn get_run_metadata(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Option<RunMetadata>, String> {
+    Ok(state.lock().await.run_metadata.clone())
+}
+
+/// Resolve the config tsc would read right now, with the current selection and flags.
+#[tauri::command]
+pub async fn resolve_effective_tsconfig(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<EffectiveTsconfig, String> {
+    state.lock().await.resolve_effective_tsconfig().await
+}
+
 #[tauri::command]
 pub async fn get_selected_tsconfig(
     state: State<'_, &Mutex<AppData>>,
@@ -125,6 +229,8 @@ pub async fn set_selected_tsconfig(
     if tsconfig_path.is_empty() {
         app_data.selected_tsconfig = None;
         debug!("[set_selected_tsconfig] selected tsconfig set to None (empty string provided)");
+        app_data.activate_workspace().await?;
+        app_data.update_typeslayer_config_toml().await;
         return Ok(());
     }
 
@@ -141,6 +247,8 @@ pub async fn set_selected_tsconfig(
         "[set_selected_tsconfig] selected tsconfig set to {:?}",
         app_data.selected_tsconfig
     );
+    app_data.activate_workspace().await?;
+    app_data.update_typeslayer_config_toml().await;
     Ok(())
 }
 
@@ -150,6 +258,13 @@ pub async fn get_data_dir(state: State<'_, &Mutex<AppData>>) -> Result<String, S
     Ok(app_data.data_dir.to_string_lossy().to_string())
 }
 
+/// Where the active workspace's outputs are written.
+#[tauri::command]
+pub async fn get_outputs_dir(state: State<'_, &Mutex<AppData>>) -> Result<String, String> {
+    let app_data = state.lock().await;
+    Ok(app_data.outputs_dir().to_string_lossy().to_string())
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NodesAndLinks {
@@ -231,7 +346,10 @@ pub struct GraphStats {
 #[tauri::command]
 pub async fn get_type_graph_stats(state: State<'_, &Mutex<AppData>>) -> Result<GraphStats, String> {
     let app_data = state.lock().await;
+    type_graph_stats(&app_data)
+}
 
+pub fn type_graph_stats(app_data: &AppData) -> Result<GraphStats, String> {
     let type_graph = app_data
         .type_graph
         .as_ref()
@@ -304,7 +422,7 @@ pub async fn clear_outputs(
     cancel_running: bool,
 ) -> Result<(), String> {
     if cancel_running {
-        process_controller.request_cancel()?;
+        process_controller.request_cancel(None)?;
     }
     state.lock().await.clear_outputs_dir().await?;
 
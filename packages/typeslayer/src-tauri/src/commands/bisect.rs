@@ -0,0 +1,23 @@
+use crate::{
+    app_data::AppData,
+    bisect::{BisectRequest, BisectResult, run_bisect},
+    commands::tasks::{TaskId, start_task},
+    process_controller::ProcessController,
+};
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+/// Find the commit between two revisions where a type-check metric crossed a threshold.
+///
+/// Progress is streamed as `task-output` events for the `bisect` task, and
+/// `cancel_generation` with that task id stops it.
+#[tauri::command]
+pub async fn bisect(
+    app: AppHandle,
+    state: State<'_, &'static Mutex<AppData>>,
+    process_controller: State<'_, ProcessController>,
+    request: BisectRequest,
+) -> Result<BisectResult, String> {
+    let guard = start_task(app, TaskId::Bisect)?;
+    run_bisect(*state, &process_controller, request, &guard.reporter()).await
+}
@@ -3,6 +3,8 @@ use crate::{
     analyze_trace::constants::ANALYZE_TRACE_FILENAME,
     app_data::{AppData, AppMode},
     log::LOG_FILENAME,
+    run_metadata::RUN_METADATA_FILENAME,
+    tsconfig::resolve::EFFECTIVE_TSCONFIG_FILENAME,
     type_graph::TYPE_GRAPH_FILENAME,
     utils::{CONFIG_FILENAME, PACKAGE_JSON_FILENAME},
     validate::{
@@ -53,6 +55,16 @@ pub async fn get_bug_report_files(
         ),
         (&outputs_dir, TYPE_GRAPH_FILENAME, "Type relationship graph"),
         (&outputs_dir, CPU_PROFILE_FILENAME, "TypeScript CPU profile"),
+        (
+            &outputs_dir,
+            EFFECTIVE_TSCONFIG_FILENAME,
+            "Resolved tsconfig the trace was generated with",
+        ),
+        (
+            &outputs_dir,
+            RUN_METADATA_FILENAME,
+            "The tsc run the outputs came from",
+        ),
         (
             &app_data.data_dir,
             ANALYTICS_EVENTS_FILENAME,
@@ -0,0 +1,100 @@
+use crate::{
+    app_data::AppData,
+    cpu_profile::{
+        CallTreeNode, CallTreeOptions, CpuProfileAnalysis, CpuProfileSummary,
+        TraceProfileCorrelation, correlate::correlate,
+    },
+    validate::{trace_json::TRACE_JSON_FILENAME, utils::CPU_PROFILE_FILENAME},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use tracing::debug;
+
+/// Parse the loaded CPU profile the first time it's needed.
+pub async fn ensure_cpu_profile_analysis(
+    app_data: &mut AppData,
+) -> Result<&CpuProfileAnalysis, String> {
+    if app_data.cpu_profile_analysis.is_none() {
+        let contents = app_data
+            .cpu_profile
+            .clone()
+            .ok_or("No CPU profile available. Please generate one.")?;
+        let analysis =
+            tauri::async_runtime::spawn_blocking(move || CpuProfileAnalysis::parse(&contents))
+                .await
+                .map_err(|e| e.to_string())??;
+        debug!(
+            "[ensure_cpu_profile_analysis] analyzed {} samples over {:.0}ms",
+            analysis.sample_count, analysis.total_time
+        );
+        app_data.cpu_profile_analysis = Some(analysis);
+    }
+    app_data
+        .cpu_profile_analysis
+        .as_ref()
+        .ok_or_else(|| "CPU profile analysis unavailable".to_string())
+}
+
+/// Line the CPU profile up with the trace, and break every hot spot down by what ran during it.
+pub async fn correlate_hot_spots(
+    app_data: &mut AppData,
+) -> Result<TraceProfileCorrelation, String> {
+    ensure_cpu_profile_analysis(app_data).await?;
+    if app_data.trace_json.is_empty() {
+        return Err("No trace available. Please generate one.".to_string());
+    }
+    if let Some(run_metadata) = &app_data.run_metadata
+        && !(run_metadata.wrote(TRACE_JSON_FILENAME) && run_metadata.wrote(CPU_PROFILE_FILENAME))
+    {
+        return Err(
+            "The trace and CPU profile come from different tsc runs. Generate them together to correlate them."
+                .to_string(),
+        );
+    }
+    let analysis = app_data
+        .cpu_profile_analysis
+        .as_ref()
+        .ok_or("CPU profile analysis unavailable")?;
+    correlate(analysis, &app_data.trace_json, &app_data.thresholds)
+}
+
+/// Total time, time per checker function group, and the `limit` functions with the most self time.
+#[tauri::command]
+pub async fn get_cpu_profile_summary(
+    state: State<'_, &Mutex<AppData>>,
+    limit: Option<usize>,
+) -> Result<CpuProfileSummary, String> {
+    let mut app_data = state.lock().await;
+    let analysis = ensure_cpu_profile_analysis(&mut app_data).await?;
+    Ok(analysis.summary(limit.unwrap_or(50)))
+}
+
+#[tauri::command]
+pub async fn get_cpu_profile_top_down(
+    state: State<'_, &Mutex<AppData>>,
+    options: Option<CallTreeOptions>,
+) -> Result<Vec<CallTreeNode>, String> {
+    let mut app_data = state.lock().await;
+    let analysis = ensure_cpu_profile_analysis(&mut app_data).await?;
+    Ok(analysis.top_down(&options.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub async fn get_cpu_profile_bottom_up(
+    state: State<'_, &Mutex<AppData>>,
+    options: Option<CallTreeOptions>,
+) -> Result<Vec<CallTreeNode>, String> {
+    let mut app_data = state.lock().await;
+    let analysis = ensure_cpu_profile_analysis(&mut app_data).await?;
+    Ok(analysis.bottom_up(&options.unwrap_or_default()))
+}
+
+/// Each hot spot in the trace, with the functions and garbage collection the CPU profile
+/// caught running during it.
+#[tauri::command]
+pub async fn get_hot_spot_profiles(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<TraceProfileCorrelation, String> {
+    let mut app_data = state.lock().await;
+    correlate_hot_spots(&mut app_data).await
+}
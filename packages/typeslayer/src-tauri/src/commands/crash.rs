@@ -0,0 +1,20 @@
+use crate::{
+    app_data::AppData,
+    crash::{CrashDiagnosis, diagnose_crash},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// What tsc was doing when the last trace generation crashed. Without one, a loaded trace
+/// with unterminated events is diagnosed from the trace alone. `None` when neither applies.
+#[tauri::command]
+pub async fn get_crash_diagnosis(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Option<CrashDiagnosis>, String> {
+    let app_data = state.lock().await;
+    if let Some(diagnosis) = &app_data.crash_diagnosis {
+        return Ok(Some(diagnosis.clone()));
+    }
+    let diagnosis = diagnose_crash(&app_data.trace_json, "", None, &app_data.settings);
+    Ok((!diagnosis.open_stack.is_empty()).then_some(diagnosis))
+}
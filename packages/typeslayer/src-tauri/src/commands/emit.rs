@@ -0,0 +1,24 @@
+use crate::{
+    app_data::AppData,
+    emit::{EmitReport, create_emit_report},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Emit time per `.js` and `.d.ts` file, `transformNodes` time per source file, and the types
+/// the checker worked on during declaration emit. `limit` caps the files and types listed.
+#[tauri::command]
+pub async fn get_emit_report(
+    state: State<'_, &Mutex<AppData>>,
+    limit: Option<usize>,
+) -> Result<EmitReport, String> {
+    let app_data = state.lock().await;
+    if app_data.trace_json.is_empty() {
+        return Err("No trace available. Please generate one.".to_string());
+    }
+    create_emit_report(
+        &app_data.trace_json,
+        &app_data.types_json,
+        limit.unwrap_or(100),
+    )
+}
@@ -0,0 +1,41 @@
+use crate::{
+    app_data::AppData,
+    commands::tasks::{TaskId, start_task},
+    experiments::{self, ExperimentRequest, ExperimentResult, run_experiment},
+    process_controller::ProcessController,
+};
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+/// Run the project under each variant's flags and compiler, returning a side-by-side comparison.
+///
+/// Progress is streamed as `task-output` events for the `experiment` task, and
+/// `cancel_generation` with that task id stops it.
+#[tauri::command]
+pub async fn run_compiler_experiment(
+    app: AppHandle,
+    state: State<'_, &'static Mutex<AppData>>,
+    process_controller: State<'_, ProcessController>,
+    request: ExperimentRequest,
+) -> Result<ExperimentResult, String> {
+    let guard = start_task(app, TaskId::Experiment)?;
+    run_experiment(*state, &process_controller, request, &guard.reporter()).await
+}
+
+/// Experiments run for the active workspace, newest first.
+#[tauri::command]
+pub async fn get_experiments(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Vec<ExperimentResult>, String> {
+    let app_data = state.lock().await;
+    Ok(experiments::list_experiments(&app_data.data_dir, &app_data.workspace_id).await)
+}
+
+#[tauri::command]
+pub async fn delete_experiment(
+    state: State<'_, &Mutex<AppData>>,
+    experiment_id: String,
+) -> Result<(), String> {
+    let app_data = state.lock().await;
+    experiments::delete_experiment(&app_data.data_dir, &app_data.workspace_id, &experiment_id).await
+}
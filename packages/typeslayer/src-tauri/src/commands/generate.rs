@@ -13,25 +13,40 @@ use crate::{
     },
     app_data::AppData,
     commands::tasks::{TaskId, start_task},
-    process_controller::{ProcessController, process_output},
+    crash::diagnose_crash,
+    history,
+    process_controller::{CommandOutput, KillReason, ProcessController, ProgressReporter},
+    run_metadata::RunMetadata,
+    trace_progress::{TRACE_PROGRESS_INTERVAL, TraceProgressWatcher},
+    tsconfig::resolve::EFFECTIVE_TSCONFIG_FILENAME,
     type_graph::{TYPE_GRAPH_FILENAME, TypeGraph},
     utils::make_cli_arg,
     validate::{
-        trace_json::{TRACE_JSON_FILENAME, TraceEvent, load_trace_json},
+        trace_json::{
+            ParseWarnings, TRACE_JSON_FILENAME, TraceEvent, load_trace_json,
+            parse_partial_trace_json,
+        },
         types_json::{TYPES_JSON_FILENAME, TypesJsonSchema, load_types_json},
         utils::CPU_PROFILE_FILENAME,
     },
 };
+use chrono::Utc;
 use std::path::Path;
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 use tauri::{AppHandle, State};
 use tokio::{fs, sync::Mutex};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 // Async helper to validate outputs in outputs_dir and return parsed results
 pub async fn validate_types_and_trace_async(
     outputs_dir: &str,
-) -> Result<((TypesJsonSchema, usize), (Vec<TraceEvent>, usize)), String> {
+) -> Result<
+    (
+        (TypesJsonSchema, usize),
+        (Vec<TraceEvent>, ParseWarnings, usize),
+    ),
+    String,
+> {
     let types_path = Path::new(outputs_dir).join(TYPES_JSON_FILENAME.trim_start_matches('/'));
     let types_json_size = std::fs::metadata(&types_path)
         .map(|meta| meta.len() as usize)
@@ -53,7 +68,7 @@ pub async fn validate_types_and_trace_async(
             "[init_types_json] types.json validation failed: {e}\nExpected file at: {types_path:?}",
         )
     })?;
-    let trace = trace_res.map_err(|e| {
+    let (trace, warnings) = trace_res.map_err(|e| {
         format!(
             "[init_trace_json] trace.json validation failed: {e}\nExpected file at: {trace_path:?}",
         )
@@ -63,7 +78,7 @@ pub async fn validate_types_and_trace_async(
         types.len(),
         trace.len()
     );
-    Ok(((types, types_json_size), (trace, trace_json_size)))
+    Ok(((types, types_json_size), (trace, warnings, trace_json_size)))
 }
 
 #[tauri::command]
@@ -72,26 +87,89 @@ pub async fn generate_trace(
     state: State<'_, &Mutex<AppData>>,
     process_controller: State<'_, ProcessController>,
 ) -> Result<(), String> {
-    let _guard = start_task(app, TaskId::GenerateTrace)?;
-
+    let guard = start_task(app, TaskId::GenerateTrace)?;
     let mut app_data = state.lock().await;
+    run_generate_trace(&mut app_data, &process_controller, &guard.reporter(), false).await
+}
+
+/// Run tsc with `--generateTrace`, then load and cache the resulting types.json and trace.json.
+/// With `with_cpu_profile`, the same run writes the CPU profile too, so the two line up.
+///
+/// Output lines and trace progress go to `reporter` while tsc runs.
+pub async fn run_generate_trace(
+    app_data: &mut AppData,
+    process_controller: &ProcessController,
+    reporter: &dyn ProgressReporter,
+    with_cpu_profile: bool,
+) -> Result<(), String> {
     let outputs_dir = app_data.outputs_dir().to_string_lossy().to_string();
     let start_time = Instant::now();
+    let started_at = SystemTime::now();
 
     info!("[generate_trace] will write outputs under {}", outputs_dir);
-    let outputs_dir_for_closure = outputs_dir.clone();
-    let flag = make_cli_arg("--generateTrace", &outputs_dir_for_closure);
+    let cpu_profile_path = Path::new(&outputs_dir).join(CPU_PROFILE_FILENAME);
+    let mut flags = vec![make_cli_arg("--generateTrace", &outputs_dir)];
+    let mut outputs = vec![
+        TYPES_JSON_FILENAME.to_string(),
+        TRACE_JSON_FILENAME.to_string(),
+    ];
+    if with_cpu_profile {
+        flags.push(make_cli_arg(
+            "--generateCpuProfile",
+            &cpu_profile_path.to_string_lossy(),
+        ));
+        outputs.push(CPU_PROFILE_FILENAME.to_string());
+    }
+    let run_metadata = RunMetadata::start(app_data, flags.clone(), outputs).await;
+    let flag = flags.join(" ");
+
+    let mut watcher = TraceProgressWatcher::new(
+        Path::new(&outputs_dir).join(TRACE_JSON_FILENAME.trim_start_matches('/')),
+    );
+    let mut ticker = tokio::time::interval(TRACE_PROGRESS_INTERVAL);
+    let command_output = {
+        let run =
+            app_data.call_typescript(process_controller, TaskId::GenerateTrace, flag, reporter);
+        tokio::pin!(run);
+        loop {
+            tokio::select! {
+                result = &mut run => break result?,
+                _ = ticker.tick() => reporter.trace_progress(watcher.poll().await),
+            }
+        }
+    };
+    let exited_at = Utc::now();
+    reporter.trace_progress(watcher.poll().await);
 
-    let command_output = app_data.call_typescript(&process_controller, flag).await?;
     let exit_success = command_output.status.success();
-    let (stdout, stderr) = process_output(command_output).await?;
+    let CommandOutput {
+        stdout,
+        stderr,
+        killed,
+        ..
+    } = command_output;
+
+    // canceling isn't a failure, so there's nothing to diagnose or report
+    if killed == Some(KillReason::Canceled) {
+        return Err("generate trace canceled".to_string());
+    }
 
-    // Check for failure via exit code or error patterns in stderr
-    if !exit_success || stderr.contains("Error:") || stderr.contains("EACCES:") {
+    // Check for failure via kill, exit code or error patterns in stderr
+    if killed.is_some() || !exit_success || stderr.contains("Error:") || stderr.contains("EACCES:")
+    {
         let duration = start_time.elapsed().as_millis() as u64;
+        let outcome = killed.map_or("failed".to_string(), |reason| reason.to_string());
+        let diagnosis = diagnose_crash(
+            &read_partial_trace(&outputs_dir, started_at).await,
+            &format!("{stdout}\n{stderr}"),
+            killed.map(|reason| reason.to_string()),
+            &app_data.settings,
+        );
+        let description = diagnosis.describe();
+        app_data.crash_diagnosis = Some(diagnosis);
 
         EventGenerateTraceFail::send(
-            &app_data,
+            app_data,
             EventGenerateTraceFailArgs {
                 duration,
                 stdout: Some(stdout.clone()),
@@ -100,7 +178,7 @@ pub async fn generate_trace(
         )
         .await;
         return Err(format!(
-            "generate trace failed:\nSTDOUT:\n{stdout}\nSTDERR:\n{stderr}"
+            "generate trace {outcome}: {description}\nSTDOUT:\n{stdout}\nSTDERR:\n{stderr}"
         ));
     }
 
@@ -115,10 +193,30 @@ pub async fn generate_trace(
         info!("[generate_trace] Could not read outputs directory: {outputs_dir}");
     }
 
-    let ((types, types_json_file_size), (trace, trace_json_file_size)) =
+    let ((types, types_json_file_size), (trace, warnings, trace_json_file_size)) =
         validate_types_and_trace_async(&outputs_dir).await?;
     app_data.types_json = types;
-    app_data.trace_json = trace;
+    app_data.set_trace_json(trace, warnings);
+    app_data.crash_diagnosis = None;
+    if with_cpu_profile {
+        match fs::read_to_string(&cpu_profile_path).await {
+            Ok(contents) => app_data.set_cpu_profile(Some(contents)),
+            Err(e) => error!("Failed to read CPU profile after generation: {}", e),
+        }
+    } else {
+        // an older run's profile would be read as if it profiled this trace
+        app_data.set_cpu_profile(None);
+        if let Err(e) = fs::remove_file(&cpu_profile_path).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            warn!(
+                "[generate_trace] failed to remove {}: {e}",
+                cpu_profile_path.display()
+            );
+        }
+    }
+    attach_effective_tsconfig(app_data).await;
+    run_metadata.finish(app_data, exited_at).await;
     let duration = start_time.elapsed().as_millis() as u64;
     app_data.update_typeslayer_config_toml().await;
     debug!(
@@ -128,7 +226,7 @@ pub async fn generate_trace(
     );
 
     EventGenerateTraceSuccess::send(
-        &app_data,
+        app_data,
         EventGenerateTraceSuccessArgs {
             duration,
             stdout: Some(stdout),
@@ -142,13 +240,56 @@ pub async fn generate_trace(
     Ok(())
 }
 
+/// The trace.json a failed run left behind. Empty when tsc didn't get as far as writing one,
+/// so a trace from an earlier run isn't mistaken for this one.
+async fn read_partial_trace(outputs_dir: &str, started_at: SystemTime) -> Vec<TraceEvent> {
+    let path = Path::new(outputs_dir).join(TRACE_JSON_FILENAME.trim_start_matches('/'));
+    let written_since_start = fs::metadata(&path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified >= started_at);
+    if !written_since_start {
+        return Vec::new();
+    }
+    match fs::read_to_string(&path).await {
+        Ok(text) => parse_partial_trace_json(&text),
+        Err(e) => {
+            warn!("[generate_trace] couldn't read {}: {e}", path.display());
+            Vec::new()
+        }
+    }
+}
+
+/// Record the resolved tsconfig next to the trace, so the analysis stays interpretable once the
+/// config has moved on. Failing to resolve it doesn't fail the run.
+async fn attach_effective_tsconfig(app_data: &mut AppData) {
+    let effective_tsconfig = match app_data.resolve_effective_tsconfig().await {
+        Ok(effective_tsconfig) => effective_tsconfig,
+        Err(e) => {
+            warn!("[generate_trace] could not resolve the effective tsconfig: {e}");
+            app_data.effective_tsconfig = None;
+            return;
+        }
+    };
+    let path = app_data.outputs_dir().join(EFFECTIVE_TSCONFIG_FILENAME);
+    match serde_json::to_string_pretty(&effective_tsconfig) {
+        Ok(json) => {
+            if let Err(e) = fs::write(&path, json).await {
+                warn!("[generate_trace] failed to write {}: {e}", path.display());
+            }
+        }
+        Err(e) => warn!("[generate_trace] failed to serialize the effective tsconfig: {e}"),
+    }
+    app_data.effective_tsconfig = Some(effective_tsconfig);
+}
+
 #[tauri::command]
 pub async fn generate_cpu_profile(
     app: AppHandle,
     state: State<'_, &Mutex<AppData>>,
     process_controller: State<'_, ProcessController>,
 ) -> Result<(), String> {
-    let _guard = start_task(app, TaskId::GenerateCpuProfile)?;
+    let guard = start_task(app, TaskId::GenerateCpuProfile)?;
 
     let mut app_data = state.lock().await;
     let outputs_dir = app_data.outputs_dir().to_string_lossy().to_string();
@@ -160,13 +301,36 @@ pub async fn generate_cpu_profile(
     let outputs_dir_for_closure = outputs_dir.clone();
     let generation_path = Path::new(&outputs_dir_for_closure).join(CPU_PROFILE_FILENAME);
     let flag = make_cli_arg("--generateCpuProfile", &generation_path.to_string_lossy());
+    let run_metadata = RunMetadata::start(
+        &app_data,
+        vec![flag.clone()],
+        vec![CPU_PROFILE_FILENAME.to_string()],
+    )
+    .await;
 
-    let command_output = app_data.call_typescript(&process_controller, flag).await?;
+    let command_output = app_data
+        .call_typescript(
+            &process_controller,
+            TaskId::GenerateCpuProfile,
+            flag,
+            &guard.reporter(),
+        )
+        .await?;
+    let exited_at = Utc::now();
 
-    if !command_output.status.success() {
-        let (stdout, stderr) = process_output(command_output).await?;
+    if command_output.killed == Some(KillReason::Canceled) {
+        return Err("generate CPU profile canceled".to_string());
+    }
+    if command_output.killed.is_some() || !command_output.status.success() {
+        let CommandOutput {
+            stdout,
+            stderr,
+            killed,
+            ..
+        } = command_output;
+        let outcome = killed.map_or("failed".to_string(), |reason| reason.to_string());
         return Err(format!(
-            "generate CPU profile failed:\nSTDOUT:\n{stdout}\nSTDERR:\n{stderr}"
+            "generate CPU profile {outcome}:\nSTDOUT:\n{stdout}\nSTDERR:\n{stderr}"
         ));
     }
 
@@ -174,11 +338,12 @@ pub async fn generate_cpu_profile(
     let path = Path::new(&outputs_dir).join(CPU_PROFILE_FILENAME);
     match tokio::fs::read_to_string(&path).await {
         Ok(contents) => {
-            app_data.cpu_profile = Some(contents);
+            app_data.set_cpu_profile(Some(contents));
             debug!(
                 "[generate_cpu_profile] cached CPU profile of size {} bytes",
                 app_data.cpu_profile.as_ref().map_or(0, |s| s.len())
             );
+            run_metadata.finish(&mut app_data, exited_at).await;
             app_data.update_typeslayer_config_toml().await;
         }
         Err(e) => error!("Failed to read CPU profile after generation: {}", e),
@@ -187,11 +352,22 @@ pub async fn generate_cpu_profile(
     Ok(())
 }
 
+/// Analyze the loaded trace and archive the run, since a fresh analysis is what makes it worth keeping.
 #[tauri::command]
 pub async fn generate_analyze_trace(
     app: AppHandle,
     state: State<'_, &Mutex<AppData>>,
     options: Option<AnalyzeTraceOptions>,
+) -> Result<(), String> {
+    run_generate_analyze_trace(app, state.clone(), options).await?;
+    snapshot_run(*state).await;
+    Ok(())
+}
+
+async fn run_generate_analyze_trace(
+    app: AppHandle,
+    state: State<'_, &Mutex<AppData>>,
+    options: Option<AnalyzeTraceOptions>,
 ) -> Result<(), String> {
     let _guard = start_task(app, TaskId::GenerateAnalyzeTrace)?;
 
@@ -199,6 +375,7 @@ pub async fn generate_analyze_trace(
     let start_time = Instant::now();
 
     let outputs_dir = app_data.outputs_dir().to_string_lossy().to_string();
+    let options = Some(options.unwrap_or_else(|| app_data.thresholds.clone()));
     debug!(
         "[generate_analyze_trace] reading inputs and writing output under {}",
         outputs_dir
@@ -363,16 +540,38 @@ pub async fn generate_all(
     state: State<'_, &Mutex<AppData>>,
     process_controller: State<'_, ProcessController>,
 ) -> Result<(), String> {
-    generate_trace(app.clone(), state.clone(), process_controller.clone()).await?;
-    generate_cpu_profile(app.clone(), state.clone(), process_controller.clone()).await?;
-    generate_analyze_trace(app.clone(), state.clone(), None).await?;
-    generate_type_graph(app, state).await?;
+    {
+        // one tsc run for both, so the CPU profile lines up with the trace
+        let guard = start_task(app.clone(), TaskId::GenerateTrace)?;
+        let _cpu_profile_guard = start_task(app.clone(), TaskId::GenerateCpuProfile)?;
+        let mut app_data = state.lock().await;
+        run_generate_trace(&mut app_data, &process_controller, &guard.reporter(), true).await?;
+    }
+    run_generate_analyze_trace(app.clone(), state.clone(), None).await?;
+    generate_type_graph(app, state.clone()).await?;
+    // after the type graph, so the snapshot has its node and link counts
+    snapshot_run(*state).await;
     Ok(())
 }
 
+/// A failed snapshot shouldn't fail the run it was meant to archive.
+/// Only gathering it holds the lock; writing it doesn't.
+async fn snapshot_run(state: &Mutex<AppData>) {
+    let pending = history::prepare_snapshot(&mut *state.lock().await).await;
+    let written = match pending {
+        Ok(pending) => history::write_snapshot(pending).await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = written {
+        error!("[snapshot_run] failed to snapshot run: {e}");
+    }
+}
+
+/// Cancel the running tsc for `task_id`, or every running tsc when no task is given.
 #[tauri::command]
 pub async fn cancel_generation(
     process_controller: State<'_, ProcessController>,
+    task_id: Option<TaskId>,
 ) -> Result<(), String> {
-    process_controller.request_cancel()
+    process_controller.request_cancel(task_id)
 }
@@ -0,0 +1,63 @@
+use crate::{
+    app_data::{AppData, workspaces::check_workspace_id},
+    history::{self, SnapshotSummary, SnapshotTimeSeries},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Archive the current analysis as a snapshot. Every analyzed run is archived automatically.
+#[tauri::command]
+pub async fn create_snapshot(state: State<'_, &Mutex<AppData>>) -> Result<SnapshotSummary, String> {
+    let pending = history::prepare_snapshot(&mut *state.lock().await).await?;
+    history::write_snapshot(pending).await
+}
+
+/// Snapshots of the active workspace, oldest first.
+#[tauri::command]
+pub async fn get_snapshots(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Vec<SnapshotSummary>, String> {
+    let app_data = state.lock().await;
+    Ok(history::list_snapshots(&app_data.data_dir, &app_data.workspace_id).await)
+}
+
+/// Every snapshot of a workspace (the active one by default) as chartable series.
+#[tauri::command]
+pub async fn get_snapshot_time_series(
+    state: State<'_, &Mutex<AppData>>,
+    workspace_id: Option<String>,
+) -> Result<SnapshotTimeSeries, String> {
+    let app_data = state.lock().await;
+    let workspace_id = workspace_id.unwrap_or_else(|| app_data.workspace_id.clone());
+    check_workspace_id(&workspace_id)?;
+    let summaries = history::list_snapshots(&app_data.data_dir, &workspace_id).await;
+    Ok(SnapshotTimeSeries::from_summaries(&summaries))
+}
+
+/// Replace the active workspace's outputs with the analysis archived in a snapshot.
+#[tauri::command]
+pub async fn restore_snapshot(
+    state: State<'_, &Mutex<AppData>>,
+    snapshot_id: String,
+) -> Result<(), String> {
+    let mut app_data = state.lock().await;
+    let snapshots = history::list_snapshots(&app_data.data_dir, &app_data.workspace_id).await;
+    if !snapshots.iter().any(|s| s.id == snapshot_id) {
+        return Err(format!("Unknown snapshot {snapshot_id:?}"));
+    }
+
+    app_data.clear_outputs_dir().await?;
+    history::restore_snapshot(&app_data.data_dir, &app_data.workspace_id, &snapshot_id).await?;
+    app_data.load_outputs().await;
+    app_data.update_typeslayer_config_toml().await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_snapshot(
+    state: State<'_, &Mutex<AppData>>,
+    snapshot_id: String,
+) -> Result<(), String> {
+    let app_data = state.lock().await;
+    history::delete_snapshot(&app_data.data_dir, &app_data.workspace_id, &snapshot_id).await
+}
@@ -1,13 +1,23 @@
 pub mod actions;
 pub mod app_data;
+pub mod bisect;
 pub mod bug_report;
+pub mod cpu_profile;
+pub mod crash;
+pub mod emit;
+pub mod experiments;
 pub mod generate;
+pub mod history;
 pub mod mcp;
 pub mod preview;
+pub mod program_construction;
 pub mod query;
+pub mod repeated_runs;
 pub mod settings;
 pub mod tasks;
 pub mod treemap;
 pub mod trivia;
+pub mod tsserver;
 pub mod upload;
 pub mod validate;
+pub mod workspaces;
@@ -0,0 +1,50 @@
+use crate::{
+    app_data::AppData,
+    program_construction::{
+        InclusionExplanation, InclusionGraph, ProgramConstructionReport,
+        create_program_construction_report,
+        inclusion::{create_inclusion_graph, explain_inclusion},
+    },
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Parse, bind and module resolution time per file and per `node_modules` package, from the
+/// loaded trace. `limit` caps the files and packages listed.
+#[tauri::command]
+pub async fn get_program_construction(
+    state: State<'_, &Mutex<AppData>>,
+    limit: Option<usize>,
+) -> Result<ProgramConstructionReport, String> {
+    let app_data = state.lock().await;
+    if app_data.trace_json.is_empty() {
+        return Err("No trace available. Please generate one.".to_string());
+    }
+    create_program_construction_report(&app_data.trace_json, limit.unwrap_or(100))
+}
+
+/// Which files reference which, and how each file first got into the program.
+#[tauri::command]
+pub async fn get_inclusion_graph(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<InclusionGraph, String> {
+    let app_data = state.lock().await;
+    if app_data.trace_json.is_empty() {
+        return Err("No trace available. Please generate one.".to_string());
+    }
+    Ok(create_inclusion_graph(&app_data.trace_json))
+}
+
+/// Why `file` is in the program: the file that referenced it first and the reason, back to
+/// what the tsconfig included.
+#[tauri::command]
+pub async fn why_is_file_included(
+    state: State<'_, &Mutex<AppData>>,
+    file: String,
+) -> Result<InclusionExplanation, String> {
+    let app_data = state.lock().await;
+    if app_data.trace_json.is_empty() {
+        return Err("No trace available. Please generate one.".to_string());
+    }
+    explain_inclusion(&create_inclusion_graph(&app_data.trace_json), &file)
+}
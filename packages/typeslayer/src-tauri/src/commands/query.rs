@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use tauri::State;
 use tokio::sync::Mutex;
 
-type Links = Vec<(LinkKind, Vec<(TypeId, String)>)>;
+pub type Links = Vec<(LinkKind, Vec<(TypeId, String)>)>;
 
 #[tauri::command]
 pub async fn get_links_to_type_id(
@@ -15,33 +15,37 @@ pub async fn get_links_to_type_id(
     type_id: usize,
 ) -> Result<Links, String> {
     let app_data = state.lock().await;
+    links_to_type_id(&app_data, type_id)
+}
+
+/// Every type that links to `type_id`, grouped by link kind (largest group first).
+pub fn links_to_type_id(app_data: &AppData, type_id: TypeId) -> Result<Links, String> {
+    let Some(graph) = &app_data.type_graph else {
+        return Err("No type graph available".to_string());
+    };
 
-    if let Some(graph) = &app_data.type_graph {
-        let mut results: Links = graph
-            .link_kind_data_by_kind
-            .iter()
-            .map(|(kind, link_kind_data)| {
-                let mut entries = Vec::new();
-                let sources = link_kind_data.by_target.target_to_sources.get(&type_id);
-                if let Some(sources) = sources {
-                    for source_id in sources {
-                        let name = app_data
-                            .types_json
-                            .get(*source_id)
-                            .map(|t| t.human_readable_name())
-                            .unwrap_or_else(|| "Unknown".to_string());
-                        entries.push((*source_id, name));
-                    }
+    let mut results: Links = graph
+        .link_kind_data_by_kind
+        .iter()
+        .map(|(kind, link_kind_data)| {
+            let mut entries = Vec::new();
+            let sources = link_kind_data.by_target.target_to_sources.get(&type_id);
+            if let Some(sources) = sources {
+                for source_id in sources {
+                    let name = app_data
+                        .types_json
+                        .get(*source_id)
+                        .map(|t| t.human_readable_name())
+                        .unwrap_or_else(|| "Unknown".to_string());
+                    entries.push((*source_id, name));
                 }
-                (kind.clone(), entries)
-            })
-            .filter(|(_, entries)| !entries.is_empty())
-            .collect();
-        results.sort_by_key(|(_, entries)| std::cmp::Reverse(entries.len()));
-        Ok(results)
-    } else {
-        Err("No type graph available".to_string())
-    }
+            }
+            (kind.clone(), entries)
+        })
+        .filter(|(_, entries)| !entries.is_empty())
+        .collect();
+    results.sort_by_key(|(_, entries)| std::cmp::Reverse(entries.len()));
+    Ok(results)
 }
 
 #[tauri::command]
@@ -51,16 +55,19 @@ pub async fn get_resolved_type_by_id(
 ) -> Result<Option<ResolvedType>, String> {
     if let Some(id) = type_id {
         let app_data = state.lock().await;
-        if let Some(t) = app_data.types_json.get(id) {
-            Ok(Some(t.clone()))
-        } else {
-            Err(format!("Type with id {id} not found"))
-        }
+        resolved_type_by_id(&app_data, id).cloned().map(Some)
     } else {
         Ok(None)
     }
 }
 
+pub fn resolved_type_by_id(app_data: &AppData, type_id: TypeId) -> Result<&ResolvedType, String> {
+    app_data
+        .types_json
+        .get(type_id)
+        .ok_or_else(|| format!("Type with id {type_id} not found"))
+}
+
 #[tauri::command]
 pub async fn get_resolved_types_by_ids(
     state: State<'_, &Mutex<AppData>>,
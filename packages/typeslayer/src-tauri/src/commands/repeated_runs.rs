@@ -0,0 +1,31 @@
+use crate::{
+    app_data::AppData,
+    commands::tasks::{TaskId, start_task},
+    process_controller::ProcessController,
+    repeated_runs::{RepeatedRunsRequest, RepeatedRunsResult, load_repeated_runs, run_repeated},
+};
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+/// Generate the trace several times (after optional warmup runs) and aggregate the timings.
+///
+/// Progress is streamed as `task-output` events for the `repeated_runs` task, and
+/// `cancel_generation` with that task id stops it.
+#[tauri::command]
+pub async fn run_repeated_traces(
+    app: AppHandle,
+    state: State<'_, &'static Mutex<AppData>>,
+    process_controller: State<'_, ProcessController>,
+    request: RepeatedRunsRequest,
+) -> Result<RepeatedRunsResult, String> {
+    let guard = start_task(app, TaskId::RepeatedRuns)?;
+    run_repeated(*state, &process_controller, request, &guard.reporter()).await
+}
+
+/// The last repeated-runs result for the active workspace.
+#[tauri::command]
+pub async fn get_repeated_runs(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Option<RepeatedRunsResult>, String> {
+    Ok(load_repeated_runs(&*state.lock().await).await)
+}
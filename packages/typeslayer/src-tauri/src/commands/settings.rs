@@ -160,6 +160,52 @@ pub async fn set_max_stack_size(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_tsc_timeout_seconds(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Option<i32>, String> {
+    let app_data = state.lock().await;
+    Ok(app_data.settings.tsc_timeout_seconds)
+}
+
+#[tauri::command]
+pub async fn set_tsc_timeout_seconds(
+    state: State<'_, &Mutex<AppData>>,
+    seconds: Option<i32>,
+) -> Result<(), String> {
+    if let Some(seconds) = seconds
+        && seconds <= 0
+    {
+        return Err(format!("tscTimeoutSeconds must be positive, got {seconds}"));
+    }
+    let mut app_data = state.lock().await;
+    app_data.settings.tsc_timeout_seconds = seconds;
+    app_data.update_typeslayer_config_toml().await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_tsc_max_rss_mb(state: State<'_, &Mutex<AppData>>) -> Result<Option<i32>, String> {
+    let app_data = state.lock().await;
+    Ok(app_data.settings.tsc_max_rss_mb)
+}
+
+#[tauri::command]
+pub async fn set_tsc_max_rss_mb(
+    state: State<'_, &Mutex<AppData>>,
+    size: Option<i32>,
+) -> Result<(), String> {
+    if let Some(size) = size
+        && size <= 0
+    {
+        return Err(format!("tscMaxRssMb must be positive, got {size}"));
+    }
+    let mut app_data = state.lock().await;
+    app_data.settings.tsc_max_rss_mb = size;
+    app_data.update_typeslayer_config_toml().await;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_typescript_compiler_variant(
     state: State<'_, &Mutex<AppData>>,
@@ -206,6 +252,68 @@ pub async fn set_max_nodes(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_max_workspaces(state: State<'_, &Mutex<AppData>>) -> Result<i32, String> {
+    let app_data = state.lock().await;
+    Ok(app_data.settings.max_workspaces)
+}
+
+#[tauri::command]
+pub async fn set_max_workspaces(
+    state: State<'_, &Mutex<AppData>>,
+    max_workspaces: i32,
+) -> Result<(), String> {
+    if max_workspaces < 0 {
+        return Err("maxWorkspaces must not be negative".to_string());
+    }
+    let mut app_data = state.lock().await;
+    app_data.settings.max_workspaces = max_workspaces;
+    app_data.update_typeslayer_config_toml().await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_workspace_retention_days(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<i32, String> {
+    let app_data = state.lock().await;
+    Ok(app_data.settings.workspace_retention_days)
+}
+
+#[tauri::command]
+pub async fn set_workspace_retention_days(
+    state: State<'_, &Mutex<AppData>>,
+    days: i32,
+) -> Result<(), String> {
+    if days < 0 {
+        return Err("workspaceRetentionDays must not be negative".to_string());
+    }
+    let mut app_data = state.lock().await;
+    app_data.settings.workspace_retention_days = days;
+    app_data.update_typeslayer_config_toml().await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_max_snapshots(state: State<'_, &Mutex<AppData>>) -> Result<i32, String> {
+    let app_data = state.lock().await;
+    Ok(app_data.settings.max_snapshots)
+}
+
+#[tauri::command]
+pub async fn set_max_snapshots(
+    state: State<'_, &Mutex<AppData>>,
+    max_snapshots: i32,
+) -> Result<(), String> {
+    if max_snapshots < 0 {
+        return Err("maxSnapshots must not be negative".to_string());
+    }
+    let mut app_data = state.lock().await;
+    app_data.settings.max_snapshots = max_snapshots;
+    app_data.update_typeslayer_config_toml().await;
+    Ok(())
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AnalyticsInfo {
@@ -1,9 +1,14 @@
 use std::time::SystemTime;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
 
-#[derive(Serialize, Clone)]
+use crate::{
+    process_controller::{OutputStream, ProgressReporter},
+    trace_progress::TraceProgress,
+};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 #[allow(clippy::enum_variant_names)]
 pub enum TaskId {
@@ -11,6 +16,9 @@ pub enum TaskId {
     GenerateCpuProfile,
     GenerateAnalyzeTrace,
     GenerateTypeGraph,
+    Bisect,
+    Experiment,
+    RepeatedRuns,
 }
 
 #[derive(Serialize, Clone)]
@@ -32,7 +40,7 @@ impl Drop for TaskGuard {
         let _ = self.app.emit(
             "tasks",
             TaskProgress {
-                task_id: self.task_id.clone(),
+                task_id: self.task_id,
                 start: 0,
                 done: true,
             },
@@ -40,12 +48,68 @@ impl Drop for TaskGuard {
     }
 }
 
+impl TaskGuard {
+    /// A reporter that forwards a running command's output and progress to the frontend
+    pub fn reporter(&self) -> TaskReporter {
+        TaskReporter {
+            app: self.app.clone(),
+            task_id: self.task_id,
+        }
+    }
+}
+
+/// A single line of output from a task's child process, emitted as a `task-output` event
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOutputLine<'a> {
+    pub task_id: TaskId,
+    pub stream: OutputStream,
+    pub line: &'a str,
+}
+
+/// Progress of a running trace, emitted as a `task-trace-progress` event
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTraceProgress<'a> {
+    pub task_id: TaskId,
+    #[serde(flatten)]
+    pub progress: &'a TraceProgress,
+}
+
+pub struct TaskReporter {
+    app: AppHandle,
+    task_id: TaskId,
+}
+
+impl ProgressReporter for TaskReporter {
+    fn output_line(&self, stream: OutputStream, line: &str) {
+        let _ = self.app.emit(
+            "task-output",
+            TaskOutputLine {
+                task_id: self.task_id,
+                stream,
+                line,
+            },
+        );
+    }
+
+    fn trace_progress(&self, progress: &TraceProgress) {
+        let _ = self.app.emit(
+            "task-trace-progress",
+            TaskTraceProgress {
+                task_id: self.task_id,
+                progress,
+            },
+        );
+    }
+}
+
 /// Start a task and return a guard that will automatically stop it when dropped
 pub fn start_task(app: AppHandle, task_id: TaskId) -> Result<TaskGuard, String> {
     app.emit(
         "tasks",
         TaskProgress {
-            task_id: task_id.clone(),
+            task_id,
             start: SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .unwrap()
@@ -1,7 +1,4 @@
-use crate::{
-    app_data::AppData,
-    treemap::{TreemapNode, build_treemap_from_trace},
-};
+use crate::{app_data::AppData, treemap::TreemapNode};
 use tauri::State;
 use tokio::sync::Mutex;
 
@@ -9,7 +6,6 @@ use tokio::sync::Mutex;
 pub async fn get_treemap_data(
     state: State<'_, &Mutex<AppData>>,
 ) -> Result<Vec<TreemapNode>, String> {
-    let app_data = state.lock().await;
-    let treemap_data = build_treemap_from_trace(&app_data.trace_json)?;
-    Ok(treemap_data)
+    let mut app_data = state.lock().await;
+    Ok(app_data.ensure_treemap()?.to_vec())
 }
@@ -0,0 +1,24 @@
+use crate::tsserver::{TsserverAnalysis, analyze_tsserver_trace_dir};
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Slow requests by command, `updateGraph` cost per project and semantic-check latency per file,
+/// from the traces tsserver wrote to `trace_dir`. `limit` caps the projects and files listed.
+#[tauri::command]
+pub async fn analyze_tsserver_trace(
+    trace_dir: String,
+    limit: Option<usize>,
+) -> Result<TsserverAnalysis, String> {
+    let trace_dir = PathBuf::from(trace_dir);
+    let analysis = tauri::async_runtime::spawn_blocking(move || {
+        analyze_tsserver_trace_dir(&trace_dir, limit.unwrap_or(100))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    debug!(
+        "[analyze_tsserver_trace] {} commands over {} trace files",
+        analysis.commands.len(),
+        analysis.trace_files.len()
+    );
+    Ok(analysis)
+}
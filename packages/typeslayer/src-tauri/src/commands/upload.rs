@@ -1,6 +1,7 @@
 use crate::{
     analyze_trace::{AnalyzeTraceResult, analyze_trace, constants::ANALYZE_TRACE_FILENAME},
     app_data::AppData,
+    cpu_profile::CpuProfile,
     type_graph::{TYPE_GRAPH_FILENAME, TypeGraph},
     validate::{
         trace_json::{TRACE_JSON_FILENAME, load_trace_json},
@@ -71,15 +72,16 @@ fn find_paired_file(path: &Path, from: &str, to: &str) -> PathBuf {
 async fn regenerate_analysis_after_upload(
     state: &State<'_, &Mutex<AppData>>,
 ) -> Result<(), String> {
-    let outputs_dir = state
-        .lock()
-        .await
-        .outputs_dir()
-        .to_string_lossy()
-        .to_string();
+    let (outputs_dir, thresholds) = {
+        let app_data = state.lock().await;
+        (
+            app_data.outputs_dir().to_string_lossy().to_string(),
+            app_data.thresholds.clone(),
+        )
+    };
 
     // Regenerate analyze trace
-    let analyze_result = analyze_trace(&outputs_dir, None);
+    let analyze_result = analyze_trace(&outputs_dir, Some(thresholds));
     let mut app_data = state.lock().await;
     if let Ok(result) = analyze_result {
         app_data.analyze_trace = Some(result);
@@ -125,8 +127,8 @@ pub async fn upload_trace_json(
                 .await
                 .map_err(|e| format!("Invalid trace.json format: {e}"))
         },
-        |data, parsed| {
-            data.trace_json = parsed;
+        |data, (trace, warnings)| {
+            data.set_trace_json(trace, warnings);
             data.analyze_trace = None;
             data.type_graph = None;
         },
@@ -198,8 +200,8 @@ pub async fn upload_types_json(
                     .await
                     .map_err(|e| format!("Invalid trace.json format: {e}"))
             },
-            |data, parsed| {
-                data.trace_json = parsed;
+            |data, (trace, warnings)| {
+                data.set_trace_json(trace, warnings);
             },
             &state,
         )
@@ -224,17 +226,17 @@ pub async fn upload_cpu_profile(
         CPU_PROFILE_FILENAME,
         async |path| {
             tauri::async_runtime::spawn_blocking(move || {
-                let file = std::fs::File::open(&path)
+                let contents = std::fs::read_to_string(&path)
                     .map_err(|e| format!("could not open cpu profile at {path:?}: {e}"))?;
-                serde_json::from_reader(BufReader::new(file))
-                    .map(|contents: serde_json::Value| contents.to_string())
-                    .map_err(|e| format!("Invalid CPU profile format (not valid JSON): {e}"))
+                serde_json::from_str::<CpuProfile>(&contents)
+                    .map_err(|e| format!("Invalid CPU profile format: {e}"))?;
+                Ok(contents)
             })
             .await
             .map_err(|e| e.to_string())?
         },
         |data, contents| {
-            data.cpu_profile = Some(contents);
+            data.set_cpu_profile(Some(contents));
         },
         &state,
     )
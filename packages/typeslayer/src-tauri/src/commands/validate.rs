@@ -1,5 +1,6 @@
 use crate::analyze_trace::{AnalyzeTraceResult, constants::ANALYZE_TRACE_FILENAME};
 use crate::app_data::AppData;
+use crate::cpu_profile::CpuProfile;
 use crate::type_graph::{TYPE_GRAPH_FILENAME, TypeGraph};
 use crate::validate::utils::CPU_PROFILE_FILENAME;
 use crate::validate::{
@@ -42,7 +43,7 @@ pub async fn validate_trace_json(state: State<'_, &Mutex<AppData>>) -> Result<()
         app_data.outputs_dir().join(TRACE_JSON_FILENAME)
     };
 
-    let parsed = load_trace_json(path).await?;
+    let (parsed, warnings) = load_trace_json(path).await?;
 
     debug!(
         "[validate_trace_json] Validated and loaded {} trace events",
@@ -50,7 +51,7 @@ pub async fn validate_trace_json(state: State<'_, &Mutex<AppData>>) -> Result<()
     );
 
     let mut app_data = state.lock().await;
-    app_data.trace_json = parsed;
+    app_data.set_trace_json(parsed, warnings);
     Ok(())
 }
 
@@ -84,9 +85,8 @@ pub async fn validate_cpu_profile(state: State<'_, &Mutex<AppData>>) -> Result<(
         .await
         .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
 
-    // Validate it's valid JSON
-    serde_json::from_str::<serde_json::Value>(&contents)
-        .map_err(|e| format!("Failed to parse {} as JSON: {}", path.display(), e))?;
+    serde_json::from_str::<CpuProfile>(&contents)
+        .map_err(|e| format!("Failed to parse {} as a CPU profile: {}", path.display(), e))?;
 
     debug!(
         "[validate_cpu_profile] Validated and loaded CPU profile ({} bytes)",
@@ -94,7 +94,7 @@ pub async fn validate_cpu_profile(state: State<'_, &Mutex<AppData>>) -> Result<(
     );
 
     let mut app_data = state.lock().await;
-    app_data.cpu_profile = Some(contents);
+    app_data.set_cpu_profile(Some(contents));
     Ok(())
 }
 
@@ -0,0 +1,130 @@
+use crate::{
+    app_data::{
+        AppData,
+        workspaces::{Workspace, WorkspaceRegistry, check_workspace_id, workspace_outputs_dir},
+    },
+    utils::{compute_window_title, set_window_title},
+};
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, State};
+use tokio::{fs, sync::Mutex};
+use tracing::debug;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceInfo {
+    #[serde(flatten)]
+    pub workspace: Workspace,
+    pub active: bool,
+    /// Total size of the workspace's output files in bytes
+    pub outputs_size: u64,
+}
+
+async fn outputs_size(dir: &Path) -> u64 {
+    let Ok(mut entries) = fs::read_dir(dir).await else {
+        return 0;
+    };
+    let mut total = 0;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if let Ok(metadata) = entry.metadata().await {
+            total += metadata.len();
+        }
+    }
+    total
+}
+
+/// Every known workspace, most recently used first.
+pub async fn list_workspaces(app_data: &AppData) -> Vec<WorkspaceInfo> {
+    let registry = WorkspaceRegistry::load(&app_data.data_dir).await;
+    let mut infos = Vec::new();
+    for workspace in registry.sorted() {
+        let dir = workspace_outputs_dir(&app_data.data_dir, &workspace.id);
+        infos.push(WorkspaceInfo {
+            active: workspace.id == app_data.workspace_id,
+            outputs_size: outputs_size(&dir).await,
+            workspace,
+        });
+    }
+    infos
+}
+
+#[tauri::command]
+pub async fn get_workspaces(
+    state: State<'_, &Mutex<AppData>>,
+) -> Result<Vec<WorkspaceInfo>, String> {
+    let app_data = state.lock().await;
+    Ok(list_workspaces(&app_data).await)
+}
+
+#[tauri::command]
+pub async fn get_active_workspace_id(state: State<'_, &Mutex<AppData>>) -> Result<String, String> {
+    Ok(state.lock().await.workspace_id.clone())
+}
+
+/// Reopen a known workspace: its project root and tsconfig are restored and its outputs loaded.
+#[tauri::command]
+pub async fn switch_workspace(
+    app: AppHandle,
+    state: State<'_, &Mutex<AppData>>,
+    id: String,
+) -> Result<(), String> {
+    let mut app_data = state.lock().await;
+    if app_data.workspace_id == id {
+        return Ok(());
+    }
+
+    let registry = WorkspaceRegistry::load(&app_data.data_dir).await;
+    let workspace = registry
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| format!("Unknown workspace {id:?}"))?;
+
+    let window_title = compute_window_title(workspace.project_root.clone()).await;
+    set_window_title(&app, window_title).await?;
+
+    app_data.project_root = workspace.project_root.clone();
+    app_data.load_project_config().await?;
+    app_data.package_manager = AppData::find_package_manager(workspace.project_root).await?;
+    app_data.discover_tsconfigs().await?;
+    app_data.selected_tsconfig = workspace
+        .tsconfig
+        .filter(|tsconfig| app_data.tsconfig_paths.contains(tsconfig));
+    app_data.activate_workspace().await?;
+    app_data.update_typeslayer_config_toml().await;
+    debug!("[switch_workspace] switched to {}", app_data.workspace_id);
+    Ok(())
+}
+
+/// Delete a workspace's outputs and forget it. The active workspace is only cleared, not forgotten.
+#[tauri::command]
+pub async fn delete_workspace(state: State<'_, &Mutex<AppData>>, id: String) -> Result<(), String> {
+    check_workspace_id(&id)?;
+    let mut app_data = state.lock().await;
+    if app_data.workspace_id == id {
+        return app_data.clear_outputs_dir().await;
+    }
+
+    let mut registry = WorkspaceRegistry::load(&app_data.data_dir).await;
+    if registry.get(&id).is_none() {
+        return Err(format!("Unknown workspace {id:?}"));
+    }
+    registry.remove(&app_data.data_dir, &id).await?;
+    registry.save(&app_data.data_dir).await
+}
+
+/// Apply the retention policy now, returning the ids of the deleted workspaces.
+#[tauri::command]
+pub async fn prune_workspaces(state: State<'_, &Mutex<AppData>>) -> Result<Vec<String>, String> {
+    let app_data = state.lock().await;
+    let mut registry = WorkspaceRegistry::load(&app_data.data_dir).await;
+    let removed = registry
+        .prune(
+            &app_data.data_dir,
+            app_data.retention_policy(),
+            &app_data.workspace_id,
+        )
+        .await?;
+    registry.save(&app_data.data_dir).await?;
+    Ok(removed)
+}
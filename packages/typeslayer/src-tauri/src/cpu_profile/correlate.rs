@@ -0,0 +1,260 @@
+use crate::{
+    analyze_trace::{
+        AnalyzeTraceOptions, EventSpanEvent, HotSpot, create_span_tree, create_spans, get_hotspots,
+    },
+    cpu_profile::{
+        CHECKER_GROUPS, CpuProfileAnalysis, HotSpotProfile, SpanFunctionTime, SpanGroupTime,
+        TraceProfileCorrelation, display_name,
+    },
+    validate::trace_json::TraceEvent,
+};
+use indexmap::IndexMap;
+
+/// Functions listed per hot spot
+const FUNCTIONS_PER_HOT_SPOT: usize = 5;
+
+/// Below this alignment, the trace and profile are probably from different runs
+const MIN_ALIGNMENT: f64 = 0.8;
+
+/// Functions under this share of a hot spot are left out of its summary
+const MIN_SUMMARY_SHARE: f64 = 0.1;
+
+const GC_GROUP: &str = "GC";
+
+/// Line up a CPU profile's samples with the span tree of a trace from the same tsc run, and
+/// break each hot spot down by the functions that were running during it.
+///
+/// The two files use different clocks, so they're aligned on `checkSourceFile`, which shows up
+/// in both: the first sample under it should fall in the first `checkSourceFile` span.
+pub fn correlate(
+    profile: &CpuProfileAnalysis,
+    trace: &[TraceEvent],
+    options: &AnalyzeTraceOptions,
+) -> Result<TraceProfileCorrelation, String> {
+    if profile.samples.is_empty() {
+        return Err(
+            "The CPU profile has no sample timestamps, so it can't be lined up with the trace"
+                .to_string(),
+        );
+    }
+
+    let parse_result = create_spans(trace)?;
+    let mut check_spans: Vec<(f64, f64)> = parse_result
+        .spans
+        .iter()
+        .filter(|span| {
+            matches!(
+                &span.event,
+                EventSpanEvent::TraceEvent(TraceEvent::CheckSourceFile { .. })
+            )
+        })
+        .map(|span| (span.start, span.end))
+        .collect();
+    let hot_spots = get_hotspots(&create_span_tree(parse_result, options))?;
+    check_spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let check_spans = merge_intervals(check_spans);
+
+    let groups: Vec<(&'static str, Vec<bool>)> = CHECKER_GROUPS
+        .iter()
+        .map(|(group, functions)| (*group, profile.checker_group_membership(functions).1))
+        .collect();
+    // `checkSourceFile` is the first group
+    let (_, under_check) = &groups[0];
+    let check_samples: Vec<(f64, f64)> = profile
+        .samples
+        .iter()
+        .filter(|sample| under_check[sample.node])
+        .map(|sample| (sample.time, sample.time + sample.duration * 1000.0))
+        .collect();
+
+    let (Some(first_span), Some(last_span), Some(first_sample), Some(last_sample)) = (
+        check_spans.first(),
+        check_spans.last(),
+        check_samples.first(),
+        check_samples.last(),
+    ) else {
+        return Err(
+            "Can't line up the CPU profile with the trace: they need to both include checkSourceFile (profiles of tsgo don't)"
+                .to_string(),
+        );
+    };
+
+    // try lining up both the start and the end of checking, in case either was missed between samples
+    let (offset, alignment) = [first_sample.0 - first_span.0, last_sample.1 - last_span.1]
+        .into_iter()
+        .map(|offset| {
+            let inside = check_samples
+                .iter()
+                .filter(|(time, _)| contains(&check_spans, time - offset))
+                .count();
+            (offset, inside as f64 / check_samples.len() as f64)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0.0, 0.0));
+
+    let warning = (alignment < MIN_ALIGNMENT).then(|| {
+        format!(
+            "Only {:.0}% of the samples in checkSourceFile line up with the trace, so the trace and CPU profile are probably from different runs",
+            alignment * 100.0
+        )
+    });
+
+    let correlator = Correlator {
+        profile,
+        groups,
+        offset,
+    };
+    Ok(TraceProfileCorrelation {
+        offset,
+        alignment,
+        warning,
+        hot_spots: hot_spots
+            .iter()
+            .map(|hot_spot| correlator.hot_spot(hot_spot))
+            .collect(),
+    })
+}
+
+struct Correlator<'a> {
+    profile: &'a CpuProfileAnalysis,
+    /// Which nodes are under each checker group
+    groups: Vec<(&'static str, Vec<bool>)>,
+    offset: f64,
+}
+
+impl Correlator<'_> {
+    fn hot_spot(&self, hot_spot: &HotSpot) -> HotSpotProfile {
+        let samples = &self.profile.samples;
+        let start = samples.partition_point(|sample| sample.time < hot_spot.start + self.offset);
+        let end = samples.partition_point(|sample| sample.time < hot_spot.end + self.offset);
+        let samples = &samples[start..end];
+
+        let sampled_time = samples
+            .iter()
+            .fold(0.0, |sum, sample| sum + sample.duration);
+        let share = |time: f64| {
+            if sampled_time > 0.0 {
+                time / sampled_time
+            } else {
+                0.0
+            }
+        };
+
+        let mut self_times: IndexMap<usize, f64> = IndexMap::new();
+        for sample in samples {
+            let frame = self.profile.nodes[sample.node].frame;
+            *self_times.entry(frame).or_default() += sample.duration;
+        }
+        let mut functions: Vec<SpanFunctionTime> = self_times
+            .into_iter()
+            .map(|(frame, self_time)| {
+                let call_frame = &self.profile.frames[frame];
+                SpanFunctionTime {
+                    function_name: display_name(call_frame),
+                    url: call_frame.url.clone(),
+                    line_number: call_frame.line_number,
+                    self_time,
+                    share: share(self_time),
+                }
+            })
+            .collect();
+        functions.sort_by(|a, b| b.self_time.total_cmp(&a.self_time));
+        functions.truncate(FUNCTIONS_PER_HOT_SPOT);
+
+        let checker_groups: Vec<SpanGroupTime> = self
+            .groups
+            .iter()
+            .map(|(group, under)| {
+                let time = samples
+                    .iter()
+                    .filter(|sample| under[sample.node])
+                    .fold(0.0, |sum, sample| sum + sample.duration);
+                SpanGroupTime {
+                    group: group.to_string(),
+                    time,
+                    share: share(time),
+                }
+            })
+            .collect();
+        let gc_share = checker_groups
+            .iter()
+            .find(|group| group.group == GC_GROUP)
+            .map_or(0.0, |group| group.share);
+
+        let duration = hot_spot.duration / 1000.0;
+        HotSpotProfile {
+            description: hot_spot.description.clone(),
+            path: hot_spot.path.clone(),
+            types: hot_spot.types.clone(),
+            duration,
+            sampled_time,
+            sample_count: samples.len(),
+            summary: summarize(duration, samples.is_empty(), &functions, gc_share),
+            functions,
+            checker_groups,
+            gc_share,
+            children: hot_spot
+                .children
+                .iter()
+                .map(|child| self.hot_spot(child))
+                .collect(),
+        }
+    }
+}
+
+/// e.g. `3.0s, 70% in structuredTypeRelatedToWorker, 20% GC`
+fn summarize(
+    duration: f64,
+    no_samples: bool,
+    functions: &[SpanFunctionTime],
+    gc_share: f64,
+) -> String {
+    let mut parts = vec![if duration >= 1000.0 {
+        format!("{:.1}s", duration / 1000.0)
+    } else {
+        format!("{duration:.0}ms")
+    }];
+    if no_samples {
+        parts.push("no samples".to_string());
+    }
+    let gc_functions = CHECKER_GROUPS
+        .iter()
+        .find(|(group, _)| *group == GC_GROUP)
+        .map_or(&[][..], |(_, functions)| *functions);
+    parts.extend(
+        functions
+            .iter()
+            .filter(|function| {
+                function.share >= MIN_SUMMARY_SHARE
+                    && !gc_functions.contains(&function.function_name.as_str())
+            })
+            .map(|function| {
+                format!(
+                    "{:.0}% in {}",
+                    function.share * 100.0,
+                    function.function_name
+                )
+            }),
+    );
+    if gc_share >= MIN_SUMMARY_SHARE {
+        parts.push(format!("{:.0}% GC", gc_share * 100.0));
+    }
+    parts.join(", ")
+}
+
+/// Sorted, possibly overlapping intervals into sorted, disjoint ones.
+fn merge_intervals(intervals: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    let mut merged: Vec<(f64, f64)> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+fn contains(intervals: &[(f64, f64)], time: f64) -> bool {
+    let i = intervals.partition_point(|(start, _)| *start <= time);
+    i > 0 && time < intervals[i - 1].1
+}
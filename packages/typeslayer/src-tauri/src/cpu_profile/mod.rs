@@ -0,0 +1,420 @@
+pub mod correlate;
+pub mod types;
+
+pub use types::*;
+
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+/// TypeScript checker functions worth breaking a profile down by, with the functions each
+/// group stands for. The names are tsc's own, so they only show up in profiles of the JS compiler.
+pub const CHECKER_GROUPS: [(&str, &[&str]); 4] = [
+    (
+        "checkSourceFile",
+        &["checkSourceFile", "checkSourceFileWorker"],
+    ),
+    (
+        "isRelatedTo",
+        &[
+            "isRelatedTo",
+            "isTypeRelatedTo",
+            "checkTypeRelatedTo",
+            "recursiveTypeRelatedTo",
+            "structuredTypeRelatedTo",
+        ],
+    ),
+    (
+        "instantiateType",
+        &[
+            "instantiateType",
+            "instantiateTypeWithAlias",
+            "instantiateTypeWorker",
+        ],
+    ),
+    ("GC", &["(garbage collector)"]),
+];
+
+/// V8's synthetic root frame, the caller of everything
+const ROOT_FUNCTION_NAME: &str = "(root)";
+
+#[derive(Debug, Clone)]
+struct AnalyzedNode {
+    frame: usize,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// Milliseconds with this node on top of the stack
+    self_time: f64,
+    /// Milliseconds in this node and everything under it
+    total_time: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Sample {
+    /// Microseconds, on the profile's clock
+    time: f64,
+    /// Milliseconds until the next sample
+    duration: f64,
+    node: usize,
+}
+
+/// A CPU profile with the time of every sample attributed to its call tree.
+#[derive(Debug, Clone)]
+pub struct CpuProfileAnalysis {
+    frames: Vec<CallFrame>,
+    nodes: Vec<AnalyzedNode>,
+    roots: Vec<usize>,
+    /// In time order. Empty for older profiles that only have hit counts.
+    samples: Vec<Sample>,
+    /// Every node, parents before children
+    preorder: Vec<usize>,
+    /// Milliseconds from the first sample to the end of the profile
+    pub total_time: f64,
+    pub sample_count: usize,
+}
+
+impl CpuProfileAnalysis {
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let profile: CpuProfile = serde_json::from_str(contents)
+            .map_err(|e| format!("Failed to parse CPU profile: {e}"))?;
+        Self::new(&profile)
+    }
+
+    pub fn new(profile: &CpuProfile) -> Result<Self, String> {
+        let index: HashMap<u64, usize> = profile
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id, i))
+            .collect();
+
+        let mut frame_index: IndexMap<&CallFrame, usize> = IndexMap::new();
+        let mut nodes: Vec<AnalyzedNode> = profile
+            .nodes
+            .iter()
+            .map(|node| {
+                let next = frame_index.len();
+                let frame = *frame_index.entry(&node.call_frame).or_insert(next);
+                AnalyzedNode {
+                    frame,
+                    parent: None,
+                    children: Vec::with_capacity(node.children.len()),
+                    self_time: 0.0,
+                    total_time: 0.0,
+                }
+            })
+            .collect();
+        for (i, node) in profile.nodes.iter().enumerate() {
+            for child_id in &node.children {
+                let &child = index
+                    .get(child_id)
+                    .ok_or_else(|| format!("node {} has unknown child {child_id}", node.id))?;
+                nodes[i].children.push(child);
+                nodes[child].parent = Some(i);
+            }
+        }
+
+        // each sample lasts until the next one; the last one until the profile ends
+        let sample_count = profile.samples.len();
+        let mut samples = Vec::new();
+        if sample_count > 0 && sample_count == profile.time_deltas.len() {
+            let mut timestamp = profile.start_time;
+            for (i, sample) in profile.samples.iter().enumerate() {
+                timestamp += profile.time_deltas[i];
+                let duration = match profile.time_deltas.get(i + 1) {
+                    Some(delta) => *delta,
+                    None => profile.end_time - timestamp,
+                };
+                let &node = index
+                    .get(sample)
+                    .ok_or_else(|| format!("sample {i} is of unknown node {sample}"))?;
+                let duration = duration.max(0.0) / 1000.0;
+                nodes[node].self_time += duration;
+                samples.push(Sample {
+                    time: timestamp,
+                    duration,
+                    node,
+                });
+            }
+            // V8 occasionally records a negative delta
+            samples.sort_by(|a, b| a.time.total_cmp(&b.time));
+        } else {
+            // older profiles only have hit counts, so spread the duration evenly over them
+            let hits: u64 = profile.nodes.iter().map(|node| node.hit_count).sum();
+            if hits > 0 {
+                let interval = (profile.end_time - profile.start_time).max(0.0) / hits as f64;
+                for (i, node) in profile.nodes.iter().enumerate() {
+                    nodes[i].self_time = node.hit_count as f64 * interval / 1000.0;
+                }
+            }
+        }
+
+        let roots: Vec<usize> = (0..nodes.len())
+            .filter(|&i| nodes[i].parent.is_none())
+            .collect();
+        let mut preorder = Vec::with_capacity(nodes.len());
+        let mut stack: Vec<usize> = roots.iter().rev().copied().collect();
+        while let Some(i) = stack.pop() {
+            preorder.push(i);
+            stack.extend(nodes[i].children.iter().rev());
+        }
+        for &i in preorder.iter().rev() {
+            let total = nodes[i].self_time
+                + nodes[i]
+                    .children
+                    .iter()
+                    .map(|&child| nodes[child].total_time)
+                    .sum::<f64>();
+            nodes[i].total_time = total;
+        }
+        let total_time = roots.iter().map(|&root| nodes[root].total_time).sum();
+
+        Ok(Self {
+            frames: frame_index.into_keys().cloned().collect(),
+            nodes,
+            roots,
+            samples,
+            preorder,
+            total_time,
+            sample_count: sample_count.max(
+                profile
+                    .nodes
+                    .iter()
+                    .map(|node| node.hit_count as usize)
+                    .sum(),
+            ),
+        })
+    }
+
+    pub fn summary(&self, limit: usize) -> CpuProfileSummary {
+        let mut functions = self.functions();
+        functions.truncate(limit);
+        CpuProfileSummary {
+            total_time: self.total_time,
+            sample_count: self.sample_count,
+            sample_interval: if self.sample_count > 0 {
+                self.total_time / self.sample_count as f64
+            } else {
+                0.0
+            },
+            checker_groups: self.checker_groups(),
+            functions,
+        }
+    }
+
+    /// Time per function, slowest first by self time.
+    pub fn functions(&self) -> Vec<FunctionTime> {
+        let mut self_times = vec![0.0; self.frames.len()];
+        let mut total_times = vec![0.0; self.frames.len()];
+        // how many times each frame is on the current stack, so recursion is only counted once
+        let mut on_stack = vec![0u32; self.frames.len()];
+
+        let mut stack: Vec<(usize, bool)> = self.roots.iter().map(|&root| (root, false)).collect();
+        while let Some((i, exiting)) = stack.pop() {
+            let node = &self.nodes[i];
+            if exiting {
+                on_stack[node.frame] -= 1;
+                continue;
+            }
+            self_times[node.frame] += node.self_time;
+            if on_stack[node.frame] == 0 {
+                total_times[node.frame] += node.total_time;
+            }
+            on_stack[node.frame] += 1;
+            stack.push((i, true));
+            stack.extend(node.children.iter().map(|&child| (child, false)));
+        }
+
+        let mut functions: Vec<FunctionTime> = self
+            .frames
+            .iter()
+            .enumerate()
+            .filter(|(frame, _)| total_times[*frame] > 0.0)
+            .map(|(frame, call_frame)| FunctionTime {
+                function_name: display_name(call_frame),
+                url: call_frame.url.clone(),
+                line_number: call_frame.line_number,
+                column_number: call_frame.column_number,
+                self_time: self_times[frame],
+                total_time: total_times[frame],
+            })
+            .collect();
+        functions.sort_by(|a, b| {
+            b.self_time
+                .total_cmp(&a.self_time)
+                .then(b.total_time.total_cmp(&a.total_time))
+        });
+        functions
+    }
+
+    /// Which call-tree nodes are one of `functions` (`own`), and which have one of them on
+    /// their stack (`under`).
+    fn checker_group_membership(&self, functions: &[&str]) -> (Vec<bool>, Vec<bool>) {
+        let own: Vec<bool> = self
+            .nodes
+            .iter()
+            .map(|node| functions.contains(&self.frames[node.frame].function_name.as_str()))
+            .collect();
+        let mut under = vec![false; self.nodes.len()];
+        for &i in &self.preorder {
+            under[i] = own[i] || self.nodes[i].parent.is_some_and(|parent| under[parent]);
+        }
+        (own, under)
+    }
+
+    pub fn checker_groups(&self) -> Vec<CheckerGroupTime> {
+        CHECKER_GROUPS
+            .iter()
+            .map(|(group, functions)| {
+                let (own, under) = self.checker_group_membership(functions);
+                let sum = |members: &[bool]| -> f64 {
+                    self.nodes
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| members[*i])
+                        // `sum` of nothing is -0.0
+                        .fold(0.0, |sum, (_, node)| sum + node.self_time)
+                };
+                let total_time = sum(&under);
+                CheckerGroupTime {
+                    group: group.to_string(),
+                    functions: functions.iter().map(|f| f.to_string()).collect(),
+                    self_time: sum(&own),
+                    total_time,
+                    share: if self.total_time > 0.0 {
+                        total_time / self.total_time
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// Callers first: the roots are where the program starts, children are what they called.
+    pub fn top_down(&self, options: &CallTreeOptions) -> Vec<CallTreeNode> {
+        let min_time = self.total_time * options.min_share;
+        let mut roots: Vec<usize> = self.roots.clone();
+        // V8's `(root)` frame only holds the real roots, so start under it
+        if let [root] = roots[..]
+            && self.frames[self.nodes[root].frame].function_name == ROOT_FUNCTION_NAME
+        {
+            roots = self.nodes[root].children.clone();
+        }
+        let mut tree: Vec<CallTreeNode> = roots
+            .into_iter()
+            .filter(|&i| self.nodes[i].total_time >= min_time)
+            .map(|i| self.top_down_node(i, 0, options.max_depth, min_time))
+            .collect();
+        sort_call_tree(&mut tree);
+        tree
+    }
+
+    fn top_down_node(
+        &self,
+        i: usize,
+        depth: usize,
+        max_depth: usize,
+        min_time: f64,
+    ) -> CallTreeNode {
+        let node = &self.nodes[i];
+        let call_frame = &self.frames[node.frame];
+        let mut children = Vec::new();
+        let mut pruned_children = 0;
+        for &child in &node.children {
+            if depth >= max_depth || self.nodes[child].total_time < min_time {
+                pruned_children += 1;
+            } else {
+                children.push(self.top_down_node(child, depth + 1, max_depth, min_time));
+            }
+        }
+        sort_call_tree(&mut children);
+        CallTreeNode {
+            function_name: display_name(call_frame),
+            url: call_frame.url.clone(),
+            line_number: call_frame.line_number,
+            self_time: node.self_time,
+            total_time: node.total_time,
+            pruned_children,
+            children,
+        }
+    }
+
+    /// Callees first: the roots are the functions time was spent in, children are who called them.
+    pub fn bottom_up(&self, options: &CallTreeOptions) -> Vec<CallTreeNode> {
+        let min_time = self.total_time * options.min_share;
+
+        #[derive(Default)]
+        struct Merged {
+            time: f64,
+            callers: IndexMap<usize, Merged>,
+        }
+
+        let mut roots: IndexMap<usize, Merged> = IndexMap::new();
+        for node in &self.nodes {
+            if node.self_time <= 0.0 {
+                continue;
+            }
+            let mut merged = roots.entry(node.frame).or_default();
+            merged.time += node.self_time;
+            let mut caller = node.parent;
+            for _ in 0..options.max_depth {
+                let Some(i) = caller else { break };
+                let frame = self.nodes[i].frame;
+                if self.frames[frame].function_name == ROOT_FUNCTION_NAME {
+                    break;
+                }
+                merged = merged.callers.entry(frame).or_default();
+                merged.time += node.self_time;
+                caller = self.nodes[i].parent;
+            }
+        }
+
+        fn convert(
+            frames: &[CallFrame],
+            frame: usize,
+            merged: Merged,
+            min_time: f64,
+        ) -> CallTreeNode {
+            let call_frame = &frames[frame];
+            let mut pruned_children = 0;
+            let mut children = Vec::new();
+            for (caller, merged) in merged.callers {
+                if merged.time < min_time {
+                    pruned_children += 1;
+                } else {
+                    children.push(convert(frames, caller, merged, min_time));
+                }
+            }
+            sort_call_tree(&mut children);
+            CallTreeNode {
+                function_name: display_name(call_frame),
+                url: call_frame.url.clone(),
+                line_number: call_frame.line_number,
+                self_time: merged.time,
+                total_time: merged.time,
+                pruned_children,
+                children,
+            }
+        }
+
+        let mut tree: Vec<CallTreeNode> = roots
+            .into_iter()
+            .filter(|(_, merged)| merged.time >= min_time)
+            .map(|(frame, merged)| convert(&self.frames, frame, merged, min_time))
+            .collect();
+        sort_call_tree(&mut tree);
+        tree
+    }
+}
+
+fn display_name(call_frame: &CallFrame) -> String {
+    if call_frame.function_name.is_empty() {
+        "(anonymous)".to_string()
+    } else {
+        call_frame.function_name.clone()
+    }
+}
+
+fn sort_call_tree(nodes: &mut [CallTreeNode]) {
+    nodes.sort_by(|a, b| b.total_time.total_cmp(&a.total_time));
+}
@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A V8 `.cpuprofile`, as written by `tsc --generateCpuProfile` (and Chrome DevTools).
+/// Times are in microseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuProfile {
+    pub nodes: Vec<ProfileNode>,
+    pub start_time: f64,
+    pub end_time: f64,
+    /// The node on top of the stack at each sample
+    #[serde(default)]
+    pub samples: Vec<u64>,
+    /// Time since the previous sample (or `start_time`, for the first)
+    #[serde(default)]
+    pub time_deltas: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileNode {
+    pub id: u64,
+    pub call_frame: CallFrame,
+    #[serde(default)]
+    pub hit_count: u64,
+    #[serde(default)]
+    pub children: Vec<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// Empty for anonymous functions; V8's own entries look like `(garbage collector)`
+    pub function_name: String,
+    #[serde(default)]
+    pub script_id: String,
+    #[serde(default)]
+    pub url: String,
+    /// 0-based, -1 when unknown
+    #[serde(default)]
+    pub line_number: i64,
+    /// 0-based, -1 when unknown
+    #[serde(default)]
+    pub column_number: i64,
+}
+
+/// A function, however many call sites it has. Times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionTime {
+    pub function_name: String,
+    pub url: String,
+    pub line_number: i64,
+    pub column_number: i64,
+    /// Time with this function on top of the stack
+    pub self_time: f64,
+    /// Time with this function anywhere on the stack, counted once however deep it recurses
+    pub total_time: f64,
+}
+
+/// Time spent in (or under) TypeScript checker functions that matter for type performance.
+/// Groups overlap: `instantiateType` mostly runs inside `checkSourceFile`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckerGroupTime {
+    /// e.g. `isRelatedTo`
+    pub group: String,
+    /// The functions counted as this group
+    pub functions: Vec<String>,
+    pub self_time: f64,
+    pub total_time: f64,
+    /// `total_time` as a fraction of the whole profile
+    pub share: f64,
+}
+
+/// A node of a top-down (callees as children) or bottom-up (callers as children) call tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallTreeNode {
+    pub function_name: String,
+    pub url: String,
+    pub line_number: i64,
+    /// Top-down: time with this node on top of the stack. Bottom-up: the same as `total_time`.
+    pub self_time: f64,
+    /// Top-down: time in this node and everything it called.
+    /// Bottom-up: the root function's self time spent on this call path.
+    pub total_time: f64,
+    /// Children that were cut off by `max_depth` or `min_share`, however many there were
+    pub pruned_children: usize,
+    pub children: Vec<CallTreeNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallTreeOptions {
+    /// Levels below the roots to include
+    pub max_depth: usize,
+    /// Leave out nodes under this fraction of the whole profile's time
+    pub min_share: f64,
+}
+
+impl Default for CallTreeOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 12,
+            min_share: 0.005,
+        }
+    }
+}
+
+/// The headline numbers of a CPU profile. Times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuProfileSummary {
+    pub total_time: f64,
+    pub sample_count: usize,
+    /// Average time between samples
+    pub sample_interval: f64,
+    pub checker_groups: Vec<CheckerGroupTime>,
+    /// Slowest first, by self time
+    pub functions: Vec<FunctionTime>,
+}
+
+/// One function's share of the samples taken during a trace span. Times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpanFunctionTime {
+    pub function_name: String,
+    pub url: String,
+    pub line_number: i64,
+    /// Time with this function on top of the stack
+    pub self_time: f64,
+    /// `self_time` as a fraction of the span's sampled time
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpanGroupTime {
+    pub group: String,
+    /// Time with one of the group's functions anywhere on the stack
+    pub time: f64,
+    pub share: f64,
+}
+
+/// A hot spot from the trace, broken down by the CPU samples taken while it ran.
+/// Times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotSpotProfile {
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<i64>>,
+    /// How long the span took, according to the trace
+    pub duration: f64,
+    /// How much of it the profile has samples for
+    pub sampled_time: f64,
+    pub sample_count: usize,
+    /// Most self time first
+    pub functions: Vec<SpanFunctionTime>,
+    pub checker_groups: Vec<SpanGroupTime>,
+    /// Fraction of the sampled time spent collecting garbage
+    pub gc_share: f64,
+    /// e.g. `3.0s, 70% in structuredTypeRelatedToWorker, 20% GC`
+    pub summary: String,
+    pub children: Vec<HotSpotProfile>,
+}
+
+/// A trace and a CPU profile of the same tsc run, lined up.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceProfileCorrelation {
+    /// Microseconds to add to a trace timestamp to get the profile's
+    pub offset: f64,
+    /// Fraction of the samples taken under `checkSourceFile` that land inside a `checkSourceFile`
+    /// span once aligned: close to 1 when both files come from the same run
+    pub alignment: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    pub hot_spots: Vec<HotSpotProfile>,
+}
@@ -0,0 +1,183 @@
+pub mod types;
+
+pub use types::*;
+
+use crate::{
+    analyze_trace::{
+        CheckSite, EventSpanEvent, SourcePositions, check_node, create_spans, innermost_site,
+    },
+    app_data::settings::Settings,
+    validate::trace_json::TraceEvent,
+};
+
+/// Output lines that give away how tsc failed. Matched case-insensitively.
+const KNOWN_FAILURES: [(&str, CrashKind); 6] = [
+    ("javascript heap out of memory", CrashKind::HeapOutOfMemory),
+    ("reached heap limit", CrashKind::HeapOutOfMemory),
+    (
+        "ineffective mark-compacts near heap limit",
+        CrashKind::HeapOutOfMemory,
+    ),
+    ("maximum call stack size exceeded", CrashKind::StackOverflow),
+    (
+        "excessively deep and possibly infinite",
+        CrashKind::ExcessivelyDeep,
+    ),
+    (
+        "excessive stack depth comparing types",
+        CrashKind::ExcessivelyDeep,
+    ),
+];
+
+/// Heap to try when `maxOldSpaceSize` isn't set. V8 picks 2-4 GB on its own.
+const SUGGESTED_MAX_OLD_SPACE_SIZE: i32 = 8192;
+/// Stack to try when `maxStackSize` isn't set. V8's default is 984 KB.
+const SUGGESTED_MAX_STACK_SIZE: i32 = 4096;
+/// `--stack-size` has to fit in the main thread's stack, 8 MB on Linux and macOS, or node
+/// segfaults instead of throwing.
+const MAX_STACK_SIZE: i32 = 7800;
+
+/// Work out what tsc was doing when it failed, from the (partial) trace it wrote and its
+/// stdout and stderr.
+pub fn diagnose_crash(
+    trace: &[TraceEvent],
+    output: &str,
+    killed: Option<String>,
+    settings: &Settings,
+) -> CrashDiagnosis {
+    let mut failures: Vec<KnownFailure> = Vec::new();
+    for line in output.lines() {
+        let lowercase = line.to_lowercase();
+        for (pattern, kind) in KNOWN_FAILURES {
+            if lowercase.contains(pattern) && !failures.iter().any(|seen| seen.kind == kind) {
+                failures.push(KnownFailure {
+                    kind,
+                    line: line.trim().to_string(),
+                });
+            }
+        }
+    }
+
+    // a crashed trace can't have unmatched ends, but an edited one can
+    let (spans, unclosed_stack) = match create_spans(trace) {
+        Ok(parse_result) => (parse_result.spans, parse_result.unclosed_stack),
+        Err(_) => (Vec::new(), Vec::new()),
+    };
+    let last_ts = trace
+        .iter()
+        .map(|event| event.common().ts)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    let checking = unclosed_stack.iter().rev().find_map(|event| {
+        check_node(event)
+            .and_then(|(path, _)| path)
+            .map(|path| (event, path))
+    });
+    let last_checked = checking.and_then(|(open, file)| {
+        let started = open.common().ts;
+        let last = spans
+            .iter()
+            .filter(|span| span.start >= started)
+            .filter_map(|span| match &span.event {
+                EventSpanEvent::TraceEvent(event) => Some((span.end, event)),
+                EventSpanEvent::Root { .. } => None,
+            })
+            .filter(|(_, event)| {
+                check_node(event).is_some_and(|(path, range)| {
+                    range.is_some() && path.is_none_or(|path| path == file)
+                })
+            })
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, event)| event)?;
+        innermost_site([last, open]).map(|site| SourcePositions::default().locate(site))
+    });
+    let checking_file = checking.map(|(_, file)| file.to_string());
+
+    let mut suggestions = Vec::new();
+    if failures
+        .iter()
+        .any(|failure| failure.kind == CrashKind::HeapOutOfMemory)
+    {
+        let current = settings.max_old_space_size;
+        suggestions.push(SettingSuggestion {
+            setting: "maxOldSpaceSize".to_string(),
+            current,
+            suggested: current.map_or(SUGGESTED_MAX_OLD_SPACE_SIZE, |size| size * 2),
+            reason: "tsc ran out of heap. It's in MB.".to_string(),
+        });
+    }
+    if failures
+        .iter()
+        .any(|failure| failure.kind == CrashKind::StackOverflow)
+    {
+        let current = settings.max_stack_size;
+        let suggested = current.map_or(SUGGESTED_MAX_STACK_SIZE, |size| {
+            (size * 2).min(MAX_STACK_SIZE)
+        });
+        if current.is_none_or(|size| size < suggested) {
+            suggestions.push(SettingSuggestion {
+                setting: "maxStackSize".to_string(),
+                current,
+                suggested,
+                reason: format!(
+                    "tsc overflowed the stack. It's in KB, and node segfaults above about {MAX_STACK_SIZE}."
+                ),
+            });
+        }
+    }
+
+    CrashDiagnosis {
+        killed,
+        failures,
+        open_stack: unclosed_stack
+            .into_iter()
+            .map(|event| OpenEvent {
+                open_for: (last_ts - event.common().ts) / 1000.0,
+                event,
+            })
+            .collect(),
+        checking_file,
+        last_checked,
+        suggestions,
+        trace_events: trace.len(),
+    }
+}
+
+impl CrashDiagnosis {
+    /// One line on what went wrong, where, and what to change.
+    pub fn describe(&self) -> String {
+        let what = match self.failures.first().map(|failure| failure.kind) {
+            Some(CrashKind::HeapOutOfMemory) => "tsc ran out of memory".to_string(),
+            Some(CrashKind::StackOverflow) => "tsc overflowed the stack".to_string(),
+            Some(CrashKind::ExcessivelyDeep) => "tsc hit an excessively deep type".to_string(),
+            None if self.killed.is_some() => "tsc stopped".to_string(),
+            None => "tsc failed".to_string(),
+        };
+        let where_ = match (&self.checking_file, &self.last_checked) {
+            (
+                Some(file),
+                Some(CheckSite {
+                    path,
+                    start: Some(start),
+                    ..
+                }),
+            ) => format!(
+                " while checking {file}, after {path}:{}:{}",
+                start.line, start.character
+            ),
+            (Some(file), _) => format!(" while checking {file}"),
+            (None, _) => match self.open_stack.last() {
+                Some(open) => format!(" in {}", open.event.name()),
+                None => String::new(),
+            },
+        };
+        let mut description = format!("{what}{where_}");
+        for suggestion in &self.suggestions {
+            description.push_str(&format!(
+                ". Try {} {}",
+                suggestion.setting, suggestion.suggested
+            ));
+        }
+        description
+    }
+}
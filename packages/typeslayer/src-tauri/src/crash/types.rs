@@ -0,0 +1,62 @@
+use crate::{analyze_trace::CheckSite, validate::trace_json::TraceEvent};
+use serde::{Deserialize, Serialize};
+
+/// A way tsc is known to fail, recognized from what it printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CrashKind {
+    /// V8 ran out of heap: `FATAL ERROR: ... JavaScript heap out of memory`
+    HeapOutOfMemory,
+    /// `RangeError: Maximum call stack size exceeded`
+    StackOverflow,
+    /// TS2589 `Type instantiation is excessively deep and possibly infinite` or TS2321
+    /// `Excessive stack depth comparing types`, which tsc reports instead of overflowing
+    ExcessivelyDeep,
+}
+
+/// A line of output that matched a known failure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownFailure {
+    pub kind: CrashKind,
+    pub line: String,
+}
+
+/// A begin event that never ended.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenEvent {
+    pub event: TraceEvent,
+    /// Milliseconds from when it began to the last event in the trace
+    pub open_for: f64,
+}
+
+/// A setting to change before trying again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingSuggestion {
+    /// `maxOldSpaceSize` or `maxStackSize`
+    pub setting: String,
+    pub current: Option<i32>,
+    pub suggested: i32,
+    pub reason: String,
+}
+
+/// What tsc was doing when a run crashed, from the trace it left behind and what it printed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashDiagnosis {
+    /// Why TypeSlayer killed tsc, if it did
+    pub killed: Option<String>,
+    pub failures: Vec<KnownFailure>,
+    /// Begin events with no end, outermost first
+    pub open_stack: Vec<OpenEvent>,
+    /// The file being checked when the trace stops
+    pub checking_file: Option<String>,
+    /// The last node in `checking_file` the checker finished. Check events are written when
+    /// they finish, so the node it was stuck on comes at or after this one.
+    pub last_checked: Option<CheckSite>,
+    pub suggestions: Vec<SettingSuggestion>,
+    /// Events in the trace, which tsc stops writing partway through when it crashes
+    pub trace_events: usize,
+}
@@ -0,0 +1,226 @@
+pub mod types;
+
+pub use types::*;
+
+use crate::{
+    analyze_trace::{EventSpan, EventSpanEvent, create_spans},
+    validate::{trace_json::TraceEvent, types_json::ResolvedType},
+};
+use indexmap::IndexMap;
+
+/// Types listed per declaration file
+const TYPES_PER_DECLARATION_FILE: usize = 5;
+
+/// A span an emit span can contain
+struct Interval<T> {
+    start: f64,
+    end: f64,
+    value: T,
+}
+
+impl<T> Interval<T> {
+    fn new(span: &EventSpan, value: T) -> Self {
+        Self {
+            start: span.start,
+            end: span.end,
+            value,
+        }
+    }
+}
+
+/// The interval `start` falls in, of intervals sorted by start that don't overlap.
+fn enclosing<T>(intervals: &[Interval<T>], start: f64) -> Option<usize> {
+    let i = intervals.partition_point(|interval| interval.start <= start);
+    (i > 0 && start < intervals[i - 1].end).then(|| i - 1)
+}
+
+#[derive(Default)]
+struct TypeEntry {
+    time: f64,
+    count: usize,
+    declaration_files: Vec<usize>,
+}
+
+/// Emit time in total, per output file and per transformed source file, and the types the
+/// checker worked on while writing declarations. `types` gives them names when it's loaded.
+/// `limit` caps the files and types listed.
+pub fn create_emit_report(
+    trace: &[TraceEvent],
+    types: &[ResolvedType],
+    limit: usize,
+) -> Result<EmitReport, String> {
+    let parse_result = create_spans(trace)?;
+
+    let mut total = 0.0;
+    let mut build_info = 0.0;
+    let mut declarations: Vec<Interval<DeclarationEmit>> = Vec::new();
+    let mut js_files: Vec<Interval<JsEmit>> = Vec::new();
+    let mut transforms: Vec<Interval<(&str, f64)>> = Vec::new();
+    let mut type_spans: Vec<Interval<(Vec<i64>, f64)>> = Vec::new();
+
+    for span in &parse_result.spans {
+        let EventSpanEvent::TraceEvent(event) = &span.event else {
+            continue;
+        };
+        let duration = span.duration / 1000.0;
+        match event {
+            TraceEvent::Emit { .. } => total += duration,
+            TraceEvent::EmitBuildInfo { .. } => build_info += duration,
+            TraceEvent::EmitDeclarationFileOrBundle { args, .. } => {
+                declarations.push(Interval::new(
+                    span,
+                    DeclarationEmit {
+                        declaration_file_path: args.declaration_file_path.clone(),
+                        duration,
+                        transform: 0.0,
+                        checker: 0.0,
+                        type_ids: Vec::new(),
+                    },
+                ))
+            }
+            TraceEvent::EmitJsFileOrBundle { args, .. } => js_files.push(Interval::new(
+                span,
+                JsEmit {
+                    js_file_path: args.js_file_path.clone(),
+                    duration,
+                    transform: 0.0,
+                },
+            )),
+            TraceEvent::TransformNodes { args, .. } => {
+                transforms.push(Interval::new(span, (args.path.as_str(), duration)))
+            }
+            TraceEvent::StructuredTypeRelatedTo { args, .. } => type_spans.push(Interval::new(
+                span,
+                (vec![args.source_id, args.target_id], duration),
+            )),
+            TraceEvent::GetVariancesWorker { args, .. } => {
+                type_spans.push(Interval::new(span, (vec![args.id], duration)))
+            }
+            TraceEvent::CheckTypeParameterDeferred { args, .. } => {
+                type_spans.push(Interval::new(span, (vec![args.id], duration)))
+            }
+            _ => {}
+        }
+    }
+    declarations.sort_by(|a, b| a.start.total_cmp(&b.start));
+    js_files.sort_by(|a, b| a.start.total_cmp(&b.start));
+    transforms.sort_by(|a, b| a.start.total_cmp(&b.start));
+    type_spans.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut transform_times: IndexMap<&str, TransformTime> = IndexMap::new();
+    let mut transform = 0.0;
+    for Interval {
+        start,
+        value: (path, duration),
+        ..
+    } in &transforms
+    {
+        transform += duration;
+        let time = transform_times
+            .entry(path)
+            .or_insert_with(|| TransformTime {
+                path: path.to_string(),
+                js: 0.0,
+                declaration: 0.0,
+                total: 0.0,
+            });
+        time.total += duration;
+        if let Some(i) = enclosing(&declarations, *start) {
+            time.declaration += duration;
+            declarations[i].value.transform += duration;
+        } else if let Some(i) = enclosing(&js_files, *start) {
+            time.js += duration;
+            js_files[i].value.transform += duration;
+        }
+    }
+
+    // only the outermost checker spans, since the ones inside are part of their time
+    let mut type_costs: IndexMap<i64, TypeEntry> = IndexMap::new();
+    let mut per_declaration: Vec<IndexMap<i64, f64>> = vec![IndexMap::new(); declarations.len()];
+    let mut outer_end = f64::NEG_INFINITY;
+    for Interval {
+        start,
+        end,
+        value: (type_ids, duration),
+    } in &type_spans
+    {
+        if *start < outer_end {
+            continue;
+        }
+        outer_end = *end;
+        let Some(i) = enclosing(&declarations, *start) else {
+            continue;
+        };
+        declarations[i].value.checker += duration;
+        for type_id in type_ids {
+            *per_declaration[i].entry(*type_id).or_default() += duration;
+            let entry = type_costs.entry(*type_id).or_default();
+            entry.time += duration;
+            entry.count += 1;
+            if entry.declaration_files.last() != Some(&i) {
+                entry.declaration_files.push(i);
+            }
+        }
+    }
+    for (declaration, mut type_times) in declarations.iter_mut().zip(per_declaration) {
+        type_times.sort_by(|_, a, _, b| b.total_cmp(a));
+        declaration.value.type_ids = type_times
+            .into_keys()
+            .take(TYPES_PER_DECLARATION_FILE)
+            .collect();
+    }
+
+    let mut types_list: Vec<EmitTypeCost> = type_costs
+        .into_iter()
+        .map(|(type_id, entry)| EmitTypeCost {
+            type_id,
+            name: usize::try_from(type_id)
+                .ok()
+                .and_then(|id| types.get(id))
+                .map(|resolved| resolved.human_readable_name()),
+            time: entry.time,
+            count: entry.count,
+            declaration_files: entry
+                .declaration_files
+                .iter()
+                .map(|i| declarations[*i].value.declaration_file_path.clone())
+                .collect(),
+        })
+        .collect();
+    types_list.sort_by(|a, b| b.time.total_cmp(&a.time));
+    types_list.truncate(limit);
+
+    let declaration_file_count = declarations.len();
+    let js_file_count = js_files.len();
+    let mut declaration_files: Vec<DeclarationEmit> =
+        declarations.into_iter().map(|d| d.value).collect();
+    let mut js_files: Vec<JsEmit> = js_files.into_iter().map(|js| js.value).collect();
+    let declaration = declaration_files
+        .iter()
+        .fold(0.0, |sum, d| sum + d.duration);
+    let declaration_checker = declaration_files.iter().fold(0.0, |sum, d| sum + d.checker);
+    let js = js_files.iter().fold(0.0, |sum, js| sum + js.duration);
+
+    declaration_files.sort_by(|a, b| b.duration.total_cmp(&a.duration));
+    declaration_files.truncate(limit);
+    js_files.sort_by(|a, b| b.duration.total_cmp(&a.duration));
+    js_files.truncate(limit);
+    let mut transforms: Vec<TransformTime> = transform_times.into_values().collect();
+    transforms.sort_by(|a, b| b.total.total_cmp(&a.total));
+    transforms.truncate(limit);
+
+    Ok(EmitReport {
+        total,
+        declaration,
+        js,
+        build_info,
+        transform,
+        declaration_checker,
+        declaration_file_count,
+        js_file_count,
+        declaration_files,
+        js_files,
+        transforms,
+        types: types_list,
+    })
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// One `emitDeclarationFileOrBundle`: writing a `.d.ts`. Times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclarationEmit {
+    pub declaration_file_path: String,
+    pub duration: f64,
+    /// In `transformNodes`, turning the source file into declarations
+    pub transform: f64,
+    /// In the checker, relating and computing variance of types, e.g. to print an inferred type
+    pub checker: f64,
+    /// The types the checker spent the most time on, most first
+    pub type_ids: Vec<i64>,
+}
+
+/// One `emitJsFileOrBundle`: writing a `.js`. Times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsEmit {
+    pub js_file_path: Option<String>,
+    pub duration: f64,
+    /// In `transformNodes`, e.g. downleveling and stripping types
+    pub transform: f64,
+}
+
+/// `transformNodes` time for one source file, by what it was emitted to. Times are in
+/// milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransformTime {
+    pub path: String,
+    pub js: f64,
+    pub declaration: f64,
+    pub total: f64,
+}
+
+/// A type the checker worked on during declaration emit, which is usually an inferred type
+/// being printed into a `.d.ts`. Adding an annotation where it's inferred avoids the work.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmitTypeCost {
+    pub type_id: i64,
+    /// From `types.json`, when it's loaded
+    pub name: Option<String>,
+    /// Milliseconds in checker work involving the type
+    pub time: f64,
+    pub count: usize,
+    pub declaration_files: Vec<String>,
+}
+
+/// Where time goes writing `.js` and `.d.ts` files. Times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmitReport {
+    /// In `emit`, over the whole program
+    pub total: f64,
+    pub declaration: f64,
+    pub js: f64,
+    pub build_info: f64,
+    pub transform: f64,
+    /// Checker work during declaration emit
+    pub declaration_checker: f64,
+    pub declaration_file_count: usize,
+    pub js_file_count: usize,
+    /// Slowest first
+    pub declaration_files: Vec<DeclarationEmit>,
+    /// Slowest first
+    pub js_files: Vec<JsEmit>,
+    /// Most total time first
+    pub transforms: Vec<TransformTime>,
+    /// Most time first
+    pub types: Vec<EmitTypeCost>,
+}
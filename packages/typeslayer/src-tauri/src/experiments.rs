@@ -0,0 +1,493 @@
+use crate::{
+    analyze_trace::{DepthLimitKind, analyze_trace, constants::ANALYZE_TRACE_FILENAME},
+    app_data::{AppData, settings::TypeScriptCompilerVariant},
+    commands::tasks::TaskId,
+    process_controller::{KillReason, OutputStream, ProcessController, ProgressReporter},
+    stats::{SampleStats, welch_t_test},
+    treemap::build_treemap_from_trace,
+    tsconfig::resolve::{EFFECTIVE_TSCONFIG_FILENAME, PerformanceOptions},
+    utils::{EXPERIMENTS_DIRECTORY, make_cli_arg},
+    validate::{
+        trace_json::{TRACE_JSON_FILENAME, load_trace_json},
+        types_json::{TYPES_JSON_FILENAME, load_types_json},
+    },
+};
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tokio::{fs, sync::Mutex};
+use tracing::{debug, info, warn};
+
+pub const EXPERIMENT_RESULT_FILENAME: &str = "experiment.json";
+
+/// One configuration to run the project under.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExperimentVariant {
+    /// Column header in the comparison, e.g. `skipLibCheck`
+    pub name: String,
+    /// Appended to the extra tsc flags, e.g. `--skipLibCheck` or `--types node,jest`
+    #[serde(default)]
+    pub flags: String,
+    /// Run with this compiler instead of the configured one
+    #[serde(default)]
+    pub compiler_variant: Option<TypeScriptCompilerVariant>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExperimentRequest {
+    /// The first variant is the baseline every other one is compared to
+    pub variants: Vec<ExperimentVariant>,
+    /// Measured runs per variant. Timings are only called significant with at least two.
+    #[serde(default = "default_repetitions")]
+    pub repetitions: usize,
+    /// Runs per variant made first and thrown away
+    #[serde(default)]
+    pub warmup: usize,
+}
+
+fn default_repetitions() -> usize {
+    1
+}
+
+/// What one variant measured. Times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantResult {
+    pub name: String,
+    /// The extra tsc flags the variant actually ran with
+    pub extra_tsc_flags: String,
+    pub compiler_variant: TypeScriptCompilerVariant,
+    /// Why the variant produced no trace, if it didn't
+    pub error: Option<String>,
+    pub exit_code: Option<i32>,
+    /// How long tsc ran, start to finish (the mean over repetitions)
+    pub wall_time: f64,
+    /// Sum of every `checkSourceFile` span (the mean over repetitions)
+    pub total_check_time: f64,
+    #[serde(default)]
+    pub wall_time_stats: SampleStats,
+    #[serde(default)]
+    pub total_check_time_stats: SampleStats,
+    pub type_count: usize,
+    pub hot_spot_count: usize,
+    pub duplicate_packages: usize,
+    pub depth_limit_counts: IndexMap<DepthLimitKind, usize>,
+    pub root_file_count: Option<usize>,
+    pub performance: Option<PerformanceOptions>,
+}
+
+/// One metric across every variant, in variant order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonRow {
+    pub metric: String,
+    /// `None` where a variant failed to produce a trace
+    pub values: Vec<Option<f64>>,
+    /// Change relative to the baseline (the first variant) in percent
+    pub deltas: Vec<Option<f64>>,
+    /// Whether the change from the baseline is beyond run-to-run noise. `None` for the baseline
+    /// itself and for metrics that don't vary between runs.
+    #[serde(default)]
+    pub significant: Vec<Option<bool>>,
+}
+
+/// A side-by-side table: one column per variant, one row per metric.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExperimentComparison {
+    pub columns: Vec<String>,
+    pub rows: Vec<ComparisonRow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExperimentResult {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub workspace_id: String,
+    pub variants: Vec<VariantResult>,
+    pub comparison: ExperimentComparison,
+}
+
+impl ExperimentComparison {
+    pub fn from_variants(variants: &[VariantResult]) -> Self {
+        let ok = |variant: &VariantResult| variant.error.is_none();
+        let mut rows = Vec::new();
+        let mut row =
+            |metric: &str,
+             value: &dyn Fn(&VariantResult) -> Option<f64>,
+             stats: Option<&dyn Fn(&VariantResult) -> &SampleStats>| {
+                let values: Vec<Option<f64>> = variants
+                    .iter()
+                    .map(|variant| if ok(variant) { value(variant) } else { None })
+                    .collect();
+                let baseline = values.first().copied().flatten();
+                let deltas = values
+                    .iter()
+                    .map(|value| match (baseline, value) {
+                        (Some(baseline), Some(value)) if baseline != 0.0 => {
+                            Some((value - baseline) / baseline * 100.0)
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                let significant = variants
+                    .iter()
+                    .enumerate()
+                    .map(|(index, variant)| match (stats, variants.first()) {
+                        (Some(stats), Some(baseline))
+                            if index > 0 && ok(baseline) && ok(variant) =>
+                        {
+                            Some(welch_t_test(stats(baseline), stats(variant)).significant)
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                rows.push(ComparisonRow {
+                    metric: metric.to_string(),
+                    values,
+                    deltas,
+                    significant,
+                });
+            };
+
+        row(
+            "wall time (ms)",
+            &|v| Some(v.wall_time),
+            Some(&|v| &v.wall_time_stats),
+        );
+        row(
+            "total check time (ms)",
+            &|v| Some(v.total_check_time),
+            Some(&|v| &v.total_check_time_stats),
+        );
+        row("types", &|v| Some(v.type_count as f64), None);
+        row("hot spots", &|v| Some(v.hot_spot_count as f64), None);
+        row(
+            "duplicate packages",
+            &|v| Some(v.duplicate_packages as f64),
+            None,
+        );
+        row(
+            "root files",
+            &|v| v.root_file_count.map(|count| count as f64),
+            None,
+        );
+        for kind in DepthLimitKind::new_counts_map().into_keys() {
+            let metric = serde_plain::to_string(&kind).unwrap_or_default();
+            row(
+                &metric,
+                &|v| Some(v.depth_limit_counts.get(&kind).copied().unwrap_or(0) as f64),
+                None,
+            );
+        }
+
+        Self {
+            columns: variants
+                .iter()
+                .map(|variant| variant.name.clone())
+                .collect(),
+            rows,
+        }
+    }
+}
+
+pub fn experiments_dir(data_dir: &Path, workspace_id: &str) -> PathBuf {
+    data_dir.join(EXPERIMENTS_DIRECTORY).join(workspace_id)
+}
+
+/// Run the project under each variant (`warmup + repetitions` times) and compare the trace analyses
+/// side by side.
+///
+/// Each variant writes to its own directory under `data_dir/experiments`, where its
+/// analyze-trace.json is kept; the much larger trace.json and types.json are removed once measured.
+pub async fn run_experiment(
+    app_data: &'static Mutex<AppData>,
+    process_controller: &ProcessController,
+    request: ExperimentRequest,
+    reporter: &dyn ProgressReporter,
+) -> Result<ExperimentResult, String> {
+    if request.variants.is_empty() {
+        return Err("An experiment needs at least one variant".to_string());
+    }
+    if request.repetitions == 0 {
+        return Err("An experiment needs at least one repetition".to_string());
+    }
+
+    let created_at = Utc::now();
+    let id = created_at.format("%Y%m%dT%H%M%S%3fZ").to_string();
+    let (base, workspace_id, experiment_dir) = {
+        let app_data = app_data.lock().await;
+        let dir = experiments_dir(&app_data.data_dir, &app_data.workspace_id).join(&id);
+        // only the settings are copied, so the app stays usable while variants run
+        let base = app_data.detached(app_data.project_root.clone(), dir.clone());
+        (base, app_data.workspace_id.clone(), dir)
+    };
+
+    let mut variants = Vec::with_capacity(request.variants.len());
+    for (index, variant) in request.variants.iter().enumerate() {
+        let message = format!(
+            "[experiment] variant {}/{}: {}",
+            index + 1,
+            request.variants.len(),
+            variant.name
+        );
+        info!("{message}");
+        reporter.output_line(OutputStream::Stdout, &message);
+
+        let mut probe = base.detached(
+            base.project_root.clone(),
+            experiment_dir.join(index.to_string()),
+        );
+        if !variant.flags.trim().is_empty() {
+            probe.settings.extra_tsc_flags = format!(
+                "{} {}",
+                probe.settings.extra_tsc_flags,
+                variant.flags.trim()
+            )
+            .trim()
+            .to_string();
+        }
+        if let Some(compiler_variant) = variant.compiler_variant {
+            probe.settings.typescript_compiler_variant = compiler_variant;
+        }
+
+        variants.push(run_variant(&probe, variant, &request, process_controller, reporter).await?);
+    }
+
+    let result = ExperimentResult {
+        id,
+        created_at,
+        workspace_id,
+        comparison: ExperimentComparison::from_variants(&variants),
+        variants,
+    };
+    let json = serde_json::to_string_pretty(&result)
+        .map_err(|e| format!("Failed to serialize experiment: {e}"))?;
+    fs::create_dir_all(&experiment_dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {e}", experiment_dir.display()))?;
+    fs::write(experiment_dir.join(EXPERIMENT_RESULT_FILENAME), json)
+        .await
+        .map_err(|e| format!("Failed to write experiment: {e}"))?;
+    Ok(result)
+}
+
+/// Run tsc for one variant. A variant that fails is recorded rather than failing the
+/// experiment, unless it was canceled.
+///
+/// Every measured run contributes timing samples; counts come from the analysis of the last run.
+async fn run_variant(
+    probe: &AppData,
+    variant: &ExperimentVariant,
+    request: &ExperimentRequest,
+    process_controller: &ProcessController,
+    reporter: &dyn ProgressReporter,
+) -> Result<VariantResult, String> {
+    let outputs_dir = probe.outputs_dir();
+    fs::create_dir_all(&outputs_dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {e}", outputs_dir.display()))?;
+
+    let mut result = VariantResult {
+        name: variant.name.clone(),
+        extra_tsc_flags: probe.settings.extra_tsc_flags.clone(),
+        compiler_variant: probe.settings.typescript_compiler_variant,
+        error: None,
+        exit_code: None,
+        wall_time: 0.0,
+        total_check_time: 0.0,
+        wall_time_stats: SampleStats::default(),
+        total_check_time_stats: SampleStats::default(),
+        type_count: 0,
+        hot_spot_count: 0,
+        duplicate_packages: 0,
+        depth_limit_counts: DepthLimitKind::new_counts_map(),
+        root_file_count: None,
+        performance: None,
+    };
+
+    match probe.resolve_effective_tsconfig().await {
+        Ok(effective_tsconfig) => {
+            result.root_file_count = Some(effective_tsconfig.root_file_count);
+            result.performance = Some(effective_tsconfig.performance.clone());
+            if let Ok(json) = serde_json::to_string_pretty(&effective_tsconfig) {
+                let _ = fs::write(outputs_dir.join(EFFECTIVE_TSCONFIG_FILENAME), json).await;
+            }
+        }
+        Err(e) => warn!(
+            "[experiment] could not resolve the tsconfig for {}: {e}",
+            variant.name
+        ),
+    }
+
+    let trace_path = outputs_dir.join(TRACE_JSON_FILENAME.trim_start_matches('/'));
+    let types_path = outputs_dir.join(TYPES_JSON_FILENAME.trim_start_matches('/'));
+    let runs = request.warmup + request.repetitions;
+    let mut wall_times = Vec::with_capacity(request.repetitions);
+    let mut total_check_times = Vec::with_capacity(request.repetitions);
+    for run in 0..runs {
+        if runs > 1 {
+            reporter.output_line(
+                OutputStream::Stdout,
+                &format!(
+                    "[experiment] {} run {}/{runs}{}",
+                    variant.name,
+                    run + 1,
+                    if run < request.warmup {
+                        " (warmup)"
+                    } else {
+                        ""
+                    }
+                ),
+            );
+        }
+
+        // a run that writes no trace mustn't be measured from the previous run's
+        remove_outputs(&[&trace_path, &types_path]).await;
+        let start = Instant::now();
+        let flag = make_cli_arg("--generateTrace", &outputs_dir.to_string_lossy());
+        let output = probe
+            .call_typescript(process_controller, TaskId::Experiment, flag, reporter)
+            .await?;
+        let wall_time = start.elapsed().as_secs_f64() * 1000.0;
+        result.exit_code = output.status.code();
+
+        match output.killed {
+            Some(KillReason::Canceled) => {
+                remove_outputs(&[&trace_path, &types_path]).await;
+                return Err("experiment canceled".to_string());
+            }
+            Some(reason) => {
+                remove_outputs(&[&trace_path, &types_path]).await;
+                result.error = Some(format!("tsc {reason}"));
+                return Ok(result);
+            }
+            None => {}
+        }
+        if !trace_path.exists() {
+            remove_outputs(&[&trace_path, &types_path]).await;
+            result.error = Some(format!("tsc produced no trace:\n{}", output.stderr));
+            return Ok(result);
+        }
+        if run < request.warmup {
+            continue;
+        }
+
+        wall_times.push(wall_time);
+        let measured = if run + 1 == runs {
+            measure(probe, &trace_path, &types_path, &mut result).await
+        } else {
+            total_check_time(&trace_path).await
+        };
+        match measured {
+            Ok(total_check_time) => total_check_times.push(total_check_time),
+            Err(e) => {
+                result.error = Some(e);
+                break;
+            }
+        }
+    }
+    result.wall_time_stats = SampleStats::from_samples(wall_times);
+    result.total_check_time_stats = SampleStats::from_samples(total_check_times);
+    result.wall_time = result.wall_time_stats.mean;
+    result.total_check_time = result.total_check_time_stats.mean;
+
+    remove_outputs(&[&trace_path, &types_path]).await;
+    Ok(result)
+}
+
+/// Delete a run's trace outputs, which are too big to keep around for every variant.
+async fn remove_outputs(paths: &[&Path]) {
+    for path in paths {
+        if path.exists()
+            && let Err(e) = fs::remove_file(path).await
+        {
+            debug!("[experiment] failed to remove {}: {e}", path.display());
+        }
+    }
+}
+
+/// Sum of every `checkSourceFile` span in the trace, in milliseconds.
+async fn total_check_time(trace_path: &Path) -> Result<f64, String> {
+    let (trace, _) = load_trace_json(trace_path.to_path_buf()).await?;
+    Ok(build_treemap_from_trace(&trace)?
+        .iter()
+        .map(|node| node.value)
+        .sum::<f64>()
+        / 1000.0)
+}
+
+/// Analyze the last run's trace into `result`, returning its total check time.
+async fn measure(
+    probe: &AppData,
+    trace_path: &Path,
+    types_path: &Path,
+    result: &mut VariantResult,
+) -> Result<f64, String> {
+    let total_check_time = total_check_time(trace_path).await?;
+    result.type_count = load_types_json(types_path.to_path_buf()).await?.len();
+
+    let outputs_dir = probe.outputs_dir();
+    let trace_dir = outputs_dir.to_string_lossy().to_string();
+    let thresholds = probe.thresholds.clone();
+    let analysis =
+        tauri::async_runtime::spawn_blocking(move || analyze_trace(&trace_dir, Some(thresholds)))
+            .await
+            .map_err(|e| e.to_string())??;
+    result.hot_spot_count = analysis.hot_spots.len();
+    result.duplicate_packages = analysis.total_duplicate_packages();
+    result.depth_limit_counts = analysis.depth_limit_counts();
+
+    let json = serde_json::to_string_pretty(&analysis)
+        .map_err(|e| format!("Failed to serialize trace analysis: {e}"))?;
+    fs::write(outputs_dir.join(ANALYZE_TRACE_FILENAME), json)
+        .await
+        .map_err(|e| format!("Failed to write trace analysis: {e}"))?;
+    Ok(total_check_time)
+}
+
+/// Every experiment run for a workspace, newest first. Unreadable results are skipped.
+pub async fn list_experiments(data_dir: &Path, workspace_id: &str) -> Vec<ExperimentResult> {
+    let Ok(mut entries) = fs::read_dir(experiments_dir(data_dir, workspace_id)).await else {
+        return Vec::new();
+    };
+
+    let mut experiments = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path().join(EXPERIMENT_RESULT_FILENAME);
+        let Ok(contents) = fs::read_to_string(&path).await else {
+            continue;
+        };
+        match serde_json::from_str::<ExperimentResult>(&contents) {
+            Ok(experiment) => experiments.push(experiment),
+            Err(e) => debug!("[list_experiments] skipping {}: {e}", path.display()),
+        }
+    }
+    experiments.sort_by_key(|experiment| std::cmp::Reverse(experiment.created_at));
+    experiments
+}
+
+pub async fn delete_experiment(
+    data_dir: &Path,
+    workspace_id: &str,
+    experiment_id: &str,
+) -> Result<(), String> {
+    // ids come from the frontend and are joined onto paths, so only allow what `run_experiment` produces
+    if experiment_id.is_empty() || !experiment_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("Invalid experiment id {experiment_id:?}"));
+    }
+    let dir = experiments_dir(data_dir, workspace_id).join(experiment_id);
+    if !dir.join(EXPERIMENT_RESULT_FILENAME).exists() {
+        return Err(format!("Unknown experiment {experiment_id:?}"));
+    }
+    fs::remove_dir_all(&dir)
+        .await
+        .map_err(|e| format!("Failed to remove {}: {e}", dir.display()))
+}
@@ -0,0 +1,345 @@
+use crate::{
+    analyze_trace::{DepthLimitKind, constants::ANALYZE_TRACE_FILENAME},
+    app_data::{AppData, workspaces::workspace_outputs_dir},
+    run_metadata::RUN_METADATA_FILENAME,
+    tsconfig::resolve::{EFFECTIVE_TSCONFIG_FILENAME, EffectiveTsconfig},
+    utils::HISTORY_DIRECTORY,
+};
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tokio::{fs, process::Command};
+use tracing::{debug, info};
+
+pub const SNAPSHOT_SUMMARY_FILENAME: &str = "summary.json";
+pub const SNAPSHOT_OUTPUTS_FILENAME: &str = "outputs.zip";
+
+/// The outputs a snapshot keeps, all small enough to archive after every run
+const ARCHIVED_OUTPUTS: [&str; 3] = [
+    ANALYZE_TRACE_FILENAME,
+    RUN_METADATA_FILENAME,
+    EFFECTIVE_TSCONFIG_FILENAME,
+];
+
+/// The headline numbers of one archived run, kept next to its compressed outputs
+/// so trends can be charted without unpacking anything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSummary {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub workspace_id: String,
+    pub project_root: PathBuf,
+    pub tsconfig: Option<PathBuf>,
+    /// `HEAD` of `project_root` when the snapshot was taken, if it's a git repo
+    pub git_commit: Option<String>,
+    /// Whether the working tree had uncommitted changes
+    pub git_dirty: bool,
+    pub version: String,
+    pub compiler_variant: String,
+    /// Sum of every `checkSourceFile` span, in microseconds
+    pub total_check_time: f64,
+    pub depth_limit_counts: IndexMap<DepthLimitKind, usize>,
+    pub duplicate_packages: usize,
+    /// `None` when the type graph wasn't generated for this run
+    pub node_count: Option<usize>,
+    pub link_count: Option<usize>,
+    /// The resolved tsconfig the run used. Missing from snapshots taken before it was recorded.
+    #[serde(default)]
+    pub effective_tsconfig: Option<EffectiveTsconfig>,
+    /// e.g. `5.9.2`. Missing from snapshots taken before it was recorded.
+    #[serde(default)]
+    pub tsc_version: Option<String>,
+}
+
+/// Every snapshot's summary as parallel arrays, oldest first, ready to be charted.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotTimeSeries {
+    pub ids: Vec<String>,
+    pub timestamps: Vec<DateTime<Utc>>,
+    pub git_commits: Vec<Option<String>>,
+    pub total_check_time: Vec<f64>,
+    pub depth_limit_counts: IndexMap<DepthLimitKind, Vec<usize>>,
+    pub duplicate_packages: Vec<usize>,
+    pub node_count: Vec<Option<usize>>,
+    pub link_count: Vec<Option<usize>>,
+}
+
+impl SnapshotTimeSeries {
+    pub fn from_summaries(summaries: &[SnapshotSummary]) -> Self {
+        let mut series = Self {
+            depth_limit_counts: DepthLimitKind::new_counts_map()
+                .into_keys()
+                .map(|kind| (kind, Vec::with_capacity(summaries.len())))
+                .collect(),
+            ..Self::default()
+        };
+        for summary in summaries {
+            series.ids.push(summary.id.clone());
+            series.timestamps.push(summary.created_at);
+            series.git_commits.push(summary.git_commit.clone());
+            series.total_check_time.push(summary.total_check_time);
+            for (kind, counts) in series.depth_limit_counts.iter_mut() {
+                counts.push(summary.depth_limit_counts.get(kind).copied().unwrap_or(0));
+            }
+            series.duplicate_packages.push(summary.duplicate_packages);
+            series.node_count.push(summary.node_count);
+            series.link_count.push(summary.link_count);
+        }
+        series
+    }
+}
+
+pub fn history_dir(data_dir: &Path, workspace_id: &str) -> PathBuf {
+    data_dir.join(HISTORY_DIRECTORY).join(workspace_id)
+}
+
+/// Snapshot ids come from the frontend and are joined onto paths, so only allow what `create_snapshot` produces.
+fn snapshot_dir(data_dir: &Path, workspace_id: &str, snapshot_id: &str) -> Result<PathBuf, String> {
+    if snapshot_id.is_empty() || !snapshot_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("Invalid snapshot id {snapshot_id:?}"));
+    }
+    Ok(history_dir(data_dir, workspace_id).join(snapshot_id))
+}
+
+/// `HEAD` of the repo containing `dir` and whether its working tree is dirty.
+pub async fn git_head(dir: &Path) -> (Option<String>, bool) {
+    let head = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    if head.is_none() {
+        return (None, false);
+    }
+
+    let dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .current_dir(dir)
+        .output()
+        .await
+        .is_ok_and(|output| output.status.success() && !output.stdout.is_empty());
+
+    (head, dirty)
+}
+
+/// A snapshot gathered while `AppData` is locked, to be written by [`write_snapshot`] once it's released.
+pub struct PendingSnapshot {
+    summary: SnapshotSummary,
+    data_dir: PathBuf,
+    /// The contents of each of [`ARCHIVED_OUTPUTS`] that exists
+    outputs: Vec<(&'static str, Vec<u8>)>,
+    max_snapshots: usize,
+}
+
+/// Summarize the loaded analysis and read the outputs worth archiving. The raw trace and types
+/// can be gigabytes, so they're left out; restoring a snapshot brings back its analysis.
+pub async fn prepare_snapshot(app_data: &mut AppData) -> Result<PendingSnapshot, String> {
+    let total_check_time = app_data
+        .ensure_treemap()?
+        .iter()
+        .map(|node| node.value)
+        .sum();
+    let analyze_trace = app_data
+        .analyze_trace
+        .as_ref()
+        .ok_or_else(|| "Cannot snapshot a run without an analyze-trace result".to_string())?;
+
+    let created_at = Utc::now();
+    let summary = SnapshotSummary {
+        id: created_at.format("%Y%m%dT%H%M%S%3fZ").to_string(),
+        created_at,
+        workspace_id: app_data.workspace_id.clone(),
+        project_root: app_data.project_root.clone(),
+        tsconfig: app_data.selected_tsconfig.clone(),
+        git_commit: None,
+        git_dirty: false,
+        version: app_data.version.clone(),
+        compiler_variant: app_data
+            .settings
+            .typescript_compiler_variant
+            .as_str()
+            .to_string(),
+        total_check_time,
+        depth_limit_counts: analyze_trace.depth_limit_counts(),
+        duplicate_packages: analyze_trace.total_duplicate_packages(),
+        node_count: app_data.type_graph.as_ref().map(|g| g.node_count),
+        link_count: app_data.type_graph.as_ref().map(|g| g.link_count),
+        effective_tsconfig: app_data.effective_tsconfig.clone(),
+        tsc_version: app_data
+            .run_metadata
+            .as_ref()
+            .and_then(|run| run.tsc_version.clone()),
+    };
+
+    let outputs_dir = app_data.outputs_dir();
+    let mut outputs = Vec::new();
+    for filename in ARCHIVED_OUTPUTS {
+        let path = outputs_dir.join(filename);
+        match fs::read(&path).await {
+            Ok(contents) => outputs.push((filename, contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    Ok(PendingSnapshot {
+        summary,
+        data_dir: app_data.data_dir.clone(),
+        outputs,
+        max_snapshots: app_data.retention_policy().max_snapshots,
+    })
+}
+
+/// Write a snapshot to the workspace's history, then drop the oldest ones past the limit.
+pub async fn write_snapshot(pending: PendingSnapshot) -> Result<SnapshotSummary, String> {
+    let PendingSnapshot {
+        mut summary,
+        data_dir,
+        outputs,
+        max_snapshots,
+    } = pending;
+    (summary.git_commit, summary.git_dirty) = git_head(&summary.project_root).await;
+
+    let snapshot_dir = history_dir(&data_dir, &summary.workspace_id).join(&summary.id);
+    fs::create_dir_all(&snapshot_dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {e}", snapshot_dir.display()))?;
+
+    let zip_path = snapshot_dir.join(SNAPSHOT_OUTPUTS_FILENAME);
+    tauri::async_runtime::spawn_blocking(move || zip_outputs(&outputs, &zip_path))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    let summary_json = serde_json::to_string_pretty(&summary)
+        .map_err(|e| format!("Failed to serialize snapshot summary: {e}"))?;
+    fs::write(snapshot_dir.join(SNAPSHOT_SUMMARY_FILENAME), summary_json)
+        .await
+        .map_err(|e| format!("Failed to write snapshot summary: {e}"))?;
+
+    info!(
+        "[create_snapshot] archived run {} for workspace {}",
+        summary.id, summary.workspace_id
+    );
+    prune_snapshots(&data_dir, &summary.workspace_id, max_snapshots).await?;
+    Ok(summary)
+}
+
+fn zip_outputs(outputs: &[(&str, Vec<u8>)], zip_path: &Path) -> Result<(), String> {
+    let file = std::fs::File::create(zip_path)
+        .map_err(|e| format!("Failed to create {}: {e}", zip_path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+
+    for (filename, contents) in outputs {
+        zip.start_file(*filename, options)
+            .map_err(|e| format!("Failed to add {filename} to snapshot: {e}"))?;
+        zip.write_all(contents)
+            .map_err(|e| format!("Failed to write {filename} to snapshot: {e}"))?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finalize snapshot: {e}"))?
+        .flush()
+        .map_err(|e| format!("Failed to flush snapshot: {e}"))
+}
+
+/// Delete a workspace's oldest snapshots until at most `max_snapshots` are left (`0` keeps all).
+/// Returns the ids that were removed.
+pub async fn prune_snapshots(
+    data_dir: &Path,
+    workspace_id: &str,
+    max_snapshots: usize,
+) -> Result<Vec<String>, String> {
+    if max_snapshots == 0 {
+        return Ok(Vec::new());
+    }
+    let snapshots = list_snapshots(data_dir, workspace_id).await;
+    let excess = snapshots.len().saturating_sub(max_snapshots);
+    let mut removed = Vec::with_capacity(excess);
+    for summary in &snapshots[..excess] {
+        delete_snapshot(data_dir, workspace_id, &summary.id).await?;
+        removed.push(summary.id.clone());
+    }
+    if !removed.is_empty() {
+        info!(
+            "[prune_snapshots] removed {} snapshot(s) of workspace {workspace_id}",
+            removed.len()
+        );
+    }
+    Ok(removed)
+}
+
+/// Every snapshot of a workspace, oldest first. Unreadable summaries are skipped.
+pub async fn list_snapshots(data_dir: &Path, workspace_id: &str) -> Vec<SnapshotSummary> {
+    let Ok(mut entries) = fs::read_dir(history_dir(data_dir, workspace_id)).await else {
+        return Vec::new();
+    };
+
+    let mut summaries = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path().join(SNAPSHOT_SUMMARY_FILENAME);
+        let Ok(contents) = fs::read_to_string(&path).await else {
+            continue;
+        };
+        match serde_json::from_str::<SnapshotSummary>(&contents) {
+            Ok(summary) => summaries.push(summary),
+            Err(e) => debug!("[list_snapshots] skipping {}: {e}", path.display()),
+        }
+    }
+    summaries.sort_by_key(|summary| summary.created_at);
+    summaries
+}
+
+/// Unpack a snapshot's outputs over the workspace's current outputs.
+pub async fn restore_snapshot(
+    data_dir: &Path,
+    workspace_id: &str,
+    snapshot_id: &str,
+) -> Result<(), String> {
+    let snapshot_dir = snapshot_dir(data_dir, workspace_id, snapshot_id)?;
+    let zip_path = snapshot_dir.join(SNAPSHOT_OUTPUTS_FILENAME);
+    if !zip_path.exists() {
+        return Err(format!("Unknown snapshot {snapshot_id:?}"));
+    }
+
+    let outputs_dir = workspace_outputs_dir(data_dir, workspace_id);
+    tauri::async_runtime::spawn_blocking(move || {
+        let file = std::fs::File::open(&zip_path)
+            .map_err(|e| format!("Failed to open {}: {e}", zip_path.display()))?;
+        let mut archive =
+            zip::ZipArchive::new(file).map_err(|e| format!("Failed to read snapshot: {e}"))?;
+        std::fs::create_dir_all(&outputs_dir)
+            .map_err(|e| format!("Failed to create {}: {e}", outputs_dir.display()))?;
+        archive
+            .extract(&outputs_dir)
+            .map_err(|e| format!("Failed to extract snapshot: {e}"))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+pub async fn delete_snapshot(
+    data_dir: &Path,
+    workspace_id: &str,
+    snapshot_id: &str,
+) -> Result<(), String> {
+    let snapshot_dir = snapshot_dir(data_dir, workspace_id, snapshot_id)?;
+    if !snapshot_dir.join(SNAPSHOT_SUMMARY_FILENAME).exists() {
+        return Err(format!("Unknown snapshot {snapshot_id:?}"));
+    }
+    fs::remove_dir_all(&snapshot_dir)
+        .await
+        .map_err(|e| format!("Failed to remove {}: {e}", snapshot_dir.display()))
+}
@@ -0,0 +1,239 @@
+use crate::{
+    analyze_trace::{DepthLimitKind, HotSpot, ResolvedDepthLimit, create_depth_limit_report},
+    app_data::AppData,
+    commands::{
+        app_data::{GraphStats, ensure_analyze_trace, type_graph_stats},
+        query::{Links, links_to_type_id, resolved_type_by_id},
+    },
+    type_graph::LinkKind,
+    validate::{types_json::ResolvedType, utils::TypeId},
+};
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+const DEFAULT_PAGE_LIMIT: usize = 100;
+const MAX_PAGE_LIMIT: usize = 1000;
+
+/// Versioned JSON API over the live AppData, mounted under `/api/v1`.
+///
+/// Handlers reuse the same functions as the Tauri commands so the webview,
+/// MCP and HTTP consumers all see identical results.
+pub fn router() -> Router<&'static Mutex<AppData>> {
+    Router::new()
+        .route("/hotspots", get(get_hotspots))
+        .route("/files", get(get_files))
+        .route("/types/{id}", get(get_type))
+        .route("/types/{id}/links", get(get_type_links))
+        .route("/graph/stats", get(get_graph_stats))
+        .route("/depth-limits", get(get_depth_limits))
+}
+
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            message: message.into(),
+        }
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: message.into(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(serde_json::json!({ "error": self.message }));
+        (self.status, body).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+/// One page of a larger result set.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    /// Number of items matching the filters, before paging
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub items: Vec<T>,
+}
+
+impl<T> Page<T> {
+    fn new(items: Vec<T>, offset: Option<usize>, limit: Option<usize>) -> Self {
+        let total = items.len();
+        let offset = offset.unwrap_or(0);
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+        let items = items.into_iter().skip(offset).take(limit).collect();
+        Self {
+            total,
+            offset,
+            limit,
+            items,
+        }
+    }
+}
+
+fn path_matches(path: Option<&str>, needle: Option<&str>) -> bool {
+    match needle {
+        None => true,
+        Some(needle) => path.is_some_and(|p| p.contains(needle)),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HotspotsQuery {
+    /// Only include hotspots at least this long (same unit as `duration`)
+    min_duration: Option<f64>,
+    /// Only include hotspots whose path contains this substring
+    path: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+async fn get_hotspots(
+    State(app_data): State<&Mutex<AppData>>,
+    Query(query): Query<HotspotsQuery>,
+) -> ApiResult<Page<HotSpot>> {
+    let mut app_data = app_data.lock().await;
+    let analyze_trace = ensure_analyze_trace(&mut app_data)
+        .await
+        .map_err(ApiError::not_found)?;
+
+    let hot_spots = analyze_trace
+        .hot_spots
+        .iter()
+        .filter(|h| query.min_duration.is_none_or(|min| h.duration >= min))
+        .filter(|h| {
+            let path = h.path.as_ref().map(|p| p.to_string_lossy());
+            path_matches(path.as_deref(), query.path.as_deref())
+        })
+        .cloned()
+        .collect();
+
+    Ok(Json(Page::new(hot_spots, query.offset, query.limit)))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDuration {
+    pub path: String,
+    pub duration: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FilesQuery {
+    /// Only include files that took at least this long to check
+    min_duration: Option<f64>,
+    /// Only include files whose path contains this substring
+    path: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+async fn get_files(
+    State(app_data): State<&Mutex<AppData>>,
+    Query(query): Query<FilesQuery>,
+) -> ApiResult<Page<FileDuration>> {
+    let mut app_data = app_data.lock().await;
+    if app_data.trace_json.is_empty() {
+        return Err(ApiError::not_found("No trace data available"));
+    }
+
+    // already sorted by duration, descending
+    let files = app_data
+        .ensure_treemap()
+        .map_err(ApiError::internal)?
+        .iter()
+        .map(|node| FileDuration {
+            path: node.path.clone().unwrap_or_else(|| node.name.clone()),
+            duration: node.value,
+        })
+        .filter(|f| query.min_duration.is_none_or(|min| f.duration >= min))
+        .filter(|f| path_matches(Some(&f.path), query.path.as_deref()))
+        .collect();
+
+    Ok(Json(Page::new(files, query.offset, query.limit)))
+}
+
+async fn get_type(
+    State(app_data): State<&Mutex<AppData>>,
+    Path(id): Path<TypeId>,
+) -> ApiResult<ResolvedType> {
+    let app_data = app_data.lock().await;
+    let resolved = resolved_type_by_id(&app_data, id).map_err(ApiError::not_found)?;
+    Ok(Json(resolved.clone()))
+}
+
+#[derive(Debug, Deserialize)]
+struct LinksQuery {
+    /// Only include links of this kind
+    kind: Option<LinkKind>,
+}
+
+async fn get_type_links(
+    State(app_data): State<&Mutex<AppData>>,
+    Path(id): Path<TypeId>,
+    Query(query): Query<LinksQuery>,
+) -> ApiResult<Links> {
+    let app_data = app_data.lock().await;
+    resolved_type_by_id(&app_data, id).map_err(ApiError::not_found)?;
+    let mut links = links_to_type_id(&app_data, id).map_err(ApiError::not_found)?;
+    if let Some(kind) = query.kind {
+        links.retain(|(k, _)| *k == kind);
+    }
+    Ok(Json(links))
+}
+
+async fn get_graph_stats(State(app_data): State<&Mutex<AppData>>) -> ApiResult<GraphStats> {
+    let app_data = app_data.lock().await;
+    let stats = type_graph_stats(&app_data).map_err(ApiError::not_found)?;
+    Ok(Json(stats))
+}
+
+#[derive(Debug, Deserialize)]
+struct DepthLimitsQuery {
+    /// Only include depth limits of this kind, e.g. `instantiateType_DepthLimit`
+    kind: Option<DepthLimitKind>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+/// Depth limits merged by the types they were hit on, as `get_depth_limit_report` serves them.
+async fn get_depth_limits(
+    State(app_data): State<&Mutex<AppData>>,
+    Query(query): Query<DepthLimitsQuery>,
+) -> ApiResult<Page<ResolvedDepthLimit>> {
+    let app_data = app_data.lock().await;
+    if app_data.trace_json.is_empty() {
+        return Err(ApiError::not_found("No trace data available"));
+    }
+
+    let limits = create_depth_limit_report(&app_data.trace_json, &app_data.types_json)
+        .map_err(ApiError::internal)?
+        .into_iter()
+        .filter(|(kind, _)| query.kind.is_none_or(|k| k == *kind))
+        .flat_map(|(_, limits)| limits)
+        .collect();
+
+    Ok(Json(Page::new(limits, query.offset, query.limit)))
+}
@@ -0,0 +1,118 @@
+mod api;
+
+use crate::{
+    analyze_trace::constants::ANALYZE_TRACE_FILENAME,
+    app_data::AppData,
+    tsconfig::resolve::EFFECTIVE_TSCONFIG_FILENAME,
+    type_graph::TYPE_GRAPH_FILENAME,
+    validate::{
+        trace_json::TRACE_JSON_FILENAME, types_json::TYPES_JSON_FILENAME,
+        utils::CPU_PROFILE_FILENAME,
+    },
+};
+use axum::{
+    Router,
+    body::Body,
+    extract::{Path, Request, State},
+    http::{HeaderValue, Method, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use tokio::sync::Mutex;
+use tower_http::{
+    compression::CompressionLayer,
+    cors::{AllowOrigin, CorsLayer},
+    services::ServeFile,
+};
+use tracing::{error, info};
+
+/// The only files `/outputs/{name}` will serve. Anything else is a 404,
+/// which also rules out `../` and absolute-path tricks.
+const OUTPUT_FILENAMES: [&str; 6] = [
+    TYPES_JSON_FILENAME,
+    TRACE_JSON_FILENAME,
+    ANALYZE_TRACE_FILENAME,
+    TYPE_GRAPH_FILENAME,
+    CPU_PROFILE_FILENAME,
+    EFFECTIVE_TSCONFIG_FILENAME,
+];
+
+/// Origins allowed to read from this server: the Tauri webview on each platform and the vite dev server.
+pub(crate) const ALLOWED_ORIGINS: [&str; 4] = [
+    "tauri://localhost",
+    "http://tauri.localhost",
+    "https://tauri.localhost",
+    "http://localhost:1993",
+];
+
+/// Standalone HTTP server for serving output files
+///
+/// This server runs on port 4765 and serves files from the outputs directory,
+/// plus a versioned JSON API under `/api/v1` (see [`api::router`]).
+/// It's independent of the Tauri app and can run alongside it.
+pub async fn run_http_server(
+    app_data: &'static Mutex<AppData>,
+    listener: tokio::net::TcpListener,
+) -> Result<(), Box<dyn std::error::Error>> {
+    info!("[run_http_server] Starting HTTP server for outputs on port 4765");
+
+    /// Streams a known output file, honoring `Range` requests (trace.json can be several GB).
+    async fn serve_output(
+        Path(name): Path<String>,
+        State(app_data): State<&Mutex<AppData>>,
+        request: Request,
+    ) -> Response {
+        let Some(name) = OUTPUT_FILENAMES.iter().find(|known| **known == name) else {
+            return (StatusCode::NOT_FOUND, format!("Unknown output {name:?}")).into_response();
+        };
+
+        let path = app_data.lock().await.outputs_dir().join(name);
+
+        // every output (including the .cpuprofile) is JSON
+        match ServeFile::new_with_mime(&path, &mime::APPLICATION_JSON)
+            .try_call(request)
+            .await
+        {
+            Ok(response) => response.map(Body::new),
+            Err(e) => {
+                error!("Failed to read {:?}: {}", path, e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to read {name}"),
+                )
+                    .into_response()
+            }
+        }
+    }
+
+    let cors = CorsLayer::new()
+        .allow_methods([Method::GET, Method::HEAD])
+        .allow_headers([header::RANGE])
+        .expose_headers([
+            header::ACCEPT_RANGES,
+            header::CONTENT_LENGTH,
+            header::CONTENT_RANGE,
+        ])
+        .allow_origin(AllowOrigin::list(
+            ALLOWED_ORIGINS.map(HeaderValue::from_static),
+        ));
+
+    // ranged responses are left uncompressed by the layer, so the two play well together
+    let compression = CompressionLayer::new().gzip(true).br(true);
+
+    let app_router = Router::new()
+        .route("/outputs/{name}", get(serve_output))
+        .nest("/api/v1", api::router())
+        .layer(compression)
+        .layer(cors)
+        .with_state(app_data);
+
+    info!(
+        "[run_http_server] HTTP server listening on {}",
+        listener.local_addr().map_err(|e| e.to_string())?
+    );
+
+    axum::serve(listener, app_router.into_make_service()).await?;
+
+    Ok(())
+}
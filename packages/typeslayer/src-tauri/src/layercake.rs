@@ -1,15 +1,22 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use tracing::debug;
+use tracing::{debug, warn};
 use ts_rs::TS;
 
 /// Source precedence options for resolution.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Source {
     Env,
     Flag,
+    /// The nearest config file above the project root, committed with the project
+    Project,
     File,
 }
 
@@ -19,15 +26,51 @@ pub enum Source {
 pub struct SourceHistory {
     from_env: Vec<String>,
     from_flag: Vec<String>,
+    from_project: Vec<String>,
     from_file: Vec<String>,
     from_default: Vec<String>,
 }
 
-/// A small helper to resolve settings by precedence across env vars, CLI flags, and a TOML config file.
+/// A value that was set but couldn't be used, so resolution fell through to the next layer.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigError {
+    /// The setting, named like its env var without the prefix, e.g. `MAX_NODES`
+    pub key: String,
+    pub source: Source,
+    /// The env var, flag, or `file: key.path` the value came from
+    pub location: String,
+    pub value: String,
+    pub reason: String,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid {} from {} ({}): {:?}: {}",
+            self.key,
+            serde_plain::to_string(&self.source).unwrap_or_default(),
+            self.location,
+            self.value,
+            self.reason
+        )
+    }
+}
+
+/// A small helper to resolve settings by precedence across env vars, CLI flags, and TOML config files.
 #[derive(Clone, Debug)]
 pub struct LayerCake {
     pub precedence: Vec<Source>,
     pub cfg: Option<toml::Value>,
+    /// Where `cfg` was loaded from
+    pub config_path: Option<PathBuf>,
+    /// The project-level config, holding only the keys a project may set
+    pub project_cfg: Option<toml::Value>,
+    /// Where `project_cfg` was loaded from
+    pub project_config_path: Option<PathBuf>,
+    /// File key paths (e.g. `settings.tsconfig`) that were resolved from the project config
+    pub project_overrides: Vec<String>,
     /// Cached CLI flags parsed as `--flag=value` or `--flag value`.
     pub flags: IndexMap<String, String>,
     /// Environment variable prefix (empty string means no prefix)
@@ -36,6 +79,8 @@ pub struct LayerCake {
     pub config_filename: String,
     /// History of which source a particular thing was resolved from
     pub source_history: SourceHistory,
+    /// Every value that was rejected while resolving, in the order it was found
+    pub errors: Vec<ConfigError>,
 }
 
 /// Named-argument container for resolving strings.
@@ -91,50 +136,198 @@ where
 /// Initialization arguments for LayerCake.
 pub struct LayerCakeInitArgs<'a> {
     pub config_filename: &'a str,
-    pub precedence: [Source; 3],
+    pub precedence: [Source; 4],
     pub env_prefix: &'a str, // empty string allowed for no prefix
 }
 
 impl LayerCake {
     /// Initialize with args struct; does not immediately load config file (only stores filename).
-    /// Validates that precedence contains exactly 3 unique sources.
+    /// Validates that precedence contains exactly 4 unique sources.
     pub fn new(args: LayerCakeInitArgs) -> Self {
         // Validate uniqueness of precedence entries
         let mut seen = std::collections::HashSet::new();
         for s in &args.precedence {
             seen.insert(std::mem::discriminant(s));
         }
-        if seen.len() != 3 {
+        if seen.len() != 4 {
             panic!(
-                "[layercake] precedence must contain exactly 3 unique sources (Env, Flag, File)"
+                "[layercake] precedence must contain exactly 4 unique sources (Env, Flag, Project, File)"
             );
         }
         let flags = Self::parse_flags();
         Self {
             precedence: args.precedence.to_vec(),
             cfg: None,
+            config_path: None,
+            project_cfg: None,
+            project_config_path: None,
+            project_overrides: Vec::new(),
             flags,
             env_prefix: args.env_prefix.to_string(),
             config_filename: args.config_filename.to_string(),
             source_history: SourceHistory::default(),
+            errors: Vec::new(),
         }
     }
 
     /// Load or reload the TOML config by joining stored filename with provided directory.
     pub async fn load_config_in_dir(&mut self, dir: String) -> Result<(), String> {
         let config_filename = self.config_filename.clone();
-        self.cfg = tauri::async_runtime::spawn_blocking(move || {
-            let path = std::path::Path::new(&dir).join(config_filename);
-            std::fs::read_to_string(&path)
-                .ok()
-                .and_then(|s| toml::from_str::<toml::Value>(&s).ok())
+        let path = Path::new(&dir).join(config_filename);
+        self.config_path = Some(path.clone());
+        let (cfg, parse_error) = tauri::async_runtime::spawn_blocking(move || {
+            let Ok(contents) = std::fs::read_to_string(&path) else {
+                return (None, None);
+            };
+            match toml::from_str::<toml::Value>(&contents) {
+                Ok(cfg) => (Some(cfg), None),
+                Err(e) => (None, Some((path, e.to_string()))),
+            }
         })
         .await
         .map_err(|e| e.to_string())?;
 
+        self.cfg = cfg;
+        if let Some((path, reason)) = parse_error {
+            // the whole file is skipped, so everything falls back to defaults rather than taking the app down
+            self.reject(
+                "",
+                Source::File,
+                &path.to_string_lossy(),
+                "",
+                format!("not valid TOML: {reason}"),
+            );
+        }
         Ok(())
     }
 
+    /// Load or reload the project config: the nearest config file in `project_root` or above it,
+    /// up to the repository root. Keys `is_project_key` doesn't accept are rejected and dropped,
+    /// so a committed file can't change someone's editor or analytics consent.
+    pub async fn load_project_config(
+        &mut self,
+        project_root: &Path,
+        is_project_key: fn(&str) -> bool,
+    ) -> Result<(), String> {
+        let config_filename = self.config_filename.clone();
+        let global_path = self.config_path.clone();
+        let project_root = project_root.to_path_buf();
+        let found = tauri::async_runtime::spawn_blocking(move || {
+            for dir in project_root.ancestors() {
+                let path = dir.join(&config_filename);
+                if path.is_file() && global_path.as_ref() != Some(&path) {
+                    let parsed = std::fs::read_to_string(&path)
+                        .map_err(|e| e.to_string())
+                        .and_then(|contents| {
+                            toml::from_str::<toml::Value>(&contents).map_err(|e| e.to_string())
+                        });
+                    return Some((path, parsed));
+                }
+                if dir.join(".git").exists() {
+                    break;
+                }
+            }
+            None
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+        // errors from the previous project's file no longer apply
+        self.errors.retain(|error| error.source != Source::Project);
+        self.project_overrides.clear();
+        self.project_cfg = None;
+        self.project_config_path = None;
+        let Some((path, parsed)) = found else {
+            return Ok(());
+        };
+        let location = path.to_string_lossy().to_string();
+        match parsed {
+            Ok(mut cfg) => {
+                let mut rejected = Vec::new();
+                Self::retain_keys(&mut cfg, "", is_project_key, &mut rejected);
+                for (key, value) in rejected {
+                    self.reject(
+                        &key,
+                        Source::Project,
+                        &format!("{location}: {key}"),
+                        value,
+                        "can't be set per project, only in your own config",
+                    );
+                }
+                debug!("[layercake] loaded project config {location}");
+                self.project_cfg = Some(cfg);
+            }
+            Err(reason) => self.reject(
+                "",
+                Source::Project,
+                &location,
+                "",
+                format!("not valid TOML: {reason}"),
+            ),
+        }
+        self.project_config_path = Some(path);
+        Ok(())
+    }
+
+    /// Drop every leaf whose dotted key path `keep` doesn't accept, collecting `(key, value)` for each.
+    fn retain_keys(
+        value: &mut toml::Value,
+        prefix: &str,
+        keep: fn(&str) -> bool,
+        rejected: &mut Vec<(String, String)>,
+    ) {
+        let Some(table) = value.as_table_mut() else {
+            return;
+        };
+        table.retain(|name, child| {
+            let key = if prefix.is_empty() {
+                name.to_string()
+            } else {
+                format!("{prefix}.{name}")
+            };
+            if child.is_table() {
+                Self::retain_keys(child, &key, keep, rejected);
+                return true;
+            }
+            if keep(&key) {
+                return true;
+            }
+            rejected.push((key, child.to_string()));
+            false
+        });
+    }
+
+    /// Whether the project config sets `key_path`, whatever the value.
+    pub fn project_sets(&self, key_path: &str) -> bool {
+        self.get_config_value(&Source::Project, key_path).is_some()
+    }
+
+    /// Put back the global config's values (or remove the key) wherever the project config won,
+    /// so writing resolved settings back to the global file doesn't copy one project's config into it.
+    pub fn restore_file_values(&self, value: &mut toml::Value) {
+        for key_path in &self.project_overrides {
+            let segments: Vec<&str> = key_path.split('.').collect();
+            let Some((last, parents)) = segments.split_last() else {
+                continue;
+            };
+            let mut cur = Some(&mut *value);
+            for segment in parents {
+                cur = cur.and_then(|cur| cur.get_mut(*segment));
+            }
+            let Some(table) = cur.and_then(|cur| cur.as_table_mut()) else {
+                continue;
+            };
+            match self.get_config_value(&Source::File, key_path) {
+                Some(global) => {
+                    table.insert(last.to_string(), global.clone());
+                }
+                None => {
+                    table.remove(*last);
+                }
+            }
+        }
+    }
+
     /// Parse CLI args into a simple flag map supporting `--name=value`, `--name value`, and `--name` (implied "true").
     fn parse_flags() -> IndexMap<String, String> {
         let mut map = IndexMap::new();
@@ -181,8 +374,76 @@ impl LayerCake {
         map
     }
 
+    fn env_key(&self, env: &str) -> String {
+        if self.env_prefix.is_empty() {
+            env.to_string()
+        } else {
+            format!("{}{}", self.env_prefix, env)
+        }
+    }
+
+    /// Record a value that can't be used. The caller falls through to the next layer.
+    pub fn reject(
+        &mut self,
+        key: &str,
+        source: Source,
+        location: &str,
+        value: impl Display,
+        reason: impl Display,
+    ) {
+        let error = ConfigError {
+            key: key.to_string(),
+            source,
+            location: location.to_string(),
+            value: value.to_string(),
+            reason: reason.to_string(),
+        };
+        // some settings are resolved again later (e.g. the tsconfig, when the project root changes)
+        if !self.errors.contains(&error) {
+            warn!("[layercake] {error}");
+            self.errors.push(error);
+        }
+    }
+
+    /// Which layer `key` (named like its env var, without the prefix) was last resolved from.
+    pub fn source_of(&self, key: &str) -> Option<Source> {
+        let history = &self.source_history;
+        [
+            (Source::Env, &history.from_env),
+            (Source::Flag, &history.from_flag),
+            (Source::Project, &history.from_project),
+            (Source::File, &history.from_file),
+        ]
+        .into_iter()
+        .find(|(_, keys)| keys.iter().any(|k| k == key))
+        .map(|(source, _)| source)
+    }
+
+    pub fn file_location(&self, source: &Source, key_path: &str) -> String {
+        match (source, &self.project_config_path) {
+            (Source::Project, Some(path)) => format!("{}: {key_path}", path.display()),
+            _ => format!("{}: {key_path}", self.config_filename),
+        }
+    }
+
+    fn record(&mut self, source: &Source, key: &str, file_key: &str) {
+        let history = match source {
+            Source::Env => &mut self.source_history.from_env,
+            Source::Flag => &mut self.source_history.from_flag,
+            Source::Project => {
+                if !self.project_overrides.iter().any(|k| k == file_key) {
+                    self.project_overrides.push(file_key.to_string());
+                }
+                &mut self.source_history.from_project
+            }
+            Source::File => &mut self.source_history.from_file,
+        };
+        history.push(key.to_string());
+    }
+
     /// Resolve a string using named arguments only.
-    /// Validate returns Result<String, String>: Ok(validated_value) or Err(message) which panics.
+    /// Validate returns Result<String, String>: Ok(validated_value) or Err(message), which is recorded
+    /// in [`LayerCake::errors`] before falling through to the next source.
     pub fn resolve_string<F, V>(&mut self, args: ResolveStringArgs<F, V>) -> String
     where
         F: FnOnce() -> String,
@@ -191,73 +452,37 @@ impl LayerCake {
         if !args.flag.starts_with("--") {
             panic!("[layercake] Flag '{}' must start with --", args.flag);
         }
-        let env_key = if self.env_prefix.is_empty() {
-            args.env.to_string()
-        } else {
-            format!("{}{}", self.env_prefix, args.env)
-        };
-        for src in &self.precedence {
-            match src {
-                Source::Env => {
-                    if let Ok(v) = std::env::var(&env_key)
-                        && !v.is_empty()
-                    {
-                        match (args.validate)(&v) {
-                            Ok(validated) => {
-                                debug!(
-                                    "[layercake] {} string resolved from env: {}='{}'",
-                                    args.env, env_key, validated
-                                );
-                                self.source_history.from_env.push(args.env.to_string());
-                                return validated;
-                            }
-                            Err(e) => {
-                                panic!(
-                                    "[layercake] Invalid {} from env {}: {}",
-                                    args.env, env_key, e
-                                )
-                            }
-                        }
-                    }
-                }
-                Source::Flag => {
-                    if let Some(v) = self.flags.get(args.flag) {
-                        match (args.validate)(v) {
-                            Ok(validated) => {
-                                debug!(
-                                    "[layercake] {} string resolved from flag {}={}",
-                                    args.env, args.flag, validated
-                                );
-                                self.source_history.from_flag.push(args.env.to_string());
-                                return validated;
-                            }
-                            Err(e) => panic!(
-                                "[layercake] Invalid {} from flag {}: {}",
-                                args.env, args.flag, e
-                            ),
+        let env_key = self.env_key(args.env);
+        for src in self.precedence.clone() {
+            let (location, raw) = match src {
+                Source::Env => (
+                    env_key.clone(),
+                    std::env::var(&env_key).ok().filter(|v| !v.is_empty()),
+                ),
+                Source::Flag => (args.flag.to_string(), self.flags.get(args.flag).cloned()),
+                Source::File | Source::Project => {
+                    let location = self.file_location(&src, args.file);
+                    match self.get_config_str(&src, args.file) {
+                        Some(Ok(v)) => (location, Some(v)),
+                        Some(Err((value, reason))) => {
+                            self.reject(args.env, src, &location, value, reason);
+                            continue;
                         }
+                        None => continue,
                     }
                 }
-                Source::File => {
-                    if let Some(v) = self.get_config_str(args.file) {
-                        match (args.validate)(&v) {
-                            Ok(validated) => {
-                                debug!(
-                                    "[layercake] {} string resolved from {} key {}: {}",
-                                    args.env, self.config_filename, args.file, validated
-                                );
-                                self.source_history.from_file.push(args.env.to_string());
-                                return validated;
-                            }
-                            Err(e) => {
-                                panic!(
-                                    "[layercake] Invalid {} from file key {}: {}",
-                                    args.env, args.file, e
-                                )
-                            }
-                        }
-                    }
+            };
+            let Some(raw) = raw else { continue };
+            match (args.validate)(&raw) {
+                Ok(validated) => {
+                    debug!(
+                        "[layercake] {} string resolved from {location}: '{validated}'",
+                        args.env
+                    );
+                    self.record(&src, args.env, args.file);
+                    return validated;
                 }
+                Err(e) => self.reject(args.env, src, &location, raw, e),
             }
         }
         let default = (args.default)();
@@ -271,7 +496,8 @@ impl LayerCake {
     }
 
     /// Resolve a number (i32) using named arguments only.
-    /// Validate returns Result<i32, String>: Ok(validated_value) or Err(message) which panics.
+    /// Validate returns Result<i32, String>: Ok(validated_value) or Err(message), which is recorded
+    /// in [`LayerCake::errors`] before falling through to the next source.
     pub fn resolve_number<F, V>(&mut self, args: ResolveNumberArgs<F, V>) -> i32
     where
         F: FnOnce() -> i32,
@@ -280,91 +506,51 @@ impl LayerCake {
         if !args.flag.starts_with("--") {
             panic!("[layercake] Flag '{}' must start with --", args.flag);
         }
-        let env_key = if self.env_prefix.is_empty() {
-            args.env.to_string()
-        } else {
-            format!("{}{}", self.env_prefix, args.env)
-        };
-        for src in &self.precedence {
-            match src {
+        let env_key = self.env_key(args.env);
+        for src in self.precedence.clone() {
+            let (location, parsed) = match src {
                 Source::Env => {
-                    if let Ok(v) = std::env::var(&env_key)
-                        && !v.is_empty()
-                    {
-                        match v.parse::<i32>() {
-                            Ok(parsed) => match (args.validate)(&parsed) {
-                                Ok(validated) => {
-                                    debug!(
-                                        "[layercake] {} number resolved from env: {}={}",
-                                        args.env, env_key, validated
-                                    );
-                                    self.source_history.from_env.push(args.env.to_string());
-                                    return validated;
-                                }
-                                Err(e) => {
-                                    panic!(
-                                        "[layercake] Invalid {} from env {}: {}",
-                                        args.env, env_key, e
-                                    )
-                                }
-                            },
-                            Err(e) => {
-                                panic!(
-                                    "[layercake] Failed to parse {} from env {}: {}",
-                                    args.env, env_key, e
-                                )
-                            }
-                        }
-                    }
+                    let Some(v) = std::env::var(&env_key).ok().filter(|v| !v.is_empty()) else {
+                        continue;
+                    };
+                    (
+                        env_key.clone(),
+                        v.trim().parse::<i32>().map_err(|e| (v, e.to_string())),
+                    )
                 }
                 Source::Flag => {
-                    if let Some(v) = self.flags.get(args.flag) {
-                        match v.parse::<i32>() {
-                            Ok(parsed) => match (args.validate)(&parsed) {
-                                Ok(validated) => {
-                                    debug!(
-                                        "[layercake] {} number resolved from flag: {}={}",
-                                        args.env, args.flag, validated
-                                    );
-                                    self.source_history.from_flag.push(args.env.to_string());
-                                    return validated;
-                                }
-                                Err(e) => {
-                                    panic!(
-                                        "[layercake] Invalid {} from flag {}: {}",
-                                        args.env, args.flag, e
-                                    )
-                                }
-                            },
-                            Err(e) => {
-                                panic!(
-                                    "[layercake] Failed to parse {} from flag {}: {}",
-                                    args.env, args.flag, e
-                                )
-                            }
-                        }
-                    }
+                    let Some(v) = self.flags.get(args.flag).cloned() else {
+                        continue;
+                    };
+                    (
+                        args.flag.to_string(),
+                        v.trim().parse::<i32>().map_err(|e| (v, e.to_string())),
+                    )
                 }
-                Source::File => {
-                    if let Some(v) = self.get_config_i32(args.file) {
-                        match (args.validate)(&v) {
-                            Ok(validated) => {
-                                debug!(
-                                    "[layercake] {} number resolved from {} key {}: {}",
-                                    args.env, self.config_filename, args.file, validated
-                                );
-                                self.source_history.from_file.push(args.env.to_string());
-                                return validated;
-                            }
-                            Err(e) => {
-                                panic!(
-                                    "[layercake] Invalid {} from file key {}: {}",
-                                    args.env, args.file, e
-                                )
-                            }
-                        }
-                    }
+                Source::File | Source::Project => {
+                    let Some(parsed) = self.get_config_i32(&src, args.file) else {
+                        continue;
+                    };
+                    (self.file_location(&src, args.file), parsed)
+                }
+            };
+            let parsed = match parsed {
+                Ok(parsed) => parsed,
+                Err((value, reason)) => {
+                    self.reject(args.env, src, &location, value, reason);
+                    continue;
                 }
+            };
+            match (args.validate)(&parsed) {
+                Ok(validated) => {
+                    debug!(
+                        "[layercake] {} number resolved from {location}: {validated}",
+                        args.env
+                    );
+                    self.record(&src, args.env, args.file);
+                    return validated;
+                }
+                Err(e) => self.reject(args.env, src, &location, parsed, e),
             }
         }
         let default = (args.default)();
@@ -381,47 +567,44 @@ impl LayerCake {
         if !args.flag.starts_with("--") {
             panic!("[layercake] Flag '{}' must start with --", args.flag);
         }
-        let env_key = if self.env_prefix.is_empty() {
-            args.env.to_string()
-        } else {
-            format!("{}{}", self.env_prefix, args.env)
-        };
-        for src in &self.precedence {
-            match src {
+        let env_key = self.env_key(args.env);
+        for src in self.precedence.clone() {
+            let (location, parsed) = match src {
                 Source::Env => {
-                    if let Ok(validated) = std::env::var(&env_key)
-                        && let Some(b) = Self::parse_bool(&validated)
-                    {
-                        debug!(
-                            "[layercake] {} boolean resolved from env: {}={}",
-                            args.env, env_key, b
-                        );
-                        self.source_history.from_env.push(args.env.to_string());
-                        return b;
-                    }
+                    let Some(v) = std::env::var(&env_key).ok().filter(|v| !v.is_empty()) else {
+                        continue;
+                    };
+                    (env_key.clone(), Self::parse_bool(&v).ok_or(v))
                 }
                 Source::Flag => {
-                    if let Some(validated) = self.flags.get(args.flag)
-                        && let Some(b) = Self::parse_bool(validated)
-                    {
-                        debug!(
-                            "[layercake] {} boolean resolved from flag: {}={}",
-                            args.env, args.flag, b
-                        );
-                        self.source_history.from_flag.push(args.env.to_string());
-                        return b;
-                    }
+                    let Some(v) = self.flags.get(args.flag).cloned() else {
+                        continue;
+                    };
+                    (args.flag.to_string(), Self::parse_bool(&v).ok_or(v))
                 }
-                Source::File => {
-                    if let Some(validated) = self.get_config_bool(args.file) {
-                        debug!(
-                            "[layercake] {} boolean resolved from {} key {}: {}",
-                            args.env, self.config_filename, args.file, validated
-                        );
-                        self.source_history.from_file.push(args.env.to_string());
-                        return validated;
-                    }
+                Source::File | Source::Project => {
+                    let Some(parsed) = self.get_config_bool(&src, args.file) else {
+                        continue;
+                    };
+                    (self.file_location(&src, args.file), parsed)
                 }
+            };
+            match parsed {
+                Ok(b) => {
+                    debug!(
+                        "[layercake] {} boolean resolved from {location}: {b}",
+                        args.env
+                    );
+                    self.record(&src, args.env, args.file);
+                    return b;
+                }
+                Err(value) => self.reject(
+                    args.env,
+                    src,
+                    &location,
+                    value,
+                    "expected a boolean (true/false, yes/no, on/off or 1/0)",
+                ),
             }
         }
         let default = (args.default)();
@@ -443,87 +626,53 @@ impl LayerCake {
         if !args.flag.starts_with("--") {
             panic!("[layercake] Flag '{}' must start with --", args.flag);
         }
-        let env_key = if self.env_prefix.is_empty() {
-            args.env.to_string()
-        } else {
-            format!("{}{}", self.env_prefix, args.env)
-        };
+        let env_key = self.env_key(args.env);
 
-        for src in &self.precedence {
-            match src {
+        for src in self.precedence.clone() {
+            let (location, parsed) = match src {
                 Source::Env => {
-                    if let Ok(v) = std::env::var(&env_key)
-                        && !v.trim().is_empty()
-                    {
-                        let parsed = Self::parse_string_list(&v);
-                        match (args.validate)(&parsed) {
-                            Ok(validated) => {
-                                debug!(
-                                    "[layercake] {} string array resolved from env: {}={}",
-                                    args.env,
-                                    env_key,
-                                    validated.join(",")
-                                );
-                                self.source_history.from_env.push(args.env.to_string());
-                                return validated;
-                            }
-                            Err(e) => {
-                                panic!(
-                                    "[layercake] Invalid {} from env {}: {}",
-                                    args.env, env_key, e
-                                )
-                            }
-                        }
-                    }
+                    let Some(v) = std::env::var(&env_key)
+                        .ok()
+                        .filter(|v| !v.trim().is_empty())
+                    else {
+                        continue;
+                    };
+                    (env_key.clone(), Self::parse_string_list(&v))
                 }
                 Source::Flag => {
-                    if let Some(v) = self.flags.get(args.flag)
-                        && !v.trim().is_empty()
-                    {
-                        let parsed = Self::parse_string_list(v);
-                        match (args.validate)(&parsed) {
-                            Ok(validated) => {
-                                debug!(
-                                    "[layercake] {} string array resolved from flag: {}=[{}]",
-                                    args.env,
-                                    args.flag,
-                                    validated.join(",")
-                                );
-                                self.source_history.from_flag.push(args.env.to_string());
-                                return validated;
-                            }
-                            Err(e) => {
-                                panic!(
-                                    "[layercake] Invalid {} from flag {}: {}",
-                                    args.env, args.flag, e
-                                )
-                            }
-                        }
-                    }
+                    let Some(v) = self
+                        .flags
+                        .get(args.flag)
+                        .filter(|v| !v.trim().is_empty())
+                        .cloned()
+                    else {
+                        continue;
+                    };
+                    (args.flag.to_string(), Self::parse_string_list(&v))
                 }
-                Source::File => {
-                    if let Some(v) = self.get_config_array_of_strings(args.file) {
-                        match (args.validate)(&v) {
-                            Ok(validated) => {
-                                debug!(
-                                    "[layercake] {} string array resolved from file {} key {}: [{}]",
-                                    args.env,
-                                    self.config_filename,
-                                    args.file,
-                                    validated.join(",")
-                                );
-                                self.source_history.from_file.push(args.env.to_string());
-                                return validated;
-                            }
-                            Err(e) => {
-                                panic!(
-                                    "[layercake] Invalid {} from file key {}: {}",
-                                    args.env, args.file, e
-                                )
-                            }
+                Source::File | Source::Project => {
+                    let location = self.file_location(&src, args.file);
+                    match self.get_config_array_of_strings(&src, args.file) {
+                        Some(Ok(v)) => (location, v),
+                        Some(Err((value, reason))) => {
+                            self.reject(args.env, src, &location, value, reason);
+                            continue;
                         }
+                        None => continue,
                     }
                 }
+            };
+            match (args.validate)(&parsed) {
+                Ok(validated) => {
+                    debug!(
+                        "[layercake] {} string array resolved from {location}: [{}]",
+                        args.env,
+                        validated.join(",")
+                    );
+                    self.record(&src, args.env, args.file);
+                    return validated;
+                }
+                Err(e) => self.reject(args.env, src, &location, parsed.join(","), e),
             }
         }
 
@@ -562,45 +711,74 @@ impl LayerCake {
         result
     }
 
-    fn get_config_str(&self, key_path: &str) -> Option<String> {
-        let mut cur = self.cfg.as_ref()?;
+    fn get_config_value(&self, source: &Source, key_path: &str) -> Option<&toml::Value> {
+        let cfg = match source {
+            Source::Project => &self.project_cfg,
+            _ => &self.cfg,
+        };
+        let mut cur = cfg.as_ref()?;
         for segment in key_path.split('.') {
             cur = cur.get(segment)?;
         }
-        cur.as_str().map(|s| s.to_string())
+        Some(cur)
     }
 
-    fn get_config_bool(&self, key_path: &str) -> Option<bool> {
-        let mut cur = self.cfg.as_ref()?;
-        for segment in key_path.split('.') {
-            cur = cur.get(segment)?;
-        }
-        cur.as_bool()
+    /// `None` when the key isn't set, `Err((value, reason))` when it's set to the wrong type.
+    /// Numbers and booleans are accepted as their string form.
+    fn get_config_str(
+        &self,
+        source: &Source,
+        key_path: &str,
+    ) -> Option<Result<String, (String, String)>> {
+        Some(match self.get_config_value(source, key_path)? {
+            toml::Value::String(s) => Ok(s.clone()),
+            toml::Value::Integer(i) => Ok(i.to_string()),
+            toml::Value::Float(f) => Ok(f.to_string()),
+            toml::Value::Boolean(b) => Ok(b.to_string()),
+            other => Err((other.to_string(), "expected a string".to_string())),
+        })
     }
 
-    fn get_config_i32(&self, key_path: &str) -> Option<i32> {
-        let mut cur = self.cfg.as_ref()?;
-        for segment in key_path.split('.') {
-            cur = cur.get(segment)?;
-        }
-        cur.as_integer().and_then(|i| i.try_into().ok())
+    fn get_config_bool(&self, source: &Source, key_path: &str) -> Option<Result<bool, String>> {
+        let value = self.get_config_value(source, key_path)?;
+        Some(value.as_bool().ok_or_else(|| value.to_string()))
     }
 
-    fn get_config_array_of_strings(&self, key_path: &str) -> Option<Vec<String>> {
-        let mut cur = self.cfg.as_ref()?;
-        for segment in key_path.split('.') {
-            cur = cur.get(segment)?;
-        }
-        let arr = cur.as_array()?;
+    fn get_config_i32(
+        &self,
+        source: &Source,
+        key_path: &str,
+    ) -> Option<Result<i32, (String, String)>> {
+        let value = self.get_config_value(source, key_path)?;
+        Some(match value.as_integer() {
+            Some(i) => i32::try_from(i).map_err(|e| (value.to_string(), e.to_string())),
+            None => Err((value.to_string(), "expected an integer".to_string())),
+        })
+    }
+
+    fn get_config_array_of_strings(
+        &self,
+        source: &Source,
+        key_path: &str,
+    ) -> Option<Result<Vec<String>, (String, String)>> {
+        let value = self.get_config_value(source, key_path)?;
+        let invalid = || {
+            (
+                value.to_string(),
+                "expected an array of strings".to_string(),
+            )
+        };
+        let Some(arr) = value.as_array() else {
+            return Some(Err(invalid()));
+        };
         let mut out = Vec::with_capacity(arr.len());
         for v in arr {
-            if let Some(s) = v.as_str() {
-                out.push(s.to_string());
-            } else {
-                return None;
+            match v.as_str() {
+                Some(s) => out.push(s.to_string()),
+                None => return Some(Err(invalid())),
             }
         }
-        Some(out)
+        Some(Ok(out))
     }
 
     /// Check whether a CLI flag was provided (e.g. `--project-root`).
@@ -620,3 +798,215 @@ impl LayerCake {
             .unwrap_or(false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "typeslayer.toml";
+
+    /// A cake whose env vars are its own, with no flags or config loaded.
+    fn cake(name: &str) -> LayerCake {
+        let mut cake = LayerCake::new(LayerCakeInitArgs {
+            config_filename: FILE,
+            precedence: [Source::Env, Source::Flag, Source::Project, Source::File],
+            env_prefix: &format!("TYPESLAYER_LAYERCAKE_TEST_{name}_"),
+        });
+        // the test harness's own arguments aren't ours
+        cake.flags.clear();
+        cake
+    }
+
+    fn set_env(cake: &LayerCake, key: &str, value: &str) {
+        // SAFETY: every test has its own prefix, so no other thread reads or writes this var
+        unsafe { std::env::set_var(cake.env_key(key), value) };
+    }
+
+    fn config(toml: &str) -> Option<toml::Value> {
+        Some(toml::from_str(toml).unwrap())
+    }
+
+    #[test]
+    fn invalid_env_string_falls_back_to_the_flag() {
+        let mut cake = cake("STRING");
+        set_env(&cake, "EDITOR", "notepad");
+        cake.flags
+            .insert("--editor".to_string(), "code".to_string());
+        let resolve = |cake: &mut LayerCake| {
+            cake.resolve_string(ResolveStringArgs {
+                env: "EDITOR",
+                flag: "--editor",
+                file: "settings.editor",
+                default: || "vim".to_string(),
+                validate: |s| match s {
+                    "code" | "vim" => Ok(s.to_string()),
+                    _ => Err(format!("unknown editor {s}")),
+                },
+            })
+        };
+
+        assert_eq!(resolve(&mut cake), "code");
+        // resolving again doesn't report the same value twice
+        assert_eq!(resolve(&mut cake), "code");
+        assert_eq!(
+            cake.errors,
+            [ConfigError {
+                key: "EDITOR".to_string(),
+                source: Source::Env,
+                location: "TYPESLAYER_LAYERCAKE_TEST_STRING_EDITOR".to_string(),
+                value: "notepad".to_string(),
+                reason: "unknown editor notepad".to_string(),
+            }]
+        );
+        assert_eq!(cake.source_of("EDITOR"), Some(Source::Flag));
+    }
+
+    #[test]
+    fn unparseable_env_number_falls_back_to_the_file() {
+        let mut cake = cake("NUMBER");
+        set_env(&cake, "MAX_NODES", "lots");
+        cake.cfg = config("[settings]\nmaxNodes = 5\n");
+
+        let max_nodes = cake.resolve_number(ResolveNumberArgs {
+            env: "MAX_NODES",
+            flag: "--max-nodes",
+            file: "settings.maxNodes",
+            default: || 100,
+            validate: |n| Ok(*n),
+        });
+
+        assert_eq!(max_nodes, 5);
+        assert_eq!(
+            cake.errors,
+            [ConfigError {
+                key: "MAX_NODES".to_string(),
+                source: Source::Env,
+                location: "TYPESLAYER_LAYERCAKE_TEST_NUMBER_MAX_NODES".to_string(),
+                value: "lots".to_string(),
+                reason: "lots".parse::<i32>().unwrap_err().to_string(),
+            }]
+        );
+        assert_eq!(cake.source_of("MAX_NODES"), Some(Source::File));
+    }
+
+    #[test]
+    fn invalid_file_number_is_reported_with_its_key_path() {
+        let mut cake = cake("FILE_NUMBER");
+        cake.cfg = config("[settings]\nmaxNodes = 5\n");
+
+        let max_nodes = cake.resolve_number(ResolveNumberArgs {
+            env: "MAX_NODES",
+            flag: "--max-nodes",
+            file: "settings.maxNodes",
+            default: || 100,
+            validate: |n| {
+                if *n >= 10 {
+                    Ok(*n)
+                } else {
+                    Err("maxNodes must be at least 10".to_string())
+                }
+            },
+        });
+
+        assert_eq!(max_nodes, 100);
+        assert_eq!(
+            cake.errors,
+            [ConfigError {
+                key: "MAX_NODES".to_string(),
+                source: Source::File,
+                location: format!("{FILE}: settings.maxNodes"),
+                value: "5".to_string(),
+                reason: "maxNodes must be at least 10".to_string(),
+            }]
+        );
+        assert_eq!(cake.source_of("MAX_NODES"), None);
+    }
+
+    #[test]
+    fn invalid_env_bool_falls_back_to_the_default() {
+        let mut cake = cake("BOOL");
+        set_env(&cake, "VERBOSE", "maybe");
+
+        let verbose = cake.resolve_bool(ResolveBoolArgs {
+            env: "VERBOSE",
+            flag: "--verbose",
+            file: "settings.verbose",
+            default: || true,
+        });
+
+        assert!(verbose);
+        assert_eq!(
+            cake.errors,
+            [ConfigError {
+                key: "VERBOSE".to_string(),
+                source: Source::Env,
+                location: "TYPESLAYER_LAYERCAKE_TEST_BOOL_VERBOSE".to_string(),
+                value: "maybe".to_string(),
+                reason: "expected a boolean (true/false, yes/no, on/off or 1/0)".to_string(),
+            }]
+        );
+        assert_eq!(cake.source_of("VERBOSE"), None);
+    }
+
+    #[test]
+    fn invalid_env_string_array_falls_back_to_the_file() {
+        let mut cake = cake("ARRAY");
+        set_env(&cake, "CONSENT", "a, b");
+        cake.cfg = config("[settings]\nconsent = [\"a\"]\n");
+
+        let consent = cake.resolve_array_of_strings(ResolveArrayOfStringsArgs {
+            env: "CONSENT",
+            flag: "--consent",
+            file: "settings.consent",
+            default: Vec::new,
+            validate: |items| match items.iter().find(|item| *item != "a") {
+                Some(item) => Err(format!("unknown event {item}")),
+                None => Ok(items.to_vec()),
+            },
+        });
+
+        assert_eq!(consent, ["a"]);
+        assert_eq!(
+            cake.errors,
+            [ConfigError {
+                key: "CONSENT".to_string(),
+                source: Source::Env,
+                location: "TYPESLAYER_LAYERCAKE_TEST_ARRAY_CONSENT".to_string(),
+                value: "a,b".to_string(),
+                reason: "unknown event b".to_string(),
+            }]
+        );
+        assert_eq!(cake.source_of("CONSENT"), Some(Source::File));
+    }
+
+    #[test]
+    fn skips_a_config_file_that_is_not_toml() {
+        let dir = std::env::temp_dir().join(format!("typeslayer-layercake-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(FILE);
+        std::fs::write(&path, "[settings\nmaxNodes = 5\n").unwrap();
+
+        let mut cake = cake("NOT_TOML");
+        tauri::async_runtime::block_on(cake.load_config_in_dir(dir.to_string_lossy().to_string()))
+            .unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(cake.cfg.is_none());
+        let [error] = cake.errors.as_slice() else {
+            panic!("expected one error, got {:?}", cake.errors);
+        };
+        assert_eq!(error.source, Source::File);
+        assert_eq!(error.location, path.to_string_lossy());
+        assert!(error.reason.starts_with("not valid TOML: "));
+
+        let max_nodes = cake.resolve_number(ResolveNumberArgs {
+            env: "MAX_NODES",
+            flag: "--max-nodes",
+            file: "settings.maxNodes",
+            default: || 100,
+            validate: |n| Ok(*n),
+        });
+        assert_eq!(max_nodes, 100);
+        assert_eq!(cake.errors.len(), 1);
+    }
+}
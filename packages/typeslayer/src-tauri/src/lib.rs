@@ -1,13 +1,26 @@
 mod analytics;
 mod analyze_trace;
 pub mod app_data;
+mod bisect;
 mod commands;
+mod cpu_profile;
+mod crash;
+mod emit;
+mod experiments;
+mod history;
 mod http_server;
 mod layercake;
 pub mod log;
 mod mcp;
 mod process_controller;
+mod program_construction;
+mod repeated_runs;
+mod run_metadata;
+mod stats;
+mod trace_progress;
 mod treemap;
+mod tsconfig;
+mod tsserver;
 mod type_graph;
 pub mod utils;
 mod validate;
@@ -15,16 +28,20 @@ mod validate;
 #[cfg(test)]
 mod export_types;
 
-use crate::process_controller::ProcessController;
+pub use crate::process_controller::ProcessController;
 use tauri::Manager;
 use tokio::sync::Mutex;
 
 pub use http_server::run_http_server;
-pub use mcp::run_mcp_server;
+pub use mcp::{run_mcp_http_server, run_mcp_server, status::McpStatusTracker};
 
 use crate::utils::compute_window_title;
 
-pub async fn run_tauri_app(app_data: &'static Mutex<app_data::AppData>) {
+pub async fn run_tauri_app(
+    app_data: &'static Mutex<app_data::AppData>,
+    mcp_status: McpStatusTracker,
+    process_controller: ProcessController,
+) {
     let app = app_data.lock().await;
     let project_root = app.project_root.clone();
     drop(app);
@@ -37,12 +54,12 @@ pub async fn run_tauri_app(app_data: &'static Mutex<app_data::AppData>) {
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_screenshots::init())
         .setup(move |app| {
-            app.manage(ProcessController::new());
+            // Shared with the MCP HTTP transport so the GUI can cancel runs agents started
+            app.manage(process_controller);
             app.manage(app_data);
 
-            // Initialize MCP status tracker
-            let mcp_tracker = mcp::status::McpStatusTracker::new();
-            app.manage(mcp_tracker);
+            // Shared with the MCP HTTP transport so running tools show up in the GUI
+            app.manage(mcp_status);
 
             // Set initial window title based on detected project package.json
             if let Some(win) = app.get_webview_window("main") {
@@ -57,26 +74,54 @@ pub async fn run_tauri_app(app_data: &'static Mutex<app_data::AppData>) {
             commands::actions::take_screenshot,
             commands::app_data::clear_outputs,
             commands::app_data::get_analyze_trace,
+            commands::app_data::get_depth_limit_report,
             commands::app_data::get_cpu_profile,
+            commands::cpu_profile::get_cpu_profile_summary,
+            commands::crash::get_crash_diagnosis,
+            commands::cpu_profile::get_cpu_profile_top_down,
+            commands::cpu_profile::get_cpu_profile_bottom_up,
+            commands::cpu_profile::get_hot_spot_profiles,
+            commands::tsserver::analyze_tsserver_trace,
             commands::app_data::get_data_dir,
+            commands::app_data::get_outputs_dir,
+            commands::app_data::get_effective_tsconfig,
+            commands::app_data::get_run_metadata,
+            commands::app_data::get_config_errors,
+            commands::app_data::get_package_manager,
+            commands::app_data::get_project_config,
+            commands::app_data::get_budget_checks,
             commands::app_data::get_project_root,
             commands::app_data::get_selected_tsconfig,
             commands::app_data::get_trace_json,
+            commands::app_data::get_unknown_trace_events,
             commands::app_data::get_tsconfig_paths,
+            commands::app_data::get_tsconfig_tree,
             commands::app_data::get_type_graph_limited_node_and_link_stats,
             commands::app_data::get_type_graph_nodes_and_links,
             commands::app_data::get_type_graph_stats,
+            commands::app_data::resolve_effective_tsconfig,
             commands::app_data::set_project_root,
             commands::app_data::set_selected_tsconfig,
+            commands::bisect::bisect,
             commands::bug_report::create_bug_report,
             commands::bug_report::get_bug_report_files,
             commands::bug_report::upload_bug_report,
+            commands::experiments::delete_experiment,
+            commands::experiments::get_experiments,
+            commands::experiments::run_compiler_experiment,
             commands::generate::cancel_generation,
             commands::generate::generate_all,
             commands::generate::generate_analyze_trace,
             commands::generate::generate_cpu_profile,
             commands::generate::generate_trace,
             commands::generate::generate_type_graph,
+            commands::history::create_snapshot,
+            commands::history::delete_snapshot,
+            commands::history::get_snapshot_time_series,
+            commands::history::get_snapshots,
+            commands::history::restore_snapshot,
+            commands::repeated_runs::get_repeated_runs,
+            commands::repeated_runs::run_repeated_traces,
             commands::mcp::get_available_mcp_resources,
             commands::mcp::get_available_mcp_tools,
             commands::mcp::get_mcp_running_tools,
@@ -97,22 +142,36 @@ pub async fn run_tauri_app(app_data: &'static Mutex<app_data::AppData>) {
             commands::settings::get_max_nodes,
             commands::settings::get_max_old_space_size,
             commands::settings::get_max_stack_size,
+            commands::settings::get_max_workspaces,
             commands::settings::get_prefer_editor_open,
             commands::settings::get_preferred_editor,
             commands::settings::get_relative_paths,
+            commands::settings::get_tsc_max_rss_mb,
+            commands::settings::get_tsc_timeout_seconds,
             commands::settings::get_typescript_compiler_variant,
             commands::settings::get_version,
+            commands::settings::get_workspace_retention_days,
+            commands::settings::get_max_snapshots,
             commands::settings::set_analytics_consent,
             commands::settings::set_apply_tsc_project_flag,
             commands::settings::set_extra_tsc_flags,
             commands::settings::set_max_nodes,
             commands::settings::set_max_old_space_size,
             commands::settings::set_max_stack_size,
+            commands::settings::set_max_workspaces,
             commands::settings::set_prefer_editor_open,
             commands::settings::set_preferred_editor,
             commands::settings::set_relative_paths,
+            commands::settings::set_tsc_max_rss_mb,
+            commands::settings::set_tsc_timeout_seconds,
             commands::settings::set_typescript_compiler_variant,
+            commands::settings::set_workspace_retention_days,
+            commands::settings::set_max_snapshots,
             commands::treemap::get_treemap_data,
+            commands::program_construction::get_program_construction,
+            commands::program_construction::get_inclusion_graph,
+            commands::program_construction::why_is_file_included,
+            commands::emit::get_emit_report,
             commands::trivia::get_app_stats,
             commands::trivia::get_available_editors,
             commands::trivia::get_link_kind_data_by_kind,
@@ -129,6 +188,11 @@ pub async fn run_tauri_app(app_data: &'static Mutex<app_data::AppData>) {
             commands::validate::validate_trace_json,
             commands::validate::validate_type_graph,
             commands::validate::validate_types_json,
+            commands::workspaces::delete_workspace,
+            commands::workspaces::get_active_workspace_id,
+            commands::workspaces::get_workspaces,
+            commands::workspaces::prune_workspaces,
+            commands::workspaces::switch_workspace,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -4,8 +4,9 @@
 use tokio::sync::Mutex;
 
 use typeslayer_lib::{
+    McpStatusTracker, ProcessController,
     app_data::{AppData, AppMode},
-    run_mcp_server, run_tauri_app,
+    run_mcp_http_server, run_mcp_server, run_tauri_app,
     utils::get_typeslayer_base_data_dir,
 };
 
@@ -31,6 +32,11 @@ async fn main() -> Result<(), String> {
         AppData::new(data_dir, mode.clone()).await?,
     )));
 
+    // invalid settings don't stop the app, but whoever set them should hear about it
+    for error in &app_data.lock().await.cake.errors {
+        eprintln!("typeslayer: {error}; falling back");
+    }
+
     if mode == AppMode::MCP {
         // Run as MCP server (STDIO mode) with shared AppData
         // In MCP mode, stdout is reserved for JSON-RPC protocol, so no HTTP server or GUI
@@ -57,8 +63,34 @@ async fn main() -> Result<(), String> {
             }
         });
 
+        // MCP over streamable HTTP, sharing the live AppData and running processes with the GUI
+        // (port 0 disables it)
+        let mcp_status = McpStatusTracker::new();
+        let process_controller = ProcessController::new();
+        let mcp_http_port = app_data.lock().await.mcp_http_port;
+        if mcp_http_port != 0 {
+            match tokio::net::TcpListener::bind(("127.0.0.1", mcp_http_port)).await {
+                Ok(listener) => {
+                    let status = mcp_status.clone();
+                    let process_controller = process_controller.clone();
+                    tokio::task::spawn(async move {
+                        if let Err(e) =
+                            run_mcp_http_server(app_data, status, process_controller, listener)
+                                .await
+                        {
+                            eprintln!("MCP HTTP server error: {e}");
+                        }
+                    });
+                }
+                Err(e) => {
+                    eprintln!("Failed to bind MCP HTTP server on port {mcp_http_port}: {e}");
+                    eprintln!("The app will continue but MCP over HTTP won't be available");
+                }
+            }
+        }
+
         // Run Tauri GUI app with the shared AppData
-        run_tauri_app(app_data).await;
+        run_tauri_app(app_data, mcp_status, process_controller).await;
     }
 
     Ok(())
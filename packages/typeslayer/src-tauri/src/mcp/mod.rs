@@ -1,17 +1,32 @@
+pub mod progress;
 pub mod resources;
 pub mod status;
 pub mod tools;
 
-use crate::app_data::AppData;
+use crate::{
+    app_data::AppData, http_server::ALLOWED_ORIGINS, process_controller::ProcessController,
+};
+use axum::{
+    extract::{Request, State},
+    http::{StatusCode, header},
+    middleware::{Next, from_fn_with_state},
+    response::Response,
+};
 use rmcp::{
-    ServerHandler, ServiceExt,
+    RoleServer, ServerHandler, ServiceExt,
     handler::server::tool::ToolRouter,
     model::{ServerCapabilities, ServerInfo},
+    service::RequestContext,
     tool, tool_handler, tool_router,
-    transport::stdio,
+    transport::{
+        stdio,
+        streamable_http_server::{
+            StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+        },
+    },
 };
-use std::io;
-use tokio::sync::Mutex;
+use std::{io, sync::Arc};
+use tokio::{net::TcpListener, sync::Mutex};
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
@@ -22,16 +37,33 @@ use tracing_subscriber::EnvFilter;
 pub struct TypeSlayerMcpServer {
     app_data: &'static Mutex<AppData>,
     pub status: status::McpStatusTracker,
+    process_controller: ProcessController,
     tool_router: ToolRouter<Self>,
 }
 
 #[tool_router]
 impl TypeSlayerMcpServer {
     pub fn new(app_data: &'static Mutex<AppData>) -> Self {
+        Self::with_status(
+            app_data,
+            status::McpStatusTracker::new(),
+            ProcessController::new(),
+        )
+    }
+
+    /// Create a server that reports running tools to an existing tracker and runs tsc
+    /// through an existing controller, so the GUI can show and cancel what an
+    /// HTTP-connected agent is doing.
+    pub fn with_status(
+        app_data: &'static Mutex<AppData>,
+        status: status::McpStatusTracker,
+        process_controller: ProcessController,
+    ) -> Self {
         info!("Initializing TypeSlayer MCP Server");
         Self {
             app_data,
-            status: status::McpStatusTracker::new(),
+            status,
+            process_controller,
             tool_router: Self::tool_router(),
         }
     }
@@ -70,7 +102,7 @@ impl TypeSlayerMcpServer {
     }
 
     #[tool(
-        description = "Returns depth limit events grouped by category (e.g., instantiateType_DepthLimit, checkTypeRelatedTo_DepthLimit). These are TypeScript compiler internal limits hit during type checking."
+        description = "Returns depth limit events grouped by category (e.g., instantiateType_DepthLimit, checkTypeRelatedTo_DepthLimit). These are TypeScript compiler internal limits hit during type checking. Events on the same types are merged, biggest depth or size first, with the names and declarations of the types involved and the files and positions being checked when the limit was hit."
     )]
     async fn get_depth_limits(&self) -> String {
         let command = crate::mcp::tools::get_depth_limits::COMMAND;
@@ -79,6 +111,37 @@ impl TypeSlayerMcpServer {
         self.status.end_tool(command).await;
         result
     }
+
+    #[tool(
+        description = "Summarizes the CPU profile of the TypeScript compiler: time in checker functions (checkSourceFile, isRelatedTo, instantiateType) and garbage collection, the functions with the most self time, and who called them. When a trace is loaded too, each hot spot is broken down by the functions and garbage collection that ran during it, e.g. \"3.0s, 70% in structuredTypeRelatedToWorker, 20% GC\": mostly GC means raising maxOldSpaceSize helps, mostly checker functions means the type itself needs fixing. Times are in milliseconds."
+    )]
+    async fn get_cpu_profile(&self) -> String {
+        let command = crate::mcp::tools::get_cpu_profile::COMMAND;
+        self.status.start_tool(command).await;
+        let result = crate::mcp::tools::get_cpu_profile::execute(self.app_data).await;
+        self.status.end_tool(command).await;
+        result
+    }
+
+    #[tool(
+        description = "Runs the TypeScript compiler with --generateTrace on the current project and loads the resulting trace. Streams compiler output and trace progress as progress notifications while it runs. If tsc crashes, the error comes with a crash diagnosis: the trace events left open, the file and node being checked, the known failure it matched (heap out of memory, stack overflow, excessively deep types) and settings to change."
+    )]
+    async fn generate_trace(&self, context: RequestContext<RoleServer>) -> String {
+        let command = crate::mcp::tools::generate_trace::COMMAND;
+        self.status.start_tool(command).await;
+        let reporter = progress::McpProgressReporter::new(
+            context.peer.clone(),
+            context.meta.get_progress_token(),
+        );
+        let result = crate::mcp::tools::generate_trace::execute(
+            self.app_data,
+            &self.process_controller,
+            &reporter,
+        )
+        .await;
+        self.status.end_tool(command).await;
+        result
+    }
 }
 
 #[tool_handler]
@@ -139,3 +202,65 @@ pub async fn run_mcp_server(app_data: &'static Mutex<AppData>) -> io::Result<()>
     info!("MCP server shutting down: {:?}", quit_reason);
     Ok(())
 }
+
+/// Run the MCP server over streamable HTTP (with SSE) inside the GUI process
+///
+/// Unlike STDIO mode, every session shares the live AppData the GUI is using,
+/// so agents see exactly what the developer is looking at without reloading outputs.
+/// The endpoint is served at `/mcp` on the given listener.
+pub async fn run_mcp_http_server(
+    app_data: &'static Mutex<AppData>,
+    status: status::McpStatusTracker,
+    process_controller: ProcessController,
+    listener: TcpListener,
+) -> io::Result<()> {
+    info!(
+        "[run_mcp_http_server] MCP streamable HTTP transport listening on http://{}/mcp",
+        listener.local_addr()?
+    );
+
+    let service = StreamableHttpService::new(
+        move || {
+            Ok(TypeSlayerMcpServer::with_status(
+                app_data,
+                status.clone(),
+                process_controller.clone(),
+            ))
+        },
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+
+    let port = listener.local_addr()?.port();
+    let router = axum::Router::new()
+        .nest_service("/mcp", service)
+        .layer(from_fn_with_state(port, reject_non_local_requests));
+
+    axum::serve(listener, router.into_make_service()).await
+}
+
+/// Only the GUI and local agents get to call tools that spawn processes. A web page the
+/// developer has open could otherwise reach the port through DNS rebinding, so requests
+/// with a foreign `Origin`, or a `Host` other than this port on loopback, are refused.
+async fn reject_non_local_requests(
+    State(port): State<u16>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let headers = request.headers();
+    let origin_allowed = headers.get(header::ORIGIN).is_none_or(|origin| {
+        origin
+            .to_str()
+            .is_ok_and(|origin| ALLOWED_ORIGINS.contains(&origin))
+    });
+    let host_allowed = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .is_some_and(|host| {
+            host == format!("127.0.0.1:{port}") || host == format!("localhost:{port}")
+        });
+    if !origin_allowed || !host_allowed {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(next.run(request).await)
+}
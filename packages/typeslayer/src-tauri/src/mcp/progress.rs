@@ -0,0 +1,78 @@
+use crate::{
+    process_controller::{OutputStream, ProgressReporter},
+    trace_progress::TraceProgress,
+};
+use rmcp::{
+    Peer, RoleServer,
+    model::{ProgressNotificationParam, ProgressToken},
+};
+use tokio::sync::mpsc;
+use tracing::debug;
+
+/// Forwards a running command's output and progress to the client as MCP progress notifications.
+///
+/// Notifications are only sent when the client asked for them with a progress token.
+/// They go through a single forwarding task so they arrive in order.
+pub struct McpProgressReporter {
+    sender: Option<mpsc::UnboundedSender<(Option<u64>, String)>>,
+}
+
+impl McpProgressReporter {
+    pub fn new(peer: Peer<RoleServer>, progress_token: Option<ProgressToken>) -> Self {
+        let Some(progress_token) = progress_token else {
+            return Self { sender: None };
+        };
+
+        let (sender, mut receiver) = mpsc::unbounded_channel::<(Option<u64>, String)>();
+        tokio::spawn(async move {
+            // progress must increase with every notification, even when there's nothing to count
+            let mut progress = 0.0;
+            while let Some((files_checked, message)) = receiver.recv().await {
+                progress = match files_checked {
+                    Some(files_checked) => f64::max(progress + 1.0, files_checked as f64),
+                    None => progress + 1.0,
+                };
+                let param = ProgressNotificationParam {
+                    progress_token: progress_token.clone(),
+                    progress,
+                    total: None,
+                    message: Some(message),
+                };
+                if let Err(e) = peer.notify_progress(param).await {
+                    debug!("[McpProgressReporter] failed to send progress: {e}");
+                    break;
+                }
+            }
+        });
+
+        Self {
+            sender: Some(sender),
+        }
+    }
+
+    fn send(&self, files_checked: Option<u64>, message: String) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send((files_checked, message));
+        }
+    }
+}
+
+impl ProgressReporter for McpProgressReporter {
+    fn output_line(&self, stream: OutputStream, line: &str) {
+        let prefix = match stream {
+            OutputStream::Stdout => "stdout",
+            OutputStream::Stderr => "stderr",
+        };
+        self.send(None, format!("[{prefix}] {line}"));
+    }
+
+    fn trace_progress(&self, progress: &TraceProgress) {
+        self.send(
+            Some(progress.files_checked as u64),
+            format!(
+                "{} files checked, trace.json is {} bytes after {}ms",
+                progress.files_checked, progress.trace_json_size, progress.elapsed_ms
+            ),
+        );
+    }
+}
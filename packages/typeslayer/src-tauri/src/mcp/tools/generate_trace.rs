@@ -0,0 +1,57 @@
+use crate::{
+    app_data::AppData,
+    commands::generate::run_generate_trace,
+    mcp::tools::ToolDefinition,
+    process_controller::{ProcessController, ProgressReporter},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::info;
+
+pub const COMMAND: &str = "generate_trace";
+pub const DESCRIPTION: &str = "Runs the TypeScript compiler with --generateTrace on the current project and loads the resulting trace. Streams compiler output and trace progress as progress notifications while it runs. If tsc crashes, the error comes with a crash diagnosis: the trace events left open, the file and node being checked, the known failure it matched (heap out of memory, stack overflow, excessively deep types) and settings to change.";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateTraceResponse {
+    pub types_count: usize,
+    pub trace_events_count: usize,
+}
+
+pub fn tool_definition() -> ToolDefinition<GenerateTraceResponse> {
+    ToolDefinition {
+        command: COMMAND.to_string(),
+        display_name: "Generate Trace".to_string(),
+        description: DESCRIPTION.to_string(),
+        parameters: vec![],
+        returns: GenerateTraceResponse {
+            types_count: 48213,
+            trace_events_count: 301877,
+        },
+    }
+}
+
+pub async fn execute(
+    state: &Mutex<AppData>,
+    process_controller: &ProcessController,
+    reporter: &dyn ProgressReporter,
+) -> String {
+    info!("[generate_trace] called");
+
+    let mut app_data = state.lock().await;
+
+    if let Err(e) = run_generate_trace(&mut app_data, process_controller, reporter, false).await {
+        return serde_json::json!({ "error": e, "crashDiagnosis": app_data.crash_diagnosis })
+            .to_string();
+    }
+
+    let response = GenerateTraceResponse {
+        types_count: app_data.types_json.len(),
+        trace_events_count: app_data.trace_json.len(),
+    };
+
+    match serde_json::to_string_pretty(&response) {
+        Ok(json) => json,
+        Err(e) => format!("{{\"error\": \"Failed to serialize response: {e}\"}}"),
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{
+    app_data::AppData,
+    commands::cpu_profile::{correlate_hot_spots, ensure_cpu_profile_analysis},
+    cpu_profile::{
+        CallTreeNode, CallTreeOptions, CheckerGroupTime, FunctionTime, TraceProfileCorrelation,
+    },
+    mcp::tools::{ToolDefinition, ToolParameter},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::info;
+
+pub const COMMAND: &str = "get_cpu_profile";
+pub const DESCRIPTION: &str = "Summarizes the CPU profile of the TypeScript compiler: time in checker functions (checkSourceFile, isRelatedTo, instantiateType) and garbage collection, the functions with the most self time, and who called them. When a trace is loaded too, each hot spot is broken down by the functions and garbage collection that ran during it, e.g. \"3.0s, 70% in structuredTypeRelatedToWorker, 20% GC\": mostly GC means raising maxOldSpaceSize helps, mostly checker functions means the type itself needs fixing. Times are in milliseconds.";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCpuProfileResponse {
+    pub total_time: f64,
+    pub sample_count: usize,
+    pub checker_groups: Vec<CheckerGroupTime>,
+    /// Slowest first, by self time
+    pub functions: Vec<FunctionTime>,
+    /// The slowest functions by self time, with the call paths that led to them
+    pub bottom_up: Vec<CallTreeNode>,
+    /// The slowest hot spots of the trace, broken down by what ran during them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hot_spots: Option<TraceProfileCorrelation>,
+}
+
+pub fn tool_definition() -> ToolDefinition<GetCpuProfileResponse> {
+    ToolDefinition {
+        command: COMMAND.to_string(),
+        display_name: "Get CPU Profile".to_string(),
+        description: DESCRIPTION.to_string(),
+        parameters: vec![ToolParameter {
+            name: "limit".to_string(),
+            optional: true,
+            default: Some(serde_json::json!(20)),
+            description: "Maximum number of functions to return".to_string(),
+        }],
+        returns: GetCpuProfileResponse {
+            total_time: 5120.0,
+            sample_count: 5120,
+            checker_groups: vec![CheckerGroupTime {
+                group: "isRelatedTo".to_string(),
+                functions: vec!["isRelatedTo".to_string()],
+                self_time: 210.0,
+                total_time: 1830.0,
+                share: 0.357,
+            }],
+            functions: vec![FunctionTime {
+                function_name: "structuredTypeRelatedToWorker".to_string(),
+                url: "file:///project/node_modules/typescript/lib/_tsc.js".to_string(),
+                line_number: 64000,
+                column_number: 4,
+                self_time: 320.5,
+                total_time: 1410.0,
+            }],
+            bottom_up: vec![],
+            hot_spots: None,
+        },
+    }
+}
+
+pub async fn execute(state: &Mutex<AppData>) -> String {
+    info!("[get_cpu_profile] called");
+
+    let mut app_data = state.lock().await;
+    let analysis = match ensure_cpu_profile_analysis(&mut app_data).await {
+        Ok(analysis) => analysis,
+        Err(e) => return serde_json::json!({ "error": e }).to_string(),
+    };
+
+    let limit = 20usize; // stub parameter, matches tool definition default
+
+    let summary = analysis.summary(limit);
+    let mut bottom_up = analysis.bottom_up(&CallTreeOptions {
+        max_depth: 4,
+        ..CallTreeOptions::default()
+    });
+    bottom_up.truncate(limit);
+
+    // the breakdown is a bonus, so a missing trace isn't an error
+    let hot_spots = correlate_hot_spots(&mut app_data)
+        .await
+        .ok()
+        .map(|mut correlation| {
+            correlation.hot_spots.truncate(limit);
+            correlation
+        });
+
+    let response = GetCpuProfileResponse {
+        total_time: summary.total_time,
+        sample_count: summary.sample_count,
+        checker_groups: summary.checker_groups,
+        functions: summary.functions,
+        bottom_up,
+        hot_spots,
+    };
+
+    match serde_json::to_string_pretty(&response) {
+        Ok(json) => json,
+        Err(e) => format!("{{\"error\": \"Failed to serialize response: {e}\"}}"),
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    analyze_trace::DepthLimitKind,
+    analyze_trace::{CheckSite, DepthLimitKind, DepthLimitType, create_depth_limit_report},
     app_data::AppData,
     mcp::tools::{ToolDefinition, ToolParameter},
 };
@@ -8,7 +8,7 @@ use tokio::sync::Mutex;
 use tracing::info;
 
 pub const COMMAND: &str = "get_depth_limits";
-pub const DESCRIPTION: &str = "Returns depth limit events grouped by category (e.g., instantiateType_DepthLimit, checkTypeRelatedTo_DepthLimit). These are TypeScript compiler internal limits hit during type checking.";
+pub const DESCRIPTION: &str = "Returns depth limit events grouped by category (e.g., instantiateType_DepthLimit, checkTypeRelatedTo_DepthLimit). These are TypeScript compiler internal limits hit during type checking. Events on the same types are merged, biggest depth or size first, with the names and declarations of the types involved and the files and positions being checked when the limit was hit.";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +23,10 @@ pub struct DepthLimitCategory {
 pub struct DepthLimitEvent {
     pub timestamp: f64,
     pub args: serde_json::Value,
+    /// Events merged into this one because they were on the same types
+    pub count: usize,
+    pub types: Vec<DepthLimitType>,
+    pub sites: Vec<CheckSite>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -57,7 +61,19 @@ pub fn tool_definition() -> ToolDefinition<GetDepthLimitsExample> {
                 count: 3,
                 events: vec![DepthLimitEvent {
                     timestamp: 123456.789,
-                    args: serde_json::json!({"instantiationDepth": 50}),
+                    args: serde_json::json!({"typeId": 4821, "instantiationDepth": 100}),
+                    count: 3,
+                    types: vec![DepthLimitType {
+                        id: 4821,
+                        name: Some("DeepPartial<Config>".to_string()),
+                        declaration: None,
+                    }],
+                    sites: vec![CheckSite {
+                        path: "/project/src/config.ts".to_string(),
+                        pos: Some(1204),
+                        end: Some(1260),
+                        start: None,
+                    }],
                 }],
             }],
             total_events: 3,
@@ -68,45 +84,47 @@ pub fn tool_definition() -> ToolDefinition<GetDepthLimitsExample> {
 pub async fn execute(state: &Mutex<AppData>) -> String {
     info!("[get_depth_limits] called");
 
-    // Lock app_data to access analyze_trace
     let app_data = state.lock().await;
+    if app_data.trace_json.is_empty() {
+        return r#"{"error": "No trace data available. Please generate a trace first."}"#
+            .to_string();
+    }
 
-    // Check if analyze-trace data is available
-    let analyze_trace = match app_data.analyze_trace.as_ref() {
-        Some(at) => at,
-        None => {
-            return r#"{"error": "No analyze-trace data available. Please run trace analysis first."}"#
-                .to_string()
-        }
+    let report = match create_depth_limit_report(&app_data.trace_json, &app_data.types_json) {
+        Ok(report) => report,
+        Err(e) => return format!("{{\"error\": \"Failed to resolve depth limits: {e}\"}}"),
     };
 
     let limit = 10usize; // stub parameter, matches tool definition default
 
     let mut total_events = 0;
-    let mut categories: Vec<DepthLimitCategory> = analyze_trace
-        .depth_limits
-        .iter()
-        .map(|(category, events)| {
-            total_events += events.len();
-            let limited_events: Vec<DepthLimitEvent> = events
-                .iter()
+    let mut categories: Vec<DepthLimitCategory> = report
+        .into_iter()
+        .map(|(category, limits)| {
+            let count = limits.iter().map(|limit| limit.count).sum();
+            total_events += count;
+            let limited_events: Vec<DepthLimitEvent> = limits
+                .into_iter()
                 .take(limit)
-                .map(|ev| DepthLimitEvent {
-                    timestamp: ev.common().ts,
-                    args: serde_json::to_value(ev).unwrap_or(serde_json::Value::Null),
+                .map(|limit| DepthLimitEvent {
+                    timestamp: limit.event.common().ts,
+                    args: serde_json::to_value(&limit.event).unwrap_or(serde_json::Value::Null),
+                    count: limit.count,
+                    types: limit.types,
+                    sites: limit.sites,
                 })
                 .collect();
 
             DepthLimitCategory {
-                category: *category,
-                count: events.len(),
+                category,
+                count,
                 events: limited_events,
             }
         })
         .collect();
 
     // Sort categories by event count (desc) for relevance
-    categories.sort_by(|a, b| b.count.cmp(&a.count));
+    categories.sort_by_key(|category| std::cmp::Reverse(category.count));
 
     let response = GetDepthLimitsResponse {
         categories,
@@ -55,7 +55,7 @@ pub async fn execute(state: &Mutex<AppData>) -> String {
     info!("[get_hot_files] called");
 
     // Lock app_data to access trace data
-    let app_data = state.lock().await;
+    let mut app_data = state.lock().await;
 
     if app_data.trace_json.is_empty() {
         return r#"{"error": "No trace data available. Please generate a trace first."}"#
@@ -63,7 +63,7 @@ pub async fn execute(state: &Mutex<AppData>) -> String {
     }
 
     // Build treemap data (already sorted desc by duration)
-    let treemap_nodes = match crate::treemap::build_treemap_from_trace(&app_data.trace_json) {
+    let treemap_nodes = match app_data.ensure_treemap() {
         Ok(nodes) => nodes,
         Err(e) => return format!("{{\"error\": \"Failed to build treemap data: {e}\"}}"),
     };
@@ -71,10 +71,10 @@ pub async fn execute(state: &Mutex<AppData>) -> String {
     let limit = 10usize; // keep stubbed for now; matches tool definition default
 
     let mut files: Vec<HotFileInfo> = treemap_nodes
-        .into_iter()
+        .iter()
         .take(limit)
         .map(|node| HotFileInfo {
-            path: node.path.unwrap_or_else(|| node.name.clone()),
+            path: node.path.clone().unwrap_or_else(|| node.name.clone()),
             duration_ms: node.value,
         })
         .collect();
@@ -1,3 +1,5 @@
+pub mod generate_trace;
+pub mod get_cpu_profile;
 pub mod get_depth_limits;
 pub mod get_duplicate_packages;
 pub mod get_hot_files;
@@ -13,6 +15,8 @@ pub fn get_tool_definitions() -> Vec<Value> {
         map_tool_definition(get_hot_types::tool_definition()),
         map_tool_definition(get_hot_files::tool_definition()),
         map_tool_definition(get_depth_limits::tool_definition()),
+        map_tool_definition(generate_trace::tool_definition()),
+        map_tool_definition(get_cpu_profile::tool_definition()),
     ];
     definitions.sort_by(|a, b| a.command.cmp(&b.command));
     definitions
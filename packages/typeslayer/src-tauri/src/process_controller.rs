@@ -1,18 +1,37 @@
 use std::{
+    collections::HashMap,
+    fmt,
     process::{ExitStatus, Stdio},
-    sync::Arc,
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
 };
 
+use serde::Serialize;
 use tokio::{
-    io::AsyncReadExt,
-    process::{ChildStderr, ChildStdout, Command},
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::Command,
     select,
-    sync::Notify,
 };
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
+use crate::{commands::tasks::TaskId, trace_progress::TraceProgress};
+
+/// How often a running command is checked against its timeout and memory limit
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Runs child processes and kills them on request, on timeout, or when they use too much memory.
+///
+/// Each running command is registered under the `TaskId` it belongs to, so canceling one task
+/// leaves the others alone.
 #[derive(Clone)]
 pub struct ProcessController {
-    cancel: Arc<Notify>,
+    /// The cancellation token of the run currently registered for each task, tagged with a run id
+    cancels: Arc<Mutex<HashMap<TaskId, (u64, CancellationToken)>>>,
+    next_run_id: Arc<AtomicU64>,
 }
 
 impl Default for ProcessController {
@@ -23,67 +42,257 @@ impl Default for ProcessController {
 
 pub struct CommandOutput {
     pub status: ExitStatus,
-    pub stdout: ChildStdout,
-    pub stderr: ChildStderr,
+    pub stdout: String,
+    pub stderr: String,
+    /// Set when the process was killed rather than exiting on its own
+    pub killed: Option<KillReason>,
+}
+
+/// Limits enforced while a command runs. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunLimits {
+    pub timeout: Option<Duration>,
+    /// Ceiling for the combined resident memory of the whole process tree (Unix only)
+    pub max_rss_mb: Option<u64>,
+}
+
+/// Why a command was killed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum KillReason {
+    Canceled,
+    TimedOut { timeout_secs: u64 },
+    OutOfMemory { rss_mb: u64, limit_mb: u64 },
+}
+
+impl fmt::Display for KillReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Canceled => write!(f, "canceled"),
+            Self::TimedOut { timeout_secs } => write!(f, "timed out after {timeout_secs}s"),
+            Self::OutOfMemory { rss_mb, limit_mb } => write!(
+                f,
+                "killed for exceeding the memory limit ({rss_mb} MB resident > {limit_mb} MB)"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Receives progress from a running command as it happens, so long tsc runs aren't silent until they exit.
+pub trait ProgressReporter: Send + Sync {
+    /// Called for every line the command writes, as soon as it's written.
+    fn output_line(&self, stream: OutputStream, line: &str);
+
+    /// Called periodically while a trace is being generated.
+    fn trace_progress(&self, _progress: &TraceProgress) {}
 }
 
 impl ProcessController {
     pub fn new() -> Self {
         Self {
-            cancel: Arc::new(Notify::new()),
+            cancels: Arc::new(Mutex::new(HashMap::new())),
+            next_run_id: Arc::new(AtomicU64::new(0)),
         }
     }
 
-    pub async fn run_command(&self, mut cmd: Command) -> Result<Option<CommandOutput>, String> {
-        let mut child = cmd
-            .stdin(Stdio::null())
+    /// Run a command to completion, forwarding each line of output to `reporter` while it runs.
+    ///
+    /// The command (and, on Unix, its whole process group) is killed if `task_id` is canceled or
+    /// `limits` are exceeded; `CommandOutput::killed` says which.
+    pub async fn run_command(
+        &self,
+        mut cmd: Command,
+        task_id: TaskId,
+        limits: RunLimits,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<CommandOutput, String> {
+        cmd.stdin(Stdio::null())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        // a fresh process group means `sh -c`, node and anything they spawn can be killed together
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        let mut child = cmd
             .spawn()
             .map_err(|e| format!("Failed to execute command: {e}"))?;
+        let pid = child.id();
 
         let stdout = child.stdout.take().expect("stdout to have been set");
         let stderr = child.stderr.take().expect("stderr to have been set");
 
-        select! {
-            result = child.wait() => {
-                Ok(Some(CommandOutput {
-                    status: result.map_err(|e| e.to_string())?,
-                    stdout,
-                    stderr
-                }))
+        let cancel = CancellationToken::new();
+        let run_id = self.next_run_id.fetch_add(1, Ordering::Relaxed);
+        self.lock_cancels()
+            .insert(task_id, (run_id, cancel.clone()));
+
+        // The pipes are drained while waiting, otherwise a chatty child blocks once the pipe buffer fills up.
+        // Killing the process closes the pipes, so this always completes and keeps whatever output was produced.
+        let run = async {
+            tokio::join!(
+                child.wait(),
+                read_lines(stdout, OutputStream::Stdout, reporter),
+                read_lines(stderr, OutputStream::Stderr, reporter),
+            )
+        };
+        tokio::pin!(run);
+
+        let started = Instant::now();
+        let mut watchdog = tokio::time::interval(WATCHDOG_INTERVAL);
+        let mut killed = None;
+
+        let (status, stdout, stderr) = loop {
+            select! {
+                result = &mut run => break result,
+                _ = cancel.cancelled(), if killed.is_none() => {
+                    killed = Some(KillReason::Canceled);
+                }
+                _ = watchdog.tick(), if killed.is_none() => {
+                    killed = check_limits(pid, started, limits).await;
+                }
             }
-            _ = self.cancel.notified() => {
-                child.kill().await.map_err(|e| e.to_string())?;
-                Ok(None)
+
+            if let Some(reason) = killed
+                && let Some(pid) = pid
+            {
+                info!("[run_command] killing {task_id:?} (pid {pid}): {reason}");
+                kill_process_tree(pid);
+            }
+        };
+
+        {
+            let mut cancels = self.lock_cancels();
+            // a newer run for the same task may have replaced this one
+            if cancels.get(&task_id).is_some_and(|(id, _)| *id == run_id) {
+                cancels.remove(&task_id);
             }
         }
+
+        Ok(CommandOutput {
+            status: status.map_err(|e| e.to_string())?,
+            stdout: stdout.map_err(|e| e.to_string())?,
+            stderr: stderr.map_err(|e| e.to_string())?,
+            killed,
+        })
     }
 
-    pub fn request_cancel(&self) -> Result<(), String> {
-        self.cancel.notify_waiters();
+    /// Cancel the command running for `task_id`, or every running command when `None`.
+    pub fn request_cancel(&self, task_id: Option<TaskId>) -> Result<(), String> {
+        let cancels = self.lock_cancels();
+        match task_id {
+            Some(task_id) => {
+                if let Some((_, cancel)) = cancels.get(&task_id) {
+                    cancel.cancel();
+                }
+            }
+            None => cancels.values().for_each(|(_, cancel)| cancel.cancel()),
+        }
 
         Ok(())
     }
+
+    fn lock_cancels(&self) -> MutexGuard<'_, HashMap<TaskId, (u64, CancellationToken)>> {
+        // the map stays consistent even if a holder panicked, so poisoning is safe to ignore
+        self.cancels.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
-pub async fn process_output(mut output: CommandOutput) -> Result<(String, String), String> {
-    let mut stdout = Vec::new();
-    output
-        .stdout
-        .read_to_end(&mut stdout)
-        .await
-        .map_err(|e| e.to_string())?;
+async fn check_limits(pid: Option<u32>, started: Instant, limits: RunLimits) -> Option<KillReason> {
+    if let Some(timeout) = limits.timeout
+        && started.elapsed() >= timeout
+    {
+        return Some(KillReason::TimedOut {
+            timeout_secs: timeout.as_secs(),
+        });
+    }
+
+    if let Some(limit_mb) = limits.max_rss_mb
+        && let Some(pid) = pid
+        && let Some(rss_mb) = process_group_rss_mb(pid).await
+        && rss_mb > limit_mb
+    {
+        return Some(KillReason::OutOfMemory { rss_mb, limit_mb });
+    }
+
+    None
+}
 
-    let mut stderr = Vec::new();
-    output
-        .stderr
-        .read_to_end(&mut stderr)
+/// Combined resident memory of every process in the group led by `pgid`
+#[cfg(unix)]
+async fn process_group_rss_mb(pgid: u32) -> Option<u64> {
+    let output = Command::new("ps")
+        .args(["-A", "-o", "pgid=,rss="])
+        .output()
         .await
-        .map_err(|e| e.to_string())?;
+        .ok()?;
+    let rss_kb: u64 = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split_whitespace();
+            let group = columns.next()?.parse::<u32>().ok()?;
+            let rss = columns.next()?.parse::<u64>().ok()?;
+            (group == pgid).then_some(rss)
+        })
+        .sum();
+    Some(rss_kb / 1024)
+}
+
+#[cfg(not(unix))]
+async fn process_group_rss_mb(_pgid: u32) -> Option<u64> {
+    None
+}
 
-    let stdout = String::from_utf8_lossy(&stdout).to_string();
-    let stderr = String::from_utf8_lossy(&stderr).to_string();
+/// Kill a process and everything it spawned
+fn kill_process_tree(pid: u32) {
+    #[cfg(unix)]
+    {
+        // the child leads its own process group, so a negative pid signals the whole group
+        if unsafe { libc::kill(-(pid as i32), libc::SIGKILL) } != 0 {
+            warn!(
+                "[kill_process_tree] failed to kill process group {pid}: {}",
+                std::io::Error::last_os_error()
+            );
+        }
+    }
 
-    Ok((stdout, stderr))
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        if let Err(e) = std::process::Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &pid.to_string()])
+            .creation_flags(0x08000000)
+            .status()
+        {
+            warn!("[kill_process_tree] failed to kill process tree {pid}: {e}");
+        }
+    }
+}
+
+/// Read `reader` line by line, reporting each line and returning everything that was read.
+async fn read_lines(
+    reader: impl AsyncRead + Unpin,
+    stream: OutputStream,
+    reporter: &dyn ProgressReporter,
+) -> std::io::Result<String> {
+    let mut reader = BufReader::new(reader);
+    let mut collected = String::new();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf).await? == 0 {
+            break;
+        }
+        let line = String::from_utf8_lossy(&buf);
+        reporter.output_line(stream, line.trim_end_matches(['\r', '\n']));
+        collected.push_str(&line);
+    }
+    Ok(collected)
 }
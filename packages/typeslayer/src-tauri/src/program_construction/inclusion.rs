@@ -0,0 +1,174 @@
+use crate::{
+    program_construction::{IncludedFile, InclusionEdge, InclusionExplanation, InclusionGraph},
+    validate::trace_json::TraceEvent,
+};
+use indexmap::{IndexMap, IndexSet};
+
+/// `fileIncludeKind`s of files the program includes without another file referencing them
+const UNREFERENCED_INCLUDE_KINDS: [&str; 3] = ["RootFile", "LibFile", "AutomaticTypeDirectiveFile"];
+
+enum Frame<'a> {
+    FindSourceFile(&'a str),
+    TypeReferenceDirective(&'a str),
+}
+
+/// Build the graph of which files reference which.
+///
+/// tsc processes a file's imports, references and type reference directives inside its
+/// `findSourceFile`, so the `findSourceFile` of a file is nested in the one of the file
+/// that referenced it, and a `processTypeReferenceDirective` in between names the directive.
+pub fn create_inclusion_graph(trace: &[TraceEvent]) -> InclusionGraph {
+    let mut root_files = 0;
+    let mut resolution_times: IndexMap<&str, f64> = IndexMap::new();
+    let mut spans: Vec<(f64, f64, Frame, Option<&str>)> = Vec::new();
+    for event in trace {
+        let start = event.common().ts;
+        match event {
+            TraceEvent::FindSourceFile { dur, args, .. } => spans.push((
+                start,
+                start + dur,
+                Frame::FindSourceFile(&args.file_name),
+                Some(&args.file_include_kind),
+            )),
+            TraceEvent::ProcessTypeReferenceDirective { dur, args, .. } => spans.push((
+                start,
+                start + dur,
+                Frame::TypeReferenceDirective(&args.directive),
+                None,
+            )),
+            TraceEvent::ResolveModuleNamesWorker { dur, args, .. }
+            | TraceEvent::ResolveTypeReferenceDirectiveNamesWorker { dur, args, .. } => {
+                *resolution_times
+                    .entry(&args.containing_file_name)
+                    .or_default() += dur / 1000.0;
+            }
+            TraceEvent::ProcessRootFiles { args, .. } => root_files += args.count,
+            _ => {}
+        }
+    }
+    // outer spans first when they start together
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0).then(b.1.total_cmp(&a.1)));
+
+    let mut files: IndexMap<&str, IncludedFile> = IndexMap::new();
+    let mut edges: IndexMap<(&str, &str), &str> = IndexMap::new();
+    let mut stack: Vec<(f64, &Frame)> = Vec::new();
+    for (start, end, frame, include_kind) in &spans {
+        while stack.last().is_some_and(|(open_end, _)| open_end <= start) {
+            stack.pop();
+        }
+        if let (Frame::FindSourceFile(file), Some(include_kind)) = (frame, include_kind) {
+            let mut directive = None;
+            let mut referenced_by = None;
+            for (_, open) in stack.iter().rev() {
+                match open {
+                    Frame::TypeReferenceDirective(name) => {
+                        directive.get_or_insert(*name);
+                    }
+                    Frame::FindSourceFile(parent) => {
+                        referenced_by = Some(*parent);
+                        break;
+                    }
+                }
+            }
+            if UNREFERENCED_INCLUDE_KINDS.contains(include_kind) {
+                referenced_by = None;
+            }
+            if let Some(from) = referenced_by
+                && from != *file
+            {
+                edges.entry((from, file)).or_insert(include_kind);
+            }
+            files.entry(file).or_insert_with(|| IncludedFile {
+                file: file.to_string(),
+                reason: include_kind.to_string(),
+                referenced_by: referenced_by.map(str::to_string),
+                directive: directive.map(str::to_string),
+                referencing_files: 0,
+                pulled_in: 0,
+                resolution_time: resolution_times.get(file).copied().unwrap_or(0.0),
+            });
+        }
+        stack.push((*end, frame));
+    }
+
+    for (_, to) in edges.keys() {
+        if let Some(file) = files.get_mut(to) {
+            file.referencing_files += 1;
+        }
+    }
+    // files are found after whatever referenced them first, so children come before parents
+    // walking backwards
+    for i in (0..files.len()).rev() {
+        let pulled_in = files[i].pulled_in;
+        let parent = files[i]
+            .referenced_by
+            .as_deref()
+            .and_then(|parent| files.get_index_of(parent));
+        if let Some(parent) = parent.filter(|parent| *parent < i) {
+            files[parent].pulled_in += pulled_in + 1;
+        }
+    }
+
+    InclusionGraph {
+        root_files,
+        files: files.into_values().collect(),
+        edges: edges
+            .into_iter()
+            .map(|((from, to), reason)| InclusionEdge {
+                from: from.to_string(),
+                to: to.to_string(),
+                reason: reason.to_string(),
+            })
+            .collect(),
+    }
+}
+
+/// Why `file` is in the program: what referenced it first, and so on back to the tsconfig.
+/// `file` can be the end of a path, e.g. `node_modules/@types/node/index.d.ts`.
+pub fn explain_inclusion(
+    graph: &InclusionGraph,
+    file: &str,
+) -> Result<InclusionExplanation, String> {
+    let normalized = file.replace('\\', "/");
+    let index = graph
+        .files
+        .iter()
+        .position(|included| included.file == normalized)
+        .or_else(|| {
+            graph.files.iter().position(|included| {
+                included
+                    .file
+                    .to_lowercase()
+                    .ends_with(&normalized.to_lowercase())
+            })
+        })
+        .ok_or_else(|| format!("{file} isn't in the program"))?;
+
+    let by_name: IndexMap<&str, &IncludedFile> = graph
+        .files
+        .iter()
+        .map(|included| (included.file.as_str(), included))
+        .collect();
+    let included = &graph.files[index];
+    let mut chain = Vec::new();
+    let mut seen: IndexSet<&str> = IndexSet::from([included.file.as_str()]);
+    let mut next = included.referenced_by.as_deref();
+    while let Some(parent) = next.and_then(|parent| by_name.get(parent)) {
+        if !seen.insert(&parent.file) {
+            break;
+        }
+        chain.push((*parent).clone());
+        next = parent.referenced_by.as_deref();
+    }
+
+    Ok(InclusionExplanation {
+        file: included.clone(),
+        chain,
+        referenced_by: graph
+            .edges
+            .iter()
+            .filter(|edge| edge.to == included.file)
+            .map(|edge| edge.from.clone())
+            .collect(),
+    })
+}
@@ -0,0 +1,241 @@
+pub mod inclusion;
+pub mod types;
+
+pub use types::*;
+
+use crate::{
+    analyze_trace::{EventSpanEvent, create_spans, package_name},
+    validate::trace_json::TraceEvent,
+};
+use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
+
+/// `fileIncludeKind`s of files found through type reference directives
+const TYPE_REFERENCE_INCLUDE_KINDS: [&str; 2] =
+    ["TypeReferenceDirective", "AutomaticTypeDirectiveFile"];
+
+/// `fileIncludeKind`s of `lib` files
+const LIBRARY_INCLUDE_KINDS: [&str; 2] = ["LibFile", "LibReferenceDirective"];
+
+#[derive(Default)]
+struct FileEntry {
+    include_kind: Option<String>,
+    times: PhaseTimes,
+}
+
+/// The path as written for each `bindSourceFile` path that isn't. `bindSourceFile` gives a
+/// file's canonical path, which is lower-cased on case-insensitive file systems, while the other
+/// events give it as written. Paths that differ only in case can be different files, so only an
+/// all lower-case canonical path is mapped, and only onto the one written path it lower-cases.
+fn written_paths(trace: &[TraceEvent]) -> HashMap<&str, &str> {
+    let mut written: HashSet<&str> = HashSet::new();
+    for event in trace {
+        match event {
+            TraceEvent::CreateSourceFile { args, .. } => written.insert(&args.path),
+            TraceEvent::ResolveModuleNamesWorker { args, .. }
+            | TraceEvent::ResolveTypeReferenceDirectiveNamesWorker { args, .. } => {
+                written.insert(&args.containing_file_name)
+            }
+            TraceEvent::FindSourceFile { args, .. } => written.insert(&args.file_name),
+            _ => false,
+        };
+    }
+
+    // `None` when more than one written path lower-cases the same
+    let mut by_lowercase: HashMap<String, Option<&str>> = HashMap::new();
+    for &path in &written {
+        by_lowercase
+            .entry(path.to_lowercase())
+            .and_modify(|only| *only = None)
+            .or_insert(Some(path));
+    }
+
+    trace
+        .iter()
+        .filter_map(|event| match event {
+            TraceEvent::BindSourceFile { args, .. } => Some(args.path.as_str()),
+            _ => None,
+        })
+        .filter(|path| !written.contains(path) && *path == path.to_lowercase())
+        .filter_map(|path| Some((path, by_lowercase.get(path).copied().flatten()?)))
+        .collect()
+}
+
+#[derive(Default)]
+struct DirectiveEntry {
+    count: usize,
+    resolved: bool,
+    time: f64,
+}
+
+/// Parse, bind and module resolution time per file and per package, and what pulled files
+/// into the program. `limit` caps the files and packages listed.
+pub fn create_program_construction_report(
+    trace: &[TraceEvent],
+    limit: usize,
+) -> Result<ProgramConstructionReport, String> {
+    let parse_result = create_spans(trace)?;
+
+    let mut programs = Vec::new();
+    let mut files: IndexMap<String, FileEntry> = IndexMap::new();
+    let written = written_paths(trace);
+    let mut root_files = 0;
+    let mut directives: IndexMap<String, DirectiveEntry> = IndexMap::new();
+    let mut unresolved_type_reference_directives = 0;
+    let mut resolve_library_count = 0;
+    let mut resolve_library_time = 0.0;
+
+    for span in &parse_result.spans {
+        let EventSpanEvent::TraceEvent(event) = &span.event else {
+            continue;
+        };
+        let duration = span.duration / 1000.0;
+        match event {
+            TraceEvent::CreateProgram { args, .. } => programs.push(ProgramTiming {
+                config_file_path: args.config_file_path.clone(),
+                duration,
+            }),
+            TraceEvent::CreateSourceFile { args, .. } => {
+                files.entry(args.path.clone()).or_default().times.parse += duration;
+            }
+            TraceEvent::BindSourceFile { args, .. } => {
+                let path = written.get(args.path.as_str()).copied();
+                let path = path.unwrap_or(&args.path).to_string();
+                files.entry(path).or_default().times.bind += duration;
+            }
+            TraceEvent::ResolveModuleNamesWorker { args, .. }
+            | TraceEvent::ResolveTypeReferenceDirectiveNamesWorker { args, .. } => {
+                files
+                    .entry(args.containing_file_name.clone())
+                    .or_default()
+                    .times
+                    .resolve += duration;
+            }
+            TraceEvent::ProcessRootFiles { args, .. } => root_files += args.count,
+            TraceEvent::ProcessTypeReferenceDirective { args, .. } => {
+                let directive = directives.entry(args.directive.clone()).or_default();
+                directive.count += 1;
+                directive.resolved |= args.has_resolved;
+                directive.time += duration;
+                if !args.has_resolved {
+                    unresolved_type_reference_directives += 1;
+                }
+            }
+            TraceEvent::ResolveLibrary { .. } => {
+                resolve_library_count += 1;
+                resolve_library_time += duration;
+            }
+            _ => {}
+        }
+    }
+
+    // `findSourceFile` is written when it finishes, after the files it pulled in, so the
+    // earliest start is what first included each file
+    let mut found: IndexMap<String, (f64, &str)> = IndexMap::new();
+    for event in trace {
+        if let TraceEvent::FindSourceFile { common, args, .. } = event {
+            let first = found
+                .entry(args.file_name.clone())
+                .or_insert((common.ts, &args.file_include_kind));
+            if common.ts < first.0 {
+                *first = (common.ts, &args.file_include_kind);
+            }
+        }
+    }
+    for (file_name, (_, include_kind)) in found {
+        files.entry(file_name).or_default().include_kind = Some(include_kind.to_string());
+    }
+
+    let mut totals = PhaseTimes::default();
+    let mut project = PhaseTimes::default();
+    let mut packages: IndexMap<String, PackageProgramCost> = IndexMap::new();
+    let mut include_kinds: IndexMap<String, usize> = IndexMap::new();
+    let mut files: Vec<FileProgramCost> = files
+        .into_iter()
+        .map(|(path, mut entry)| {
+            let times = &mut entry.times;
+            times.total = times.parse + times.bind + times.resolve;
+            totals.add(times);
+            let package = package_name(&path).map(str::to_string);
+            match &package {
+                Some(package) => {
+                    let cost =
+                        packages
+                            .entry(package.clone())
+                            .or_insert_with(|| PackageProgramCost {
+                                package: package.clone(),
+                                files: 0,
+                                times: PhaseTimes::default(),
+                            });
+                    cost.files += 1;
+                    cost.times.add(times);
+                }
+                None => project.add(times),
+            }
+            if let Some(include_kind) = &entry.include_kind {
+                *include_kinds.entry(include_kind.clone()).or_default() += 1;
+            }
+            FileProgramCost {
+                path,
+                package,
+                include_kind: entry.include_kind,
+                times: entry.times,
+            }
+        })
+        .collect();
+    let file_count = files.len();
+    files.sort_by(|a, b| b.times.total.total_cmp(&a.times.total));
+    files.truncate(limit);
+
+    let mut packages: Vec<PackageProgramCost> = packages.into_values().collect();
+    packages.sort_by(|a, b| b.times.total.total_cmp(&a.times.total));
+    packages.truncate(limit);
+
+    let files_included_by = |kinds: &[&str]| {
+        kinds
+            .iter()
+            .filter_map(|kind| include_kinds.get(*kind))
+            .sum::<usize>()
+    };
+    let type_reference_directive_files = files_included_by(&TYPE_REFERENCE_INCLUDE_KINDS);
+    let library_files = files_included_by(&LIBRARY_INCLUDE_KINDS);
+    let mut include_kinds: Vec<IncludeKindCount> = include_kinds
+        .into_iter()
+        .map(|(include_kind, files)| IncludeKindCount {
+            include_kind,
+            files,
+        })
+        .collect();
+    include_kinds.sort_by_key(|kind| std::cmp::Reverse(kind.files));
+
+    let mut type_reference_directives: Vec<TypeReferenceDirectiveCount> = directives
+        .into_iter()
+        .map(|(directive, entry)| TypeReferenceDirectiveCount {
+            directive,
+            count: entry.count,
+            resolved: entry.resolved,
+            time: entry.time,
+        })
+        .collect();
+    type_reference_directives.sort_by(|a, b| b.time.total_cmp(&a.time));
+
+    Ok(ProgramConstructionReport {
+        create_program: programs
+            .iter()
+            .fold(0.0, |sum, program| sum + program.duration),
+        programs,
+        totals,
+        project,
+        file_count,
+        root_files,
+        files,
+        packages,
+        include_kinds,
+        type_reference_directive_files,
+        library_files,
+        type_reference_directives,
+        unresolved_type_reference_directives,
+        resolve_library_count,
+        resolve_library_time,
+    })
+}
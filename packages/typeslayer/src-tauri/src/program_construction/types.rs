@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+
+/// Time spent building the program, by phase. Times are in milliseconds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTimes {
+    /// `createSourceFile`
+    pub parse: f64,
+    /// `bindSourceFile`, which runs when the checker is created, after `createProgram`
+    pub bind: f64,
+    /// `resolveModuleNamesWorker` and `resolveTypeReferenceDirectiveNamesWorker`, for the
+    /// imports and `/// <reference types>` of a file
+    pub resolve: f64,
+    pub total: f64,
+}
+
+impl PhaseTimes {
+    pub fn add(&mut self, other: &PhaseTimes) {
+        self.parse += other.parse;
+        self.bind += other.bind;
+        self.resolve += other.resolve;
+        self.total += other.total;
+    }
+}
+
+/// One `createProgram`. `tsc --build` makes one per project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramTiming {
+    pub config_file_path: String,
+    /// Milliseconds
+    pub duration: f64,
+}
+
+/// What building the program cost for one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileProgramCost {
+    pub path: String,
+    /// `None` outside `node_modules`
+    pub package: Option<String>,
+    /// Why the file was first found, e.g. `Import` or `TypeReferenceDirective`
+    pub include_kind: Option<String>,
+    pub times: PhaseTimes,
+}
+
+/// What building the program cost for the files of one `node_modules` package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageProgramCost {
+    pub package: String,
+    pub files: usize,
+    pub times: PhaseTimes,
+}
+
+/// How many files were first found for one reason, e.g. `RootFile` or `Import`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncludeKindCount {
+    pub include_kind: String,
+    pub files: usize,
+}
+
+/// `processTypeReferenceDirective` runs for one directive, e.g. `node` from `"types": ["node"]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeReferenceDirectiveCount {
+    pub directive: String,
+    pub count: usize,
+    /// Whether it ever resolved to a file
+    pub resolved: bool,
+    /// Milliseconds, including the files it pulled in
+    pub time: f64,
+}
+
+/// Where the time before checking goes: parsing, binding and resolving modules, per file and
+/// per `node_modules` package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramConstructionReport {
+    pub programs: Vec<ProgramTiming>,
+    /// Milliseconds in `createProgram`, over every program
+    pub create_program: f64,
+    /// Over every file
+    pub totals: PhaseTimes,
+    /// Over the files outside `node_modules`
+    pub project: PhaseTimes,
+    pub file_count: usize,
+    /// Files named in the tsconfig or on the command line
+    pub root_files: u64,
+    /// Most total time first
+    pub files: Vec<FileProgramCost>,
+    /// Most total time first
+    pub packages: Vec<PackageProgramCost>,
+    /// Most files first
+    pub include_kinds: Vec<IncludeKindCount>,
+    /// Files first found through a `/// <reference types>`, `"types"` or `@types` directive
+    pub type_reference_directive_files: usize,
+    /// Files first found as a `lib` file or through a `/// <reference lib>`
+    pub library_files: usize,
+    /// Most time first
+    pub type_reference_directives: Vec<TypeReferenceDirectiveCount>,
+    pub unresolved_type_reference_directives: usize,
+    /// `resolveLibrary` runs, for `lib` replacements like `@typescript/lib-dom`
+    pub resolve_library_count: usize,
+    /// Milliseconds in `resolveLibrary`
+    pub resolve_library_time: f64,
+}
+
+/// How a file got into the program: the first time it was found.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncludedFile {
+    pub file: String,
+    /// The `fileIncludeKind` it was first found as, e.g. `RootFile`, `Import`,
+    /// `TypeReferenceDirective` or `LibFile`
+    pub reason: String,
+    /// The file that referenced it first. `None` for root files, default lib files and
+    /// automatic type directives, which the tsconfig pulls in.
+    pub referenced_by: Option<String>,
+    /// The type reference directive that found it, e.g. `node`
+    pub directive: Option<String>,
+    /// Files that reference it
+    pub referencing_files: usize,
+    /// Files first found through it, directly or not
+    pub pulled_in: usize,
+    /// Milliseconds resolving its own imports and type reference directives
+    pub resolution_time: f64,
+}
+
+/// A file referencing another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionEdge {
+    pub from: String,
+    pub to: String,
+    pub reason: String,
+}
+
+/// Which files reference which, from the `findSourceFile` spans nested in each other while
+/// the program was built.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionGraph {
+    /// Files named in the tsconfig or on the command line
+    pub root_files: u64,
+    /// In the order they were found
+    pub files: Vec<IncludedFile>,
+    pub edges: Vec<InclusionEdge>,
+}
+
+/// Why a file is in the program.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionExplanation {
+    pub file: IncludedFile,
+    /// From the file that referenced it first back to what the tsconfig included
+    pub chain: Vec<IncludedFile>,
+    /// Every file that references it
+    pub referenced_by: Vec<String>,
+}
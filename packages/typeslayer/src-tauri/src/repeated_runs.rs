@@ -0,0 +1,299 @@
+use crate::{
+    analyze_trace::{HotSpot, analyze_trace},
+    app_data::{AppData, init::generate_session_id},
+    commands::tasks::TaskId,
+    process_controller::{OutputStream, ProcessController, ProgressReporter},
+    stats::SampleStats,
+    treemap::build_treemap_from_trace,
+    utils::make_cli_arg,
+    validate::trace_json::{TRACE_JSON_FILENAME, load_trace_json},
+};
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, time::Instant};
+use tokio::{fs, sync::Mutex};
+use tracing::{info, warn};
+
+pub const REPEATED_RUNS_FILENAME: &str = "repeated-runs.json";
+
+/// More than this and a run on a large project takes all day.
+const MAX_RUNS: usize = 50;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepeatedRunsRequest {
+    /// Measured runs
+    pub runs: usize,
+    /// Runs made first and thrown away, to warm the disk cache and the JIT
+    #[serde(default)]
+    pub warmup: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRunStats {
+    pub path: String,
+    pub check_time: SampleStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotSpotRunStats {
+    pub description: String,
+    pub path: Option<PathBuf>,
+    pub start_line: Option<i64>,
+    pub start_char: Option<i64>,
+    /// Only the runs where this hot spot was over the analysis threshold contribute samples.
+    /// There's no confidence interval unless that's every run, since the missing runs are the
+    /// fast ones and leaving them out biases it upward.
+    pub duration: SampleStats,
+    /// Runs this hot spot was over the analysis threshold in. 0 in results saved before it
+    /// was recorded.
+    #[serde(default)]
+    pub appeared_in: usize,
+}
+
+/// Timings aggregated over every measured run. Times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepeatedRunsResult {
+    pub created_at: DateTime<Utc>,
+    pub runs: usize,
+    pub warmup: usize,
+    pub extra_tsc_flags: String,
+    pub wall_time: SampleStats,
+    /// Sum of every `checkSourceFile` span
+    pub total_check_time: SampleStats,
+    /// Slowest first, by mean
+    pub files: Vec<FileRunStats>,
+    /// Slowest first, by mean
+    pub hot_spots: Vec<HotSpotRunStats>,
+}
+
+/// A hot spot's source location identifies it across runs; ids and timestamps aren't stable.
+type HotSpotKey = (String, Option<PathBuf>, Option<i64>);
+
+/// One measured run, before aggregation.
+struct RunSample {
+    wall_time: f64,
+    files: Vec<(String, f64)>,
+    hot_spots: Vec<HotSpot>,
+}
+
+/// Run tsc with `--generateTrace` `warmup + runs` times and summarize the spread of every timing.
+///
+/// Runs happen in a scratch directory with a detached copy of the settings, so the app stays
+/// usable and the loaded trace isn't replaced. The result is written to the workspace outputs.
+pub async fn run_repeated(
+    app_data: &'static Mutex<AppData>,
+    process_controller: &ProcessController,
+    request: RepeatedRunsRequest,
+    reporter: &dyn ProgressReporter,
+) -> Result<RepeatedRunsResult, String> {
+    if request.runs == 0 || request.runs > MAX_RUNS {
+        return Err(format!("runs must be between 1 and {MAX_RUNS}"));
+    }
+    if request.warmup > MAX_RUNS {
+        return Err(format!("warmup must be at most {MAX_RUNS}"));
+    }
+
+    let scratch = std::env::temp_dir().join(format!("typeslayer-runs-{}", generate_session_id()));
+    let (probe, outputs_dir) = {
+        let app_data = app_data.lock().await;
+        (
+            app_data.detached(app_data.project_root.clone(), scratch.clone()),
+            app_data.outputs_dir(),
+        )
+    };
+
+    let created_at = Utc::now();
+    let mut samples = Vec::with_capacity(request.runs);
+    let mut failure = None;
+    for index in 0..request.warmup + request.runs {
+        let warmup = index < request.warmup;
+        let message = if warmup {
+            format!("[runs] warmup {}/{}", index + 1, request.warmup)
+        } else {
+            format!("[runs] run {}/{}", index - request.warmup + 1, request.runs)
+        };
+        info!("{message}");
+        reporter.output_line(OutputStream::Stdout, &message);
+
+        match run_once(&probe, process_controller, reporter, !warmup).await {
+            Ok(Some(sample)) => samples.push(sample),
+            Ok(None) => {}
+            Err(e) => {
+                failure = Some(e);
+                break;
+            }
+        }
+    }
+
+    if let Err(e) = fs::remove_dir_all(&scratch).await {
+        warn!("[runs] failed to remove {}: {e}", scratch.display());
+    }
+    if let Some(e) = failure {
+        return Err(e);
+    }
+
+    let result = aggregate(
+        created_at,
+        &request,
+        probe.settings.extra_tsc_flags.clone(),
+        samples,
+    );
+    let json = serde_json::to_string_pretty(&result)
+        .map_err(|e| format!("Failed to serialize repeated runs: {e}"))?;
+    fs::create_dir_all(&outputs_dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {e}", outputs_dir.display()))?;
+    fs::write(outputs_dir.join(REPEATED_RUNS_FILENAME), json)
+        .await
+        .map_err(|e| format!("Failed to write repeated runs: {e}"))?;
+    Ok(result)
+}
+
+/// Run tsc once. Returns `None` without measuring anything when `measure` is false (a warmup run).
+async fn run_once(
+    probe: &AppData,
+    process_controller: &ProcessController,
+    reporter: &dyn ProgressReporter,
+    measure: bool,
+) -> Result<Option<RunSample>, String> {
+    let outputs_dir = probe.outputs_dir();
+    if outputs_dir.exists() {
+        fs::remove_dir_all(&outputs_dir)
+            .await
+            .map_err(|e| format!("Failed to clear {}: {e}", outputs_dir.display()))?;
+    }
+
+    let start = Instant::now();
+    let flag = make_cli_arg("--generateTrace", &outputs_dir.to_string_lossy());
+    let output = probe
+        .call_typescript(process_controller, TaskId::RepeatedRuns, flag, reporter)
+        .await?;
+    let wall_time = start.elapsed().as_secs_f64() * 1000.0;
+    if let Some(reason) = output.killed {
+        return Err(format!("tsc {reason}"));
+    }
+    let trace_path = outputs_dir.join(TRACE_JSON_FILENAME.trim_start_matches('/'));
+    if !trace_path.exists() {
+        return Err(format!("tsc produced no trace:\n{}", output.stderr));
+    }
+    if !measure {
+        return Ok(None);
+    }
+
+    let (trace, _) = load_trace_json(trace_path).await?;
+    let files = build_treemap_from_trace(&trace)?
+        .into_iter()
+        .map(|node| (node.path.unwrap_or(node.name), node.value / 1000.0))
+        .collect();
+    drop(trace);
+
+    let trace_dir = outputs_dir.to_string_lossy().to_string();
+    let thresholds = probe.thresholds.clone();
+    let analysis =
+        tauri::async_runtime::spawn_blocking(move || analyze_trace(&trace_dir, Some(thresholds)))
+            .await
+            .map_err(|e| e.to_string())??;
+
+    Ok(Some(RunSample {
+        wall_time,
+        files,
+        hot_spots: analysis.hot_spots,
+    }))
+}
+
+fn aggregate(
+    created_at: DateTime<Utc>,
+    request: &RepeatedRunsRequest,
+    extra_tsc_flags: String,
+    samples: Vec<RunSample>,
+) -> RepeatedRunsResult {
+    let runs = samples.len();
+    let mut wall_times = Vec::with_capacity(samples.len());
+    let mut total_check_times = Vec::with_capacity(samples.len());
+    // a file that isn't checked in some run (it shouldn't happen) just has fewer samples
+    let mut files: IndexMap<String, Vec<f64>> = IndexMap::new();
+    let mut hot_spots: IndexMap<HotSpotKey, (HotSpot, Vec<f64>)> = IndexMap::new();
+
+    for sample in samples {
+        wall_times.push(sample.wall_time);
+        total_check_times.push(sample.files.iter().map(|(_, time)| time).sum());
+        for (path, time) in sample.files {
+            files.entry(path).or_default().push(time);
+        }
+
+        let mut stack = sample.hot_spots;
+        while let Some(mut hot_spot) = stack.pop() {
+            stack.append(&mut hot_spot.children);
+            let key = (
+                hot_spot.description.clone(),
+                hot_spot.path.clone(),
+                hot_spot.start_offset,
+            );
+            let duration = hot_spot.duration / 1000.0;
+            hot_spots
+                .entry(key)
+                .or_insert_with(|| (hot_spot, Vec::new()))
+                .1
+                .push(duration);
+        }
+    }
+
+    let mut files: Vec<FileRunStats> = files
+        .into_iter()
+        .map(|(path, samples)| FileRunStats {
+            path,
+            check_time: SampleStats::from_samples(samples),
+        })
+        .collect();
+    files.sort_by(|a, b| b.check_time.mean.total_cmp(&a.check_time.mean));
+
+    let mut hot_spots: Vec<HotSpotRunStats> = hot_spots
+        .into_values()
+        .map(|(hot_spot, samples)| {
+            let appeared_in = samples.len();
+            let mut duration = SampleStats::from_samples(samples);
+            if appeared_in < runs {
+                duration.ci_low = None;
+                duration.ci_high = None;
+            }
+            HotSpotRunStats {
+                description: hot_spot.description,
+                path: hot_spot.path,
+                start_line: hot_spot.start_line,
+                start_char: hot_spot.start_char,
+                duration,
+                appeared_in,
+            }
+        })
+        .collect();
+    hot_spots.sort_by(|a, b| b.duration.mean.total_cmp(&a.duration.mean));
+
+    RepeatedRunsResult {
+        created_at,
+        runs: request.runs,
+        warmup: request.warmup,
+        extra_tsc_flags,
+        wall_time: SampleStats::from_samples(wall_times),
+        total_check_time: SampleStats::from_samples(total_check_times),
+        files,
+        hot_spots,
+    }
+}
+
+/// The last repeated-runs result for the workspace, if there is one.
+pub async fn load_repeated_runs(app_data: &AppData) -> Option<RepeatedRunsResult> {
+    let path = app_data.outputs_dir().join(REPEATED_RUNS_FILENAME);
+    let contents = fs::read_to_string(&path).await.ok()?;
+    match serde_json::from_str(&contents) {
+        Ok(result) => Some(result),
+        Err(e) => {
+            warn!("[runs] failed to parse {}: {e}", path.display());
+            None
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::app_data::AppData;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, process::Stdio, time::Duration};
+use tracing::{debug, warn};
+
+pub const RUN_METADATA_FILENAME: &str = "run-metadata.json";
+
+/// `tsc --version` only has to start the compiler, so anything slower than this is stuck
+const TSC_VERSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The tsc run the outputs came from, so a trace and CPU profile can be told apart from ones
+/// that were generated separately.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunMetadata {
+    pub started_at: DateTime<Utc>,
+    /// Milliseconds from starting tsc until it exited
+    pub duration: u64,
+    /// e.g. `5.9.2`. `None` when `tsc --version` failed.
+    pub tsc_version: Option<String>,
+    pub compiler_variant: String,
+    /// The flags TypeSlayer asked for, e.g. `--generateTrace <dir>`
+    pub flags: Vec<String>,
+    pub extra_tsc_flags: String,
+    pub tsconfig: Option<PathBuf>,
+    pub max_old_space_size: Option<i32>,
+    pub max_stack_size: Option<i32>,
+    /// The whole command line, as run
+    pub command: String,
+    /// The files this run wrote, e.g. `trace.json` and `tsc.cpuprofile`
+    pub outputs: Vec<String>,
+}
+
+impl RunMetadata {
+    /// Describe a run of `flags` starting now, to be finished with [`RunMetadata::finish`].
+    pub async fn start(app_data: &AppData, flags: Vec<String>, outputs: Vec<String>) -> Self {
+        let tsc_version = tsc_version(app_data).await;
+        let settings = &app_data.settings;
+        Self {
+            started_at: Utc::now(),
+            duration: 0,
+            tsc_version,
+            compiler_variant: settings.typescript_compiler_variant.as_str().to_string(),
+            command: app_data.get_tsc_call(&flags.join(" ")).to_string(),
+            flags,
+            extra_tsc_flags: settings.extra_tsc_flags.clone(),
+            tsconfig: app_data
+                .selected_tsconfig
+                .clone()
+                .filter(|_| settings.apply_tsc_project_flag),
+            max_old_space_size: settings.max_old_space_size,
+            max_stack_size: settings.max_stack_size,
+            outputs,
+        }
+    }
+
+    /// Record how long the run took, given when tsc `exited_at`, write it next to its outputs,
+    /// and keep it in `app_data`. Failing to write it doesn't fail the run.
+    pub async fn finish(mut self, app_data: &mut AppData, exited_at: DateTime<Utc>) {
+        self.duration = (exited_at - self.started_at).num_milliseconds().max(0) as u64;
+        let path = app_data.outputs_dir().join(RUN_METADATA_FILENAME);
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => {
+                if let Err(e) = tokio::fs::write(&path, json).await {
+                    warn!("[run_metadata] failed to write {}: {e}", path.display());
+                }
+            }
+            Err(e) => warn!("[run_metadata] failed to serialize run metadata: {e}"),
+        }
+        app_data.run_metadata = Some(self);
+    }
+
+    /// Whether this run wrote `filename`
+    pub fn wrote(&self, filename: &str) -> bool {
+        let filename = filename.trim_start_matches('/');
+        self.outputs
+            .iter()
+            .any(|output| output.trim_start_matches('/') == filename)
+    }
+}
+
+/// The compiler's version, as `tsc --version` reports it with the configured package manager.
+pub async fn tsc_version(app_data: &AppData) -> Option<String> {
+    let mut cmd = app_data
+        .get_tsc_call("--version")
+        .to_command(&app_data.project_root);
+    cmd.stdin(Stdio::null()).kill_on_drop(true);
+    let output = match tokio::time::timeout(TSC_VERSION_TIMEOUT, cmd.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => {
+            warn!("[tsc_version] failed to run tsc --version: {e}");
+            return None;
+        }
+        Err(_) => {
+            warn!("[tsc_version] tsc --version timed out");
+            return None;
+        }
+    };
+    let version = parse_tsc_version(&String::from_utf8_lossy(&output.stdout));
+    debug!("[tsc_version] {version:?}");
+    version
+}
+
+/// tsc prints `Version 5.9.2`
+fn parse_tsc_version(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Version "))
+        .map(|version| version.trim().to_string())
+}
@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+
+/// Two-sided 95% critical values of Student's t distribution for 1 to 30 degrees of freedom.
+const T_CRITICAL_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// The 95% critical value for `degrees_of_freedom`, falling back to the normal distribution past 30.
+fn t_critical_95(degrees_of_freedom: f64) -> f64 {
+    if !degrees_of_freedom.is_finite() || degrees_of_freedom < 1.0 {
+        return f64::INFINITY;
+    }
+    // rounding down is conservative: fewer degrees of freedom means a wider interval
+    T_CRITICAL_95
+        .get(degrees_of_freedom.floor() as usize - 1)
+        .copied()
+        .unwrap_or(1.960)
+}
+
+/// Summary of repeated measurements of one quantity.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SampleStats {
+    pub samples: Vec<f64>,
+    pub mean: f64,
+    pub median: f64,
+    /// Sample standard deviation (n - 1); 0 for a single sample
+    pub stddev: f64,
+    /// 95% confidence interval of the mean. `None` for a single sample, which has no spread to go on.
+    pub ci_low: Option<f64>,
+    pub ci_high: Option<f64>,
+}
+
+impl SampleStats {
+    pub fn from_samples(samples: Vec<f64>) -> Self {
+        let n = samples.len() as f64;
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mean = samples.iter().sum::<f64>() / n;
+        let mut sorted = samples.clone();
+        sorted.sort_by(f64::total_cmp);
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2.0
+        } else {
+            sorted[middle]
+        };
+
+        let stddev = if samples.len() > 1 {
+            (samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let margin = t_critical_95(n - 1.0) * stddev / n.sqrt();
+        let (ci_low, ci_high) = if samples.len() > 1 {
+            (Some(mean - margin), Some(mean + margin))
+        } else {
+            (None, None)
+        };
+
+        Self {
+            samples,
+            mean,
+            median,
+            stddev,
+            ci_low,
+            ci_high,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether the whole confidence interval is above `threshold`, i.e. it's exceeded beyond noise.
+    /// A single sample is compared directly.
+    pub fn exceeds(&self, threshold: f64) -> bool {
+        self.ci_low.unwrap_or(self.mean) > threshold
+    }
+}
+
+/// The result of comparing two sets of samples with Welch's t-test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Significance {
+    /// `candidate.mean - baseline.mean`
+    pub difference: f64,
+    /// `None` when it can't be computed: too few samples, or no variance on either side
+    pub t: Option<f64>,
+    pub degrees_of_freedom: f64,
+    /// Whether the difference is significant at the 95% level. Never true with fewer than two samples on a side.
+    pub significant: bool,
+}
+
+/// Welch's t-test, which doesn't assume both sides have the same variance.
+pub fn welch_t_test(baseline: &SampleStats, candidate: &SampleStats) -> Significance {
+    let difference = candidate.mean - baseline.mean;
+    if baseline.count() < 2 || candidate.count() < 2 {
+        return Significance {
+            difference,
+            t: None,
+            degrees_of_freedom: 0.0,
+            significant: false,
+        };
+    }
+
+    let (n1, n2) = (baseline.count() as f64, candidate.count() as f64);
+    let (v1, v2) = (baseline.stddev.powi(2) / n1, candidate.stddev.powi(2) / n2);
+    let standard_error = (v1 + v2).sqrt();
+    if standard_error == 0.0 {
+        // both sides are perfectly stable, so any difference at all is real
+        return Significance {
+            difference,
+            t: None,
+            degrees_of_freedom: n1 + n2 - 2.0,
+            significant: difference != 0.0,
+        };
+    }
+
+    let t = difference / standard_error;
+    let degrees_of_freedom =
+        (v1 + v2).powi(2) / (v1.powi(2) / (n1 - 1.0) + v2.powi(2) / (n2 - 1.0));
+    Significance {
+        difference,
+        t: Some(t),
+        degrees_of_freedom,
+        significant: t.abs() > t_critical_95(degrees_of_freedom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn t_critical_values_match_the_table() {
+        assert_eq!(t_critical_95(1.0), 12.706);
+        assert_eq!(t_critical_95(10.0), 2.228);
+        assert_eq!(t_critical_95(30.0), 2.042);
+        assert_eq!(t_critical_95(31.0), 1.960);
+        assert_eq!(t_critical_95(1000.0), 1.960);
+    }
+
+    #[test]
+    fn t_critical_rounds_fractional_degrees_of_freedom_down() {
+        assert_eq!(t_critical_95(4.9), t_critical_95(4.0));
+        assert!(t_critical_95(4.9) > t_critical_95(5.0));
+    }
+
+    #[test]
+    fn t_critical_is_infinite_without_degrees_of_freedom() {
+        assert_eq!(t_critical_95(0.0), f64::INFINITY);
+        assert_eq!(t_critical_95(0.5), f64::INFINITY);
+        assert_eq!(t_critical_95(f64::NAN), f64::INFINITY);
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        // mean 5, sample standard deviation sqrt(32 / 7)
+        let stats = SampleStats::from_samples(vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_eq!(stats.count(), 8);
+        assert_close(stats.mean, 5.0);
+        assert_close(stats.median, 4.5);
+        assert_close(stats.stddev, 2.138);
+        // 5 ± 2.365 * 2.138 / sqrt(8)
+        assert_close(stats.ci_low.unwrap(), 3.212);
+        assert_close(stats.ci_high.unwrap(), 6.788);
+    }
+
+    #[test]
+    fn median_of_an_odd_count_is_the_middle_sample() {
+        assert_eq!(SampleStats::from_samples(vec![9.0, 1.0, 3.0]).median, 3.0);
+    }
+
+    #[test]
+    fn no_samples_is_the_default() {
+        assert_eq!(
+            SampleStats::from_samples(Vec::new()),
+            SampleStats::default()
+        );
+    }
+
+    #[test]
+    fn single_sample_has_no_interval() {
+        let stats = SampleStats::from_samples(vec![7.0]);
+        assert_eq!(stats.mean, 7.0);
+        assert_eq!(stats.median, 7.0);
+        assert_eq!(stats.stddev, 0.0);
+        assert_eq!((stats.ci_low, stats.ci_high), (None, None));
+        assert!(stats.exceeds(6.9));
+        assert!(!stats.exceeds(7.0));
+    }
+
+    #[test]
+    fn zero_variance_collapses_the_interval() {
+        let stats = SampleStats::from_samples(vec![3.0, 3.0, 3.0]);
+        assert_eq!(stats.stddev, 0.0);
+        assert_eq!((stats.ci_low, stats.ci_high), (Some(3.0), Some(3.0)));
+        assert!(stats.exceeds(2.9));
+        assert!(!stats.exceeds(3.0));
+    }
+
+    #[test]
+    fn exceeds_only_when_the_whole_interval_is_above() {
+        let stats = SampleStats::from_samples(vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        // the mean is above 4, but the interval reaches down to about 3.2
+        assert!(!stats.exceeds(4.0));
+        assert!(stats.exceeds(3.0));
+    }
+
+    #[test]
+    fn welch_with_equal_variances() {
+        // scipy.stats.ttest_ind([1, 2, 3], [4, 5, 6], equal_var=False): t = 3.674, df = 4
+        let baseline = SampleStats::from_samples(vec![1.0, 2.0, 3.0]);
+        let candidate = SampleStats::from_samples(vec![4.0, 5.0, 6.0]);
+        let result = welch_t_test(&baseline, &candidate);
+        assert_close(result.difference, 3.0);
+        assert_close(result.t.unwrap(), 3.674);
+        assert_close(result.degrees_of_freedom, 4.0);
+        assert!(result.significant);
+    }
+
+    #[test]
+    fn welch_with_unequal_variances() {
+        // scipy.stats.ttest_ind([1, 2, 3], [10, 20, 30, 40], equal_var=False): t = 3.549, df = 3.048
+        let baseline = SampleStats::from_samples(vec![1.0, 2.0, 3.0]);
+        let candidate = SampleStats::from_samples(vec![10.0, 20.0, 30.0, 40.0]);
+        let result = welch_t_test(&baseline, &candidate);
+        assert_close(result.t.unwrap(), 3.549);
+        assert_close(result.degrees_of_freedom, 3.048);
+        // above t(3) = 3.182
+        assert!(result.significant);
+    }
+
+    #[test]
+    fn welch_is_not_significant_within_noise() {
+        let baseline = SampleStats::from_samples(vec![10.0, 12.0, 11.0, 13.0]);
+        let candidate = SampleStats::from_samples(vec![11.0, 13.0, 12.0, 10.5]);
+        let result = welch_t_test(&baseline, &candidate);
+        assert!(result.t.is_some());
+        assert!(!result.significant);
+    }
+
+    #[test]
+    fn welch_needs_two_samples_a_side() {
+        let baseline = SampleStats::from_samples(vec![1.0]);
+        let candidate = SampleStats::from_samples(vec![100.0, 101.0, 102.0]);
+        let result = welch_t_test(&baseline, &candidate);
+        assert_eq!(result.t, None);
+        assert_eq!(result.degrees_of_freedom, 0.0);
+        assert!(!result.significant);
+    }
+
+    #[test]
+    fn welch_with_zero_variance() {
+        let stable = SampleStats::from_samples(vec![5.0, 5.0]);
+        let moved = SampleStats::from_samples(vec![6.0, 6.0, 6.0]);
+        let result = welch_t_test(&stable, &moved);
+        assert_eq!(result.t, None);
+        assert_eq!(result.degrees_of_freedom, 3.0);
+        assert!(result.significant);
+        assert!(!welch_t_test(&stable, &stable).significant);
+    }
+}
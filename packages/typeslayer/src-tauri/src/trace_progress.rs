@@ -0,0 +1,86 @@
+use serde::Serialize;
+use std::{
+    io::SeekFrom,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// How often a running trace is polled for progress
+pub const TRACE_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A snapshot of a trace that's still being written
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceProgress {
+    /// Current size of trace.json in bytes
+    pub trace_json_size: u64,
+    /// Number of `checkSourceFile` spans that have finished so far
+    pub files_checked: usize,
+    pub elapsed_ms: u64,
+}
+
+/// Tails a trace.json as tsc writes it.
+///
+/// tsc writes one event per line, so only the newly appended bytes are read on each poll,
+/// and finished `checkSourceFile` spans are counted as a rough files-checked estimate.
+pub struct TraceProgressWatcher {
+    path: PathBuf,
+    start: Instant,
+    offset: u64,
+    partial_line: Vec<u8>,
+    progress: TraceProgress,
+}
+
+impl TraceProgressWatcher {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            start: Instant::now(),
+            offset: 0,
+            partial_line: Vec::new(),
+            progress: TraceProgress::default(),
+        }
+    }
+
+    pub async fn poll(&mut self) -> &TraceProgress {
+        self.progress.elapsed_ms = self.start.elapsed().as_millis() as u64;
+
+        // the file doesn't exist until tsc gets past program creation
+        let Ok(mut file) = tokio::fs::File::open(&self.path).await else {
+            return &self.progress;
+        };
+        let Ok(metadata) = file.metadata().await else {
+            return &self.progress;
+        };
+        self.progress.trace_json_size = metadata.len();
+
+        if metadata.len() <= self.offset || file.seek(SeekFrom::Start(self.offset)).await.is_err() {
+            return &self.progress;
+        }
+
+        let mut appended = Vec::new();
+        if let Ok(read) = file.read_to_end(&mut appended).await {
+            self.offset += read as u64;
+            self.count_finished_files(&appended);
+        }
+
+        &self.progress
+    }
+
+    fn count_finished_files(&mut self, appended: &[u8]) {
+        self.partial_line.extend_from_slice(appended);
+        let Some(last_newline) = self.partial_line.iter().rposition(|b| *b == b'\n') else {
+            return;
+        };
+
+        let complete: Vec<u8> = self.partial_line.drain(..=last_newline).collect();
+        self.progress.files_checked += complete
+            .split(|b| *b == b'\n')
+            .filter_map(|line| std::str::from_utf8(line).ok())
+            .filter(|line| {
+                line.contains(r#""ph":"E""#) && line.contains(r#""name":"checkSourceFile""#)
+            })
+            .count();
+    }
+}
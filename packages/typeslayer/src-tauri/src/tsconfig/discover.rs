@@ -0,0 +1,517 @@
+use super::jsonc;
+use crate::utils::{PACKAGE_JSON_FILENAME, TSCONFIG_FILENAME, normalize_path};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+use tracing::{debug, warn};
+
+const NODE_MODULES: &str = "node_modules";
+const GIT_DIR: &str = ".git";
+const PNPM_WORKSPACE_YAML: &str = "pnpm-workspace.yaml";
+
+/// The package a tsconfig belongs to: the nearest directory at or above it with a `package.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwningPackage {
+    /// `name` from the package.json, if it has one
+    pub name: Option<String>,
+    pub dir: PathBuf,
+    /// Whether `dir` matches one of the project root's pnpm/yarn/npm workspace globs
+    pub workspace_member: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsconfigNode {
+    pub path: PathBuf,
+    pub package: Option<OwningPackage>,
+    /// Every config this one inherits from, nearest first. Package specifiers are resolved through node_modules.
+    pub extends: Vec<PathBuf>,
+    /// The configs listed in `references`, each with its own references
+    pub references: Vec<TsconfigNode>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TsconfigDiscovery {
+    /// Every config found, the root `tsconfig.json` first, then grouped by directory
+    pub paths: Vec<PathBuf>,
+    /// Configs no other config references, with their references nested under them
+    pub tree: Vec<TsconfigNode>,
+}
+
+/// Just the parts of a tsconfig that link it to other configs.
+#[derive(Debug, Default, Deserialize)]
+struct TsconfigLinks {
+    #[serde(default)]
+    extends: Option<Extends>,
+    #[serde(default)]
+    references: Vec<ProjectReference>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Extends {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Extends {
+    pub fn specifiers(&self) -> Vec<&str> {
+        match self {
+            Extends::One(specifier) => vec![specifier.as_str()],
+            Extends::Many(specifiers) => specifiers.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectReference {
+    pub path: String,
+}
+
+/// Resolve an `extends` specifier the way `tsc` does: relative and absolute paths against the
+/// config's directory (adding `.json` if needed), anything else as a package in node_modules.
+pub fn resolve_extends(specifier: &str, config_dir: &Path) -> Option<PathBuf> {
+    let is_path = specifier.starts_with("./")
+        || specifier.starts_with("../")
+        || Path::new(specifier).is_absolute();
+    if is_path {
+        let path = normalize_path(&config_dir.join(specifier));
+        if path.is_file() {
+            return Some(path);
+        }
+        if !specifier.ends_with(".json") {
+            let with_extension = PathBuf::from(format!("{}.json", path.to_string_lossy()));
+            if with_extension.is_file() {
+                return Some(with_extension);
+            }
+        }
+        return None;
+    }
+
+    for dir in config_dir.ancestors() {
+        let candidate = dir.join(NODE_MODULES).join(specifier);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let with_extension = PathBuf::from(format!("{}.json", candidate.to_string_lossy()));
+        if with_extension.is_file() {
+            return Some(with_extension);
+        }
+        if candidate.is_dir() {
+            // a package may point at its config with a `tsconfig` field, e.g. @tsconfig/bases
+            let from_field = std::fs::read_to_string(candidate.join(PACKAGE_JSON_FILENAME))
+                .ok()
+                .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+                .and_then(|json| json.get("tsconfig")?.as_str().map(str::to_string))
+                .map(|field| normalize_path(&candidate.join(field)))
+                .filter(|path| path.is_file());
+            if from_field.is_some() {
+                return from_field;
+            }
+            let default = candidate.join(TSCONFIG_FILENAME);
+            if default.is_file() {
+                return Some(default);
+            }
+        }
+    }
+    None
+}
+
+/// Resolve a `references[].path`, which may name a config file or a directory containing a `tsconfig.json`.
+pub fn resolve_reference(path: &str, config_dir: &Path) -> PathBuf {
+    let path = normalize_path(&config_dir.join(path));
+    if path.is_file() {
+        path
+    } else {
+        path.join(TSCONFIG_FILENAME)
+    }
+}
+
+fn is_tsconfig_file_name(file_name: &str) -> bool {
+    file_name.starts_with("tsconfig") && file_name.ends_with(".json")
+}
+
+/// The workspace globs declared by `package.json` (`workspaces` as an array or as `{ packages }`)
+/// and `pnpm-workspace.yaml` at the project root.
+fn read_workspace_globs(project_root: &Path) -> Vec<String> {
+    let mut globs = Vec::new();
+
+    if let Ok(contents) = std::fs::read_to_string(project_root.join(PACKAGE_JSON_FILENAME))
+        && let Ok(json) = serde_json::from_str::<serde_json::Value>(&contents)
+    {
+        let workspaces = json.get("workspaces");
+        let list = workspaces
+            .and_then(|w| w.as_array())
+            .or_else(|| workspaces?.get("packages")?.as_array());
+        globs.extend(
+            list.into_iter()
+                .flatten()
+                .filter_map(|g| g.as_str().map(str::to_string)),
+        );
+    }
+
+    if let Ok(contents) = std::fs::read_to_string(project_root.join(PNPM_WORKSPACE_YAML)) {
+        globs.extend(pnpm_workspace_packages(&contents));
+    }
+
+    globs
+}
+
+/// The `packages` list of a pnpm-workspace.yaml. Only that list is needed, so this reads the
+/// handful of YAML shapes pnpm documents rather than pulling in a YAML parser.
+fn pnpm_workspace_packages(contents: &str) -> Vec<String> {
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+    for line in contents.lines() {
+        let line = line.split(" #").next().unwrap_or_default().trim_end();
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = line.trim() == "packages:";
+            continue;
+        }
+        if in_packages && let Some(item) = line.trim().strip_prefix('-') {
+            let item = item.trim().trim_matches(|c| c == '"' || c == '\'');
+            if !item.is_empty() {
+                packages.push(item.to_string());
+            }
+        }
+    }
+    packages
+}
+
+/// Include and exclude (`!`-prefixed) workspace globs, matched against paths relative to the project root.
+struct WorkspaceGlobs {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl WorkspaceGlobs {
+    fn new(globs: &[String]) -> Self {
+        let mut include = GlobSetBuilder::new();
+        let mut exclude = GlobSetBuilder::new();
+        for glob in globs {
+            let (builder, pattern) = match glob.strip_prefix('!') {
+                Some(pattern) => (&mut exclude, pattern),
+                None => (&mut include, glob.as_str()),
+            };
+            let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+            match GlobBuilder::new(pattern).literal_separator(true).build() {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(e) => {
+                    warn!("[discover_tsconfigs] ignoring invalid workspace glob {glob:?}: {e}")
+                }
+            }
+        }
+        let build = |builder: GlobSetBuilder| builder.build().unwrap_or_else(|_| GlobSet::empty());
+        Self {
+            include: build(include),
+            exclude: build(exclude),
+        }
+    }
+
+    fn matches(&self, relative: &Path) -> bool {
+        self.include.is_match(relative) && !self.exclude.is_match(relative)
+    }
+}
+
+/// Walk `project_root` for tsconfig files, respecting `.gitignore` and skipping node_modules
+/// and `.git` but not other hidden directories like `.storybook`, then follow `references` and `extends` to configs the walk didn't reach.
+pub fn discover_tsconfigs(project_root: &Path) -> Result<TsconfigDiscovery, String> {
+    if !project_root.is_dir() {
+        return Err(format!("Could not read dir {}", project_root.display()));
+    }
+    let project_root = normalize_path(project_root);
+
+    let mut found = BTreeSet::new();
+    let mut packages: BTreeMap<PathBuf, Option<String>> = BTreeMap::new();
+    let walker = WalkBuilder::new(&project_root)
+        .require_git(false)
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != NODE_MODULES && entry.file_name() != GIT_DIR)
+        .build();
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                debug!("[discover_tsconfigs] skipping unreadable entry: {e}");
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy();
+        if is_tsconfig_file_name(&file_name) {
+            found.insert(normalize_path(entry.path()));
+        } else if file_name == PACKAGE_JSON_FILENAME
+            && let Some(dir) = entry.path().parent()
+        {
+            let name = std::fs::read_to_string(entry.path())
+                .ok()
+                .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+                .and_then(|json| json.get("name")?.as_str().map(str::to_string));
+            packages.insert(normalize_path(dir), name);
+        }
+    }
+
+    let workspace_globs = WorkspaceGlobs::new(&read_workspace_globs(&project_root));
+    let mut discoverer = Discoverer {
+        project_root,
+        packages,
+        workspace_globs,
+        links: HashMap::new(),
+    };
+
+    // follow references and extends until nothing new turns up
+    let mut queue: Vec<PathBuf> = found.iter().cloned().collect();
+    while let Some(path) = queue.pop() {
+        let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        let links = discoverer.links(&path);
+        let extends: Vec<PathBuf> = links
+            .extends
+            .as_ref()
+            .map(|e| e.specifiers())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|specifier| resolve_extends(specifier, &dir))
+            .collect();
+        let references: Vec<PathBuf> = links
+            .references
+            .iter()
+            .map(|reference| resolve_reference(&reference.path, &dir))
+            .filter(|path| path.is_file())
+            .collect();
+        for linked in extends.into_iter().chain(references) {
+            // shared bases in node_modules are shown in `extends`, but aren't something to pick
+            let in_node_modules = linked.components().any(|c| c.as_os_str() == NODE_MODULES);
+            if !in_node_modules && found.insert(linked.clone()) {
+                queue.push(linked);
+            }
+        }
+    }
+
+    let root_tsconfig = discoverer.project_root.join(TSCONFIG_FILENAME);
+    let mut paths: Vec<PathBuf> = found.into_iter().collect();
+    paths.sort_by_cached_key(|path| {
+        (
+            *path != root_tsconfig,
+            path.parent().map(Path::to_path_buf),
+            path.file_name()
+                .is_none_or(|name| name != TSCONFIG_FILENAME),
+            path.clone(),
+        )
+    });
+
+    let tree = discoverer.tree(&paths);
+    debug!(
+        "[discover_tsconfigs] found {} tsconfig(s) under {}",
+        paths.len(),
+        discoverer.project_root.display()
+    );
+    Ok(TsconfigDiscovery { paths, tree })
+}
+
+struct Discoverer {
+    project_root: PathBuf,
+    /// Directories containing a package.json, with the package's name
+    packages: BTreeMap<PathBuf, Option<String>>,
+    workspace_globs: WorkspaceGlobs,
+    links: HashMap<PathBuf, TsconfigLinks>,
+}
+
+impl Discoverer {
+    fn links(&mut self, path: &Path) -> &TsconfigLinks {
+        self.links.entry(path.to_path_buf()).or_insert_with(|| {
+            std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|contents| jsonc::from_str(&contents))
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "[discover_tsconfigs] could not parse {}: {e}",
+                        path.display()
+                    );
+                    TsconfigLinks::default()
+                })
+        })
+    }
+
+    fn owning_package(&self, path: &Path) -> Option<OwningPackage> {
+        let dir = path
+            .parent()?
+            .ancestors()
+            .take_while(|dir| dir.starts_with(&self.project_root))
+            .find(|dir| self.packages.contains_key(*dir))?;
+        let workspace_member = dir.strip_prefix(&self.project_root).is_ok_and(|relative| {
+            !relative.as_os_str().is_empty() && self.workspace_globs.matches(relative)
+        });
+        Some(OwningPackage {
+            name: self.packages.get(dir).cloned().flatten(),
+            dir: dir.to_path_buf(),
+            workspace_member,
+        })
+    }
+
+    /// The full `extends` chain of a config, nearest first, stopping at cycles.
+    fn extends_chain(&mut self, path: &Path) -> Vec<PathBuf> {
+        let mut chain = Vec::new();
+        self.collect_extends(path, &mut HashSet::from([path.to_path_buf()]), &mut chain);
+        chain
+    }
+
+    fn collect_extends(
+        &mut self,
+        path: &Path,
+        seen: &mut HashSet<PathBuf>,
+        chain: &mut Vec<PathBuf>,
+    ) {
+        let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        let parents: Vec<PathBuf> = self
+            .links(path)
+            .extends
+            .as_ref()
+            .map(|e| e.specifiers())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|specifier| resolve_extends(specifier, &dir))
+            .collect();
+        // later entries of an `extends` array win, so the last one is the nearest
+        for parent in parents.into_iter().rev() {
+            if seen.insert(parent.clone()) {
+                chain.push(parent.clone());
+                self.collect_extends(&parent, seen, chain);
+            }
+        }
+    }
+
+    fn references(&mut self, path: &Path) -> Vec<PathBuf> {
+        let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        self.links(path)
+            .references
+            .iter()
+            .map(|reference| resolve_reference(&reference.path, &dir))
+            .filter(|path| path.is_file())
+            .collect()
+    }
+
+    fn tree(&mut self, paths: &[PathBuf]) -> Vec<TsconfigNode> {
+        let referenced: HashSet<PathBuf> = paths
+            .iter()
+            .flat_map(|path| self.references(path))
+            .collect();
+
+        let mut placed = HashSet::new();
+        let mut tree = Vec::new();
+        for path in paths.iter().filter(|path| !referenced.contains(*path)) {
+            tree.push(self.node(path, &mut Vec::new(), &mut placed));
+        }
+        // configs that only appear inside a reference cycle have no natural root
+        for path in paths {
+            if !placed.contains(path) {
+                tree.push(self.node(path, &mut Vec::new(), &mut placed));
+            }
+        }
+        tree
+    }
+
+    fn node(
+        &mut self,
+        path: &Path,
+        ancestors: &mut Vec<PathBuf>,
+        placed: &mut HashSet<PathBuf>,
+    ) -> TsconfigNode {
+        placed.insert(path.to_path_buf());
+        ancestors.push(path.to_path_buf());
+        let mut references = Vec::new();
+        for reference in self.references(path) {
+            if !ancestors.contains(&reference) {
+                references.push(self.node(&reference, ancestors, placed));
+            }
+        }
+        ancestors.pop();
+
+        TsconfigNode {
+            path: path.to_path_buf(),
+            package: self.owning_package(path),
+            extends: self.extends_chain(path),
+            references,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_pnpm_workspace_packages() {
+        let yaml = "packages:\n  - packages/*\n  - \"apps/**\"\n  - '!**/test/**'\n";
+        assert_eq!(
+            pnpm_workspace_packages(yaml),
+            ["packages/*", "apps/**", "!**/test/**"]
+        );
+    }
+
+    #[test]
+    fn skips_comments_and_other_keys() {
+        let yaml = "# workspace\npackages:\n  # apps first\n  - apps/* # the apps\n\n  - libs/*\ncatalog:\n  - not-a-package\nonlyBuiltDependencies:\n  - esbuild\n";
+        assert_eq!(pnpm_workspace_packages(yaml), ["apps/*", "libs/*"]);
+    }
+
+    #[test]
+    fn reads_unindented_list_items() {
+        let yaml = "packages:\n- a/*\n- b\n";
+        assert_eq!(pnpm_workspace_packages(yaml), ["a/*", "b"]);
+    }
+
+    #[test]
+    fn matches_workspace_globs() {
+        let globs =
+            WorkspaceGlobs::new(&["./packages/*/".to_string(), "!packages/private".to_string()]);
+        assert!(globs.matches(Path::new("packages/app")));
+        assert!(!globs.matches(Path::new("packages/private")));
+        assert!(!globs.matches(Path::new("packages/app/nested")));
+    }
+
+    #[test]
+    fn walks_hidden_directories_but_not_ignored_ones() {
+        let root = std::env::temp_dir().join(format!("typeslayer-discover-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for (path, contents) in [
+            ("tsconfig.json", "{}"),
+            (".storybook/tsconfig.json", "{}"),
+            (".gitignore", "generated/\n"),
+            ("generated/tsconfig.json", "{}"),
+            ("node_modules/pkg/tsconfig.json", "{}"),
+            (".git/tsconfig.json", "{}"),
+        ] {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+
+        let discovery = discover_tsconfigs(&root).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+        let root = normalize_path(&root);
+        let found: Vec<&Path> = discovery
+            .paths
+            .iter()
+            .map(|path| path.strip_prefix(&root).unwrap())
+            .collect();
+        assert_eq!(
+            found,
+            [
+                Path::new("tsconfig.json"),
+                Path::new(".storybook/tsconfig.json")
+            ]
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use serde::de::DeserializeOwned;
+
+/// Parse JSON with comments and trailing commas, the dialect `tsc` accepts for tsconfig files.
+pub fn from_str<T: DeserializeOwned>(source: &str) -> Result<T, String> {
+    serde_json::from_str(&strip(source)).map_err(|e| e.to_string())
+}
+
+/// Rewrite JSONC into plain JSON by blanking out comments and dropping trailing commas.
+///
+/// Comments are replaced with spaces (newlines are kept) so serde's line/column errors still
+/// point at the right place in the original file.
+pub fn strip(source: &str) -> String {
+    let chars: Vec<char> = source.trim_start_matches('\u{feff}').chars().collect();
+    let mut out = String::with_capacity(source.len());
+    // index in `out` of a comma that will be dropped if the next significant char closes a container
+    let mut pending_comma: Option<usize> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => {
+                pending_comma = None;
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    match chars[i] {
+                        '\\' if i + 1 < chars.len() => {
+                            out.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        '"' => {
+                            i += 1;
+                            break;
+                        }
+                        _ => i += 1,
+                    }
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    out.push(' ');
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                out.push_str("  ");
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    out.push(if chars[i] == '\n' { '\n' } else { ' ' });
+                    i += 1;
+                }
+                if i < chars.len() {
+                    out.push_str("  ");
+                    i += 2;
+                }
+            }
+            ',' => {
+                pending_comma = Some(out.len());
+                out.push(c);
+                i += 1;
+            }
+            '}' | ']' => {
+                if let Some(index) = pending_comma.take() {
+                    out.replace_range(index..index + 1, " ");
+                }
+                out.push(c);
+                i += 1;
+            }
+            _ => {
+                if !c.is_whitespace() {
+                    pending_comma = None;
+                }
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn parse(source: &str) -> Value {
+        from_str(source).unwrap()
+    }
+
+    #[test]
+    fn strips_line_and_block_comments() {
+        let source = "{\n  // line\n  \"a\": 1, /* block\n spanning lines */ \"b\": 2\n}";
+        assert_eq!(parse(source), json!({ "a": 1, "b": 2 }));
+    }
+
+    #[test]
+    fn keeps_comment_markers_inside_strings() {
+        let source = r#"{ "url": "https://example.com/*", "glob": "src/**/*.ts" }"#;
+        assert_eq!(
+            parse(source),
+            json!({ "url": "https://example.com/*", "glob": "src/**/*.ts" })
+        );
+    }
+
+    #[test]
+    fn keeps_escaped_quotes_inside_strings() {
+        let source = r#"{ "a": "say \"hi\" // not a comment", }"#;
+        assert_eq!(parse(source), json!({ "a": "say \"hi\" // not a comment" }));
+    }
+
+    #[test]
+    fn drops_trailing_commas() {
+        let source = "{ \"a\": [1, 2, ], \"b\": { \"c\": 3, /* trailing */ }, }";
+        assert_eq!(parse(source), json!({ "a": [1, 2], "b": { "c": 3 } }));
+    }
+
+    #[test]
+    fn keeps_commas_inside_strings() {
+        assert_eq!(parse(r#"{ "a": ",}" }"#), json!({ "a": ",}" }));
+    }
+
+    #[test]
+    fn keeps_line_numbers() {
+        let source = "{\n/* one\ntwo */\n\"a\": 1\n}";
+        assert_eq!(strip(source).lines().count(), source.lines().count());
+    }
+
+    #[test]
+    fn skips_byte_order_mark() {
+        assert_eq!(parse("\u{feff}{ \"a\": 1 }"), json!({ "a": 1 }));
+    }
+
+    #[test]
+    fn rejects_unterminated_json() {
+        assert!(from_str::<Value>("{ \"a\": 1, // }").is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod discover;
+pub mod jsonc;
+pub mod resolve;
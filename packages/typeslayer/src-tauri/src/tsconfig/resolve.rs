@@ -0,0 +1,646 @@
+use super::{
+    discover::{resolve_extends, resolve_reference},
+    jsonc,
+};
+use crate::utils::normalize_path;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+pub const EFFECTIVE_TSCONFIG_FILENAME: &str = "effective-tsconfig.json";
+
+/// Directories tsc never descends into when expanding wildcards.
+const SKIPPED_DIRECTORIES: [&str; 3] = ["node_modules", "bower_components", "jspm_packages"];
+
+const TS_EXTENSIONS: [&str; 7] = [".ts", ".tsx", ".d.ts", ".mts", ".cts", ".d.mts", ".d.cts"];
+const JS_EXTENSIONS: [&str; 4] = [".js", ".jsx", ".mjs", ".cjs"];
+
+/// The compiler options with the biggest effect on type-check time, after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceOptions {
+    pub strict: bool,
+    pub skip_lib_check: bool,
+    pub isolated_declarations: bool,
+    /// Also on when `composite` is set and `incremental` isn't
+    pub incremental: bool,
+    /// `None` means every package under `node_modules/@types` is included
+    pub types: Option<Vec<String>>,
+}
+
+/// A tsconfig as tsc sees it: `extends` merged, command-line flags applied and root files expanded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveTsconfig {
+    /// `None` when tsc ran without a config file
+    pub path: Option<PathBuf>,
+    /// Every config inherited from, nearest first
+    pub extends: Vec<PathBuf>,
+    pub references: Vec<PathBuf>,
+    pub files: Option<Vec<String>>,
+    pub include: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+    /// The merged `compilerOptions`, including overrides from the extra tsc flags. Path-valued
+    /// options are absolute, since each is relative to the config that set it.
+    pub compiler_options: Map<String, Value>,
+    pub performance: PerformanceOptions,
+    /// Number of files matched by `files` and `include`, before imports pull in more
+    pub root_file_count: usize,
+}
+
+/// `files`/`include`/`exclude` are relative to the config that declared them, which may be a base config.
+#[derive(Debug, Clone)]
+struct FileSpecs {
+    specs: Vec<String>,
+    base_dir: PathBuf,
+}
+
+#[derive(Debug, Default)]
+struct MergedConfig {
+    compiler_options: Map<String, Value>,
+    files: Option<FileSpecs>,
+    include: Option<FileSpecs>,
+    exclude: Option<FileSpecs>,
+}
+
+impl MergedConfig {
+    /// Layer `other` over `self`: options are overridden one by one, file lists wholesale.
+    fn merge(&mut self, other: MergedConfig) {
+        self.compiler_options.extend(other.compiler_options);
+        if other.files.is_some() {
+            self.files = other.files;
+        }
+        if other.include.is_some() {
+            self.include = other.include;
+        }
+        if other.exclude.is_some() {
+            self.exclude = other.exclude;
+        }
+    }
+}
+
+fn read_config(path: &Path) -> Result<Map<String, Value>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    jsonc::from_str(&contents).map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+}
+
+fn string_list(value: Option<&Value>) -> Option<Vec<String>> {
+    Some(
+        value?
+            .as_array()?
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+    )
+}
+
+/// Compiler options holding a path, which tsc resolves against the config that sets them.
+const PATH_OPTIONS: [&str; 7] = [
+    "baseUrl",
+    "declarationDir",
+    "out",
+    "outDir",
+    "outFile",
+    "rootDir",
+    "tsBuildInfoFile",
+];
+/// Compiler options holding a list of paths, resolved like [`PATH_OPTIONS`].
+const PATH_LIST_OPTIONS: [&str; 2] = ["rootDirs", "typeRoots"];
+
+/// Make the path-valued options absolute against `dir`, so an option inherited from a base
+/// config still points where the base config meant.
+fn rebase_path_options(options: &mut Map<String, Value>, dir: &Path) {
+    let rebase = |value: &mut Value| {
+        if let Value::String(path) = value {
+            *path = to_slashes(&normalize_path(&dir.join(&*path)));
+        }
+    };
+    for key in PATH_OPTIONS {
+        if let Some(value) = options.get_mut(key) {
+            rebase(value);
+        }
+    }
+    for key in PATH_LIST_OPTIONS {
+        if let Some(Value::Array(values)) = options.get_mut(key) {
+            values.iter_mut().for_each(rebase);
+        }
+    }
+}
+
+/// Load `path` with everything it extends merged in, recording the chain (nearest first) in `chain`.
+fn load_merged(
+    path: &Path,
+    ancestors: &mut Vec<PathBuf>,
+    chain: &mut Vec<PathBuf>,
+) -> Result<MergedConfig, String> {
+    if ancestors.iter().any(|ancestor| ancestor == path) {
+        return Err(format!(
+            "Circularity detected while resolving {}",
+            path.display()
+        ));
+    }
+    let raw = read_config(path)?;
+    let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+    let specifiers: Vec<String> = match raw.get("extends") {
+        Some(Value::String(specifier)) => vec![specifier.clone()],
+        Some(value) => string_list(Some(value)).unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    ancestors.push(path.to_path_buf());
+    let mut merged = MergedConfig::default();
+    let mut bases = Vec::new();
+    for specifier in &specifiers {
+        let base = resolve_extends(specifier, &dir).ok_or_else(|| {
+            format!(
+                "Cannot find base config {specifier:?} extended by {}",
+                path.display()
+            )
+        })?;
+        let mut base_chain = Vec::new();
+        merged.merge(load_merged(&base, ancestors, &mut base_chain)?);
+        bases.push((base, base_chain));
+    }
+    ancestors.pop();
+
+    // later entries of an `extends` array win, so the last one is the nearest
+    for (base, base_chain) in bases.into_iter().rev() {
+        chain.push(base);
+        chain.extend(base_chain);
+    }
+
+    let file_specs = |key: &str| {
+        string_list(raw.get(key)).map(|specs| FileSpecs {
+            specs,
+            base_dir: dir.clone(),
+        })
+    };
+    let mut compiler_options = raw
+        .get("compilerOptions")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    rebase_path_options(&mut compiler_options, &dir);
+    merged.merge(MergedConfig {
+        compiler_options,
+        files: file_specs("files"),
+        include: file_specs("include"),
+        exclude: file_specs("exclude"),
+    });
+    Ok(merged)
+}
+
+/// Boolean compiler options that can be set from the extra tsc flags, e.g. `--skipLibCheck` or `--incremental false`.
+const BOOLEAN_FLAGS: [&str; 6] = [
+    "strict",
+    "skipLibCheck",
+    "isolatedDeclarations",
+    "incremental",
+    "composite",
+    "allowJs",
+];
+
+/// Apply the command-line flags tsc is run with over the config's options. Only the flags that
+/// feed [`PerformanceOptions`] and the root file count are understood.
+fn apply_command_line(options: &mut Map<String, Value>, flags: &str) {
+    let tokens: Vec<&str> = flags.split_whitespace().collect();
+    let mut i = 0;
+    while i < tokens.len() {
+        let name = tokens[i].trim_start_matches('-');
+        i += 1;
+        if !tokens[i - 1].starts_with("--") {
+            continue;
+        }
+        if let Some(flag) = BOOLEAN_FLAGS
+            .iter()
+            .find(|flag| flag.eq_ignore_ascii_case(name))
+        {
+            let value = match tokens.get(i) {
+                Some(&"true") => Some(true),
+                Some(&"false") => Some(false),
+                _ => None,
+            };
+            if value.is_some() {
+                i += 1;
+            }
+            options.insert(flag.to_string(), Value::Bool(value.unwrap_or(true)));
+        } else if name.eq_ignore_ascii_case("types")
+            && let Some(list) = tokens.get(i)
+        {
+            i += 1;
+            let types = list
+                .split(',')
+                .filter(|t| !t.is_empty())
+                .map(|t| Value::String(t.to_string()))
+                .collect();
+            options.insert("types".to_string(), Value::Array(types));
+        }
+    }
+}
+
+fn option_bool(options: &Map<String, Value>, key: &str) -> Option<bool> {
+    options.get(key).and_then(Value::as_bool)
+}
+
+impl PerformanceOptions {
+    fn from_compiler_options(options: &Map<String, Value>) -> Self {
+        Self {
+            strict: option_bool(options, "strict").unwrap_or(false),
+            skip_lib_check: option_bool(options, "skipLibCheck").unwrap_or(false),
+            isolated_declarations: option_bool(options, "isolatedDeclarations").unwrap_or(false),
+            incremental: option_bool(options, "incremental")
+                .unwrap_or_else(|| option_bool(options, "composite").unwrap_or(false)),
+            types: string_list(options.get("types")),
+        }
+    }
+}
+
+fn to_slashes(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn has_wildcard(s: &str) -> bool {
+    s.contains(['*', '?'])
+}
+
+/// Split a spec into the directory it can't match outside of (everything before the first
+/// wildcard, resolved against `base_dir`) and the remaining wildcard segments.
+fn split_spec<'a>(spec: &'a str, base_dir: &Path) -> (PathBuf, Vec<&'a str>) {
+    let segments: Vec<&str> = spec.trim_end_matches('/').split('/').collect();
+    let literal_len = segments
+        .iter()
+        .take_while(|segment| !has_wildcard(segment))
+        .count();
+    let root = normalize_path(&base_dir.join(segments[..literal_len].join("/")));
+    (root, segments[literal_len..].to_vec())
+}
+
+/// Turn an `include`/`exclude` spec into an absolute glob. A last segment with no wildcard and no
+/// extension names a directory, which tsc treats as everything under it.
+fn spec_to_glob(spec: &str, base_dir: &Path) -> String {
+    let (root, rest) = split_spec(spec, base_dir);
+    let mut glob = globset::escape(&to_slashes(&root));
+    for segment in &rest {
+        glob.push('/');
+        glob.push_str(segment);
+    }
+    let is_directory = rest.is_empty()
+        && root
+            .file_name()
+            .is_none_or(|name| !name.to_string_lossy().contains('.'));
+    if is_directory {
+        glob.push_str("/**/*");
+    }
+    glob
+}
+
+fn build_globs(globs: impl IntoIterator<Item = String>) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        match GlobBuilder::new(&glob).literal_separator(true).build() {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(e) => debug!("[resolve_tsconfig] ignoring invalid glob {glob:?}: {e}"),
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+/// Count the files tsc starts from: `files` plus everything `include` matches that `exclude` doesn't.
+fn count_root_files(merged: &MergedConfig, config_dir: &Path) -> usize {
+    let allow_js = option_bool(&merged.compiler_options, "allowJs").unwrap_or(false);
+    let has_extension = |path: &str| {
+        TS_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+            || (allow_js && JS_EXTENSIONS.iter().any(|ext| path.ends_with(ext)))
+    };
+
+    let mut root_files = BTreeSet::new();
+    if let Some(files) = &merged.files {
+        for file in &files.specs {
+            let path = normalize_path(&files.base_dir.join(file));
+            if path.is_file() {
+                root_files.insert(to_slashes(&path));
+            }
+        }
+    }
+
+    // with neither `files` nor `include`, tsc includes everything under the config's directory
+    let include = match (&merged.include, &merged.files) {
+        (Some(include), _) => include.clone(),
+        (None, None) => FileSpecs {
+            specs: vec!["**/*".to_string()],
+            base_dir: config_dir.to_path_buf(),
+        },
+        (None, Some(_)) => FileSpecs {
+            specs: Vec::new(),
+            base_dir: config_dir.to_path_buf(),
+        },
+    };
+    let exclude = merged.exclude.clone().unwrap_or_else(|| {
+        let mut specs: Vec<String> = SKIPPED_DIRECTORIES.iter().map(|d| d.to_string()).collect();
+        for key in ["outDir", "declarationDir"] {
+            if let Some(dir) = merged.compiler_options.get(key).and_then(Value::as_str) {
+                specs.push(dir.to_string());
+            }
+        }
+        FileSpecs {
+            specs,
+            base_dir: config_dir.to_path_buf(),
+        }
+    });
+
+    let include_globs = build_globs(
+        include
+            .specs
+            .iter()
+            .map(|spec| spec_to_glob(spec, &include.base_dir)),
+    );
+    // an exclude may name a file or a directory, and excluding a directory excludes what's in it
+    let exclude_globs = build_globs(exclude.specs.iter().flat_map(|spec| {
+        let glob = spec_to_glob(spec, &exclude.base_dir);
+        let glob = glob
+            .strip_suffix("/**/*")
+            .map(str::to_string)
+            .unwrap_or(glob);
+        [format!("{glob}/**"), glob]
+    }));
+
+    let walk_roots: BTreeSet<PathBuf> = include
+        .specs
+        .iter()
+        .map(|spec| split_spec(spec, &include.base_dir).0)
+        .collect();
+    for root in walk_roots {
+        if root.is_file() {
+            let path = to_slashes(&root);
+            if include_globs.is_match(&path) && !exclude_globs.is_match(&path) {
+                root_files.insert(path);
+            }
+            continue;
+        }
+        let excluded_dirs = exclude_globs.clone();
+        let walker = WalkBuilder::new(&root)
+            .standard_filters(false)
+            .filter_entry(move |entry| {
+                let name = entry.file_name().to_string_lossy();
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                entry.depth() == 0
+                    || !(is_dir
+                        && (name.starts_with('.')
+                            || SKIPPED_DIRECTORIES.contains(&name.as_ref())
+                            || excluded_dirs.is_match(to_slashes(entry.path()))))
+            })
+            .build();
+        for entry in walker.flatten() {
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let path = to_slashes(entry.path());
+            if has_extension(&path)
+                && include_globs.is_match(&path)
+                && !exclude_globs.is_match(&path)
+            {
+                root_files.insert(path);
+            }
+        }
+    }
+
+    // like tsc, a declaration file is dropped when its implementation is also a root
+    root_files
+        .iter()
+        .filter(|path| {
+            let Some(stem) = path.strip_suffix(".d.ts") else {
+                return true;
+            };
+            !root_files.contains(&format!("{stem}.ts"))
+                && !root_files.contains(&format!("{stem}.tsx"))
+        })
+        .count()
+}
+
+/// Resolve a tsconfig the way tsc does, with `command_line_flags` (the extra tsc flags) applied on top.
+///
+/// `None` describes a run without any config file, where tsc only uses its defaults and the flags.
+pub fn resolve_tsconfig(
+    path: Option<&Path>,
+    command_line_flags: &str,
+) -> Result<EffectiveTsconfig, String> {
+    let Some(path) = path else {
+        let mut compiler_options = Map::new();
+        apply_command_line(&mut compiler_options, command_line_flags);
+        return Ok(EffectiveTsconfig {
+            path: None,
+            extends: Vec::new(),
+            references: Vec::new(),
+            files: None,
+            include: None,
+            exclude: None,
+            performance: PerformanceOptions::from_compiler_options(&compiler_options),
+            compiler_options,
+            root_file_count: 0,
+        });
+    };
+
+    let path = normalize_path(path);
+    let config_dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+    let mut extends = Vec::new();
+    let mut merged = load_merged(&path, &mut Vec::new(), &mut extends)?;
+    apply_command_line(&mut merged.compiler_options, command_line_flags);
+
+    // references aren't inherited, so only the config's own count
+    let references = read_config(&path)?
+        .get("references")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|reference| reference.get("path")?.as_str())
+        .map(|reference| resolve_reference(reference, &config_dir))
+        .collect();
+
+    let root_file_count = count_root_files(&merged, &config_dir);
+    debug!(
+        "[resolve_tsconfig] {} extends {} config(s) and has {root_file_count} root file(s)",
+        path.display(),
+        extends.len()
+    );
+
+    Ok(EffectiveTsconfig {
+        performance: PerformanceOptions::from_compiler_options(&merged.compiler_options),
+        files: merged.files.map(|f| f.specs),
+        include: merged.include.map(|f| f.specs),
+        exclude: merged.exclude.map(|f| f.specs),
+        compiler_options: merged.compiler_options,
+        path: Some(path),
+        extends,
+        references,
+        root_file_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// A scratch project with `files` written (parent directories included), removed on drop.
+    struct Project(PathBuf);
+
+    impl Project {
+        fn new(name: &str, files: &[(&str, &str)]) -> Self {
+            let root = std::env::temp_dir()
+                .join(format!("typeslayer-resolve-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            for (path, contents) in files {
+                let path = root.join(path);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, contents).unwrap();
+            }
+            Self(root)
+        }
+
+        fn resolve(&self, config: &str) -> EffectiveTsconfig {
+            resolve_tsconfig(Some(&self.0.join(config)), "").unwrap()
+        }
+    }
+
+    impl Drop for Project {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn counts_everything_without_include() {
+        let project = Project::new(
+            "default-include",
+            &[
+                ("tsconfig.json", "{}"),
+                ("src/a.ts", ""),
+                ("src/nested/b.tsx", ""),
+                ("src/c.js", ""),
+                ("node_modules/dep/index.ts", ""),
+                (".hidden/d.ts", ""),
+            ],
+        );
+        assert_eq!(project.resolve("tsconfig.json").root_file_count, 2);
+    }
+
+    #[test]
+    fn counts_include_globs_minus_exclude_globs() {
+        let project = Project::new(
+            "include-exclude",
+            &[
+                (
+                    "tsconfig.json",
+                    r#"{ "include": ["src/**/*", "types"], "exclude": ["src/**/*.test.ts", "src/legacy"] }"#,
+                ),
+                ("src/a.ts", ""),
+                ("src/a.test.ts", ""),
+                ("src/legacy/old.ts", ""),
+                ("src/deep/b.ts", ""),
+                ("types/global.d.ts", ""),
+                ("scripts/c.ts", ""),
+            ],
+        );
+        assert_eq!(project.resolve("tsconfig.json").root_file_count, 3);
+    }
+
+    #[test]
+    fn star_does_not_cross_directories() {
+        let project = Project::new(
+            "single-star",
+            &[
+                ("tsconfig.json", r#"{ "include": ["src/*.ts"] }"#),
+                ("src/a.ts", ""),
+                ("src/deep/b.ts", ""),
+            ],
+        );
+        assert_eq!(project.resolve("tsconfig.json").root_file_count, 1);
+    }
+
+    #[test]
+    fn drops_declarations_with_an_implementation() {
+        let project = Project::new(
+            "declarations",
+            &[
+                ("tsconfig.json", "{}"),
+                ("a.ts", ""),
+                ("a.d.ts", ""),
+                ("b.d.ts", ""),
+            ],
+        );
+        assert_eq!(project.resolve("tsconfig.json").root_file_count, 2);
+    }
+
+    #[test]
+    fn counts_js_only_with_allow_js() {
+        let project = Project::new(
+            "allow-js",
+            &[
+                (
+                    "tsconfig.json",
+                    r#"{ "compilerOptions": { "allowJs": true } }"#,
+                ),
+                ("a.ts", ""),
+                ("b.js", ""),
+            ],
+        );
+        assert_eq!(project.resolve("tsconfig.json").root_file_count, 2);
+    }
+
+    #[test]
+    fn includes_are_relative_to_the_declaring_config() {
+        let project = Project::new(
+            "inherited-include",
+            &[
+                ("base/tsconfig.json", r#"{ "include": ["src"] }"#),
+                ("base/src/a.ts", ""),
+                (
+                    "app/tsconfig.json",
+                    r#"{ "extends": "../base/tsconfig.json" }"#,
+                ),
+                ("app/src/b.ts", ""),
+                ("app/src/c.ts", ""),
+            ],
+        );
+        assert_eq!(project.resolve("app/tsconfig.json").root_file_count, 1);
+    }
+
+    #[test]
+    fn excludes_an_out_dir_inherited_from_a_base_config() {
+        let project = Project::new(
+            "inherited-out-dir",
+            &[
+                (
+                    "tsconfig.base.json",
+                    r#"{ "compilerOptions": { "outDir": "./app/dist", "typeRoots": ["./types"] } }"#,
+                ),
+                (
+                    "app/tsconfig.json",
+                    r#"{ "extends": "../tsconfig.base.json" }"#,
+                ),
+                ("app/index.ts", ""),
+                ("app/dist/index.d.ts", ""),
+            ],
+        );
+        let tsconfig = project.resolve("app/tsconfig.json");
+        assert_eq!(tsconfig.root_file_count, 1);
+        assert_eq!(
+            tsconfig.compiler_options["outDir"],
+            to_slashes(&project.0.join("app/dist"))
+        );
+        assert_eq!(
+            tsconfig.compiler_options["typeRoots"],
+            Value::Array(vec![Value::String(to_slashes(&project.0.join("types")))])
+        );
+    }
+}
@@ -0,0 +1,257 @@
+pub mod types;
+
+pub use types::*;
+
+use crate::{
+    analyze_trace::{EventSpanEvent, create_spans},
+    validate::trace_json::{TraceEvent, parse_trace_json},
+};
+use indexmap::IndexMap;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use tracing::{debug, warn};
+
+/// Requests listed per command
+const SLOWEST_REQUESTS_PER_COMMAND: usize = 5;
+
+/// The events of one tsserver process. Editors run more than one, e.g. VS Code's syntax and
+/// semantic servers, and each writes its own trace.
+#[derive(Debug, Clone)]
+pub struct TsserverTrace {
+    pub path: PathBuf,
+    pub events: Vec<TraceEvent>,
+}
+
+/// The traces in a directory tsserver was started with `--traceDirectory` for.
+/// tsserver names them `trace.<pid>.json`, next to a `types.<pid>.json` for each.
+pub fn find_trace_files(trace_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = std::fs::read_dir(trace_dir)
+        .map_err(|e| format!("Failed to read {}: {e}", trace_dir.display()))?;
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("trace") && name.ends_with(".json"))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Every trace in `trace_dir`, and the ones that couldn't be parsed.
+pub fn load_tsserver_trace_dir(
+    trace_dir: &Path,
+) -> Result<(Vec<TsserverTrace>, Vec<SkippedTraceFile>), String> {
+    let paths = find_trace_files(trace_dir)?;
+    if paths.is_empty() {
+        return Err(format!(
+            "No trace.*.json files in {}. Start tsserver with --traceDirectory, or in VS Code turn on \"typescript.tsserver.enableTracing\"",
+            trace_dir.display()
+        ));
+    }
+
+    let mut traces = Vec::new();
+    let mut skipped = Vec::new();
+    for path in paths {
+        match load_tsserver_trace(&path) {
+            Ok(events) => {
+                debug!(
+                    "[load_tsserver_trace_dir] loaded {} events from {}",
+                    events.len(),
+                    path.display()
+                );
+                traces.push(TsserverTrace { path, events });
+            }
+            Err(error) => {
+                warn!(
+                    "[load_tsserver_trace_dir] skipping {}: {error}",
+                    path.display()
+                );
+                skipped.push(SkippedTraceFile { path, error });
+            }
+        }
+    }
+    Ok((traces, skipped))
+}
+
+fn load_tsserver_trace(path: &Path) -> Result<Vec<TraceEvent>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let parsed = match terminate(&contents) {
+        Some(terminated) => parse_trace_json(path.to_path_buf(), terminated.as_bytes()),
+        None => parse_trace_json(path.to_path_buf(), contents.as_bytes()),
+    };
+    parsed.map(|(events, _)| events)
+}
+
+/// A tsserver that's still running (or was killed) leaves its trace without the closing `]`,
+/// maybe with half an event at the end. Events are written a line each, so drop the last line
+/// unless it's complete, and close the array.
+fn terminate(contents: &str) -> Option<String> {
+    let trimmed = contents.trim_end();
+    if trimmed.ends_with(']') {
+        return None;
+    }
+    let complete = if trimmed.ends_with('}') {
+        trimmed
+    } else {
+        &trimmed[..trimmed.rfind('\n').unwrap_or(0)]
+    };
+    let complete = complete.trim_end().trim_end_matches(',');
+    if complete.trim_start() == "[" || complete.is_empty() {
+        return Some("[]".to_string());
+    }
+    Some(format!("{complete}\n]"))
+}
+
+/// Load and analyze every trace in `trace_dir`.
+pub fn analyze_tsserver_trace_dir(
+    trace_dir: &Path,
+    limit: usize,
+) -> Result<TsserverAnalysis, String> {
+    let (traces, skipped) = load_tsserver_trace_dir(trace_dir)?;
+    Ok(analyze_tsserver_traces(trace_dir, &traces, skipped, limit))
+}
+
+#[derive(Default)]
+struct CommandTimings {
+    requests: Vec<RequestTiming>,
+    failed: usize,
+    canceled: usize,
+}
+
+/// Slow requests by command, `updateGraph` cost per project and semantic-check latency per
+/// file, over every trace. `limit` caps the projects and files listed.
+pub fn analyze_tsserver_traces(
+    trace_dir: &Path,
+    traces: &[TsserverTrace],
+    mut skipped: Vec<SkippedTraceFile>,
+    limit: usize,
+) -> TsserverAnalysis {
+    let mut commands: IndexMap<String, CommandTimings> = IndexMap::new();
+    let mut unanswered_requests = 0;
+    let mut update_graph: IndexMap<String, Vec<f64>> = IndexMap::new();
+    let mut semantic_checks: IndexMap<(String, String), Vec<f64>> = IndexMap::new();
+
+    for trace in traces {
+        // requests and responses are instants, matched up by `seq`
+        let mut pending: HashMap<u64, (&str, f64)> = HashMap::new();
+        for event in &trace.events {
+            match event {
+                TraceEvent::Request { common, args, .. } => {
+                    pending.insert(args.seq, (&args.command, common.ts));
+                }
+                TraceEvent::Response { common, args, .. } => {
+                    let Some((command, start)) = pending.remove(&args.seq) else {
+                        continue;
+                    };
+                    let timings = commands.entry(command.to_string()).or_default();
+                    if !args.success {
+                        timings.failed += 1;
+                    }
+                    timings.requests.push(RequestTiming {
+                        seq: args.seq,
+                        duration: (common.ts - start) / 1000.0,
+                        start,
+                        trace_file: trace.path.clone(),
+                    });
+                }
+                TraceEvent::CommandCanceled { args, .. } => {
+                    pending.remove(&args.seq);
+                    commands.entry(args.command.clone()).or_default().canceled += 1;
+                }
+                _ => {}
+            }
+        }
+        unanswered_requests += pending.len();
+
+        let spans = match create_spans(&trace.events) {
+            Ok(parse_result) => parse_result.spans,
+            Err(error) => {
+                skipped.push(SkippedTraceFile {
+                    path: trace.path.clone(),
+                    error,
+                });
+                continue;
+            }
+        };
+        for span in spans {
+            let EventSpanEvent::TraceEvent(event) = &span.event else {
+                continue;
+            };
+            let duration = span.duration / 1000.0;
+            match event {
+                TraceEvent::UpdateGraph { args, .. } => {
+                    update_graph
+                        .entry(args.name.clone())
+                        .or_default()
+                        .push(duration);
+                }
+                TraceEvent::SemanticCheck { args, .. } => {
+                    semantic_checks
+                        .entry((args.file.clone(), args.config_file_path.clone()))
+                        .or_default()
+                        .push(duration);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut commands: Vec<CommandLatency> = commands
+        .into_iter()
+        .map(|(command, mut timings)| {
+            timings
+                .requests
+                .sort_by(|a, b| b.duration.total_cmp(&a.duration));
+            let latency =
+                Latency::from_durations(timings.requests.iter().map(|r| r.duration).collect());
+            timings.requests.truncate(SLOWEST_REQUESTS_PER_COMMAND);
+            CommandLatency {
+                command,
+                latency,
+                failed: timings.failed,
+                canceled: timings.canceled,
+                slowest: timings.requests,
+            }
+        })
+        .collect();
+    commands.sort_by(|a, b| b.latency.total.total_cmp(&a.latency.total));
+
+    let mut update_graph: Vec<ProjectUpdateGraph> = update_graph
+        .into_iter()
+        .map(|(project, durations)| ProjectUpdateGraph {
+            project,
+            latency: Latency::from_durations(durations),
+        })
+        .collect();
+    update_graph.sort_by(|a, b| b.latency.total.total_cmp(&a.latency.total));
+    update_graph.truncate(limit);
+
+    let mut semantic_checks: Vec<FileSemanticCheck> = semantic_checks
+        .into_iter()
+        .map(|((file, config_file_path), durations)| FileSemanticCheck {
+            file,
+            config_file_path,
+            latency: Latency::from_durations(durations),
+        })
+        .collect();
+    semantic_checks.sort_by(|a, b| b.latency.max.total_cmp(&a.latency.max));
+    semantic_checks.truncate(limit);
+
+    TsserverAnalysis {
+        trace_dir: trace_dir.to_path_buf(),
+        trace_files: traces.iter().map(|trace| trace.path.clone()).collect(),
+        skipped,
+        commands,
+        unanswered_requests,
+        update_graph,
+        semantic_checks,
+    }
+}
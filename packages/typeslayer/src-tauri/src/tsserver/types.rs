@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// How long something took, over every time it happened. Times are in milliseconds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Latency {
+    pub count: usize,
+    pub total: f64,
+    pub mean: f64,
+    /// 95th percentile
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl Latency {
+    pub fn from_durations(mut durations: Vec<f64>) -> Self {
+        if durations.is_empty() {
+            return Self::default();
+        }
+        durations.sort_by(f64::total_cmp);
+        let count = durations.len();
+        let total = durations.iter().sum::<f64>();
+        // nearest-rank
+        let p95 = durations[((count as f64 * 0.95).ceil() as usize).clamp(1, count) - 1];
+        Self {
+            count,
+            total,
+            mean: total / count as f64,
+            p95,
+            max: durations[count - 1],
+        }
+    }
+}
+
+/// One request tsserver answered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestTiming {
+    pub seq: u64,
+    /// Milliseconds from the request arriving until the response was sent
+    pub duration: f64,
+    /// Microseconds, on the trace's clock
+    pub start: f64,
+    /// The trace file it's from, since each server process writes its own
+    pub trace_file: PathBuf,
+}
+
+/// Requests of one command, e.g. `completionInfo` or `geterr`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandLatency {
+    pub command: String,
+    pub latency: Latency,
+    /// Responses that reported failure
+    pub failed: usize,
+    pub canceled: usize,
+    /// Slowest first
+    pub slowest: Vec<RequestTiming>,
+}
+
+/// `updateGraph` runs of one project: rebuilding its program after an edit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectUpdateGraph {
+    /// The project's name, which for configured projects is its tsconfig path
+    pub project: String,
+    pub latency: Latency,
+}
+
+/// `semanticCheck` runs of one file: the errors squiggles wait on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSemanticCheck {
+    pub file: String,
+    pub config_file_path: String,
+    pub latency: Latency,
+}
+
+/// A trace file that couldn't be read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedTraceFile {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// What made an editor session slow, from the traces tsserver wrote with `--traceDirectory`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsserverAnalysis {
+    pub trace_dir: PathBuf,
+    pub trace_files: Vec<PathBuf>,
+    pub skipped: Vec<SkippedTraceFile>,
+    /// Most total time first
+    pub commands: Vec<CommandLatency>,
+    /// Requests that never got a response, e.g. because the server was restarted
+    pub unanswered_requests: usize,
+    /// Most total time first
+    pub update_graph: Vec<ProjectUpdateGraph>,
+    /// Slowest single check first
+    pub semantic_checks: Vec<FileSemanticCheck>,
+}
@@ -1,6 +1,6 @@
 use std::{
     borrow::Cow,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
 };
 
 use tauri::{AppHandle, Manager};
@@ -27,10 +27,39 @@ pub fn make_cli_arg(flag: &str, path: &str) -> String {
     format!("{} {}", flag, quote_if_needed(path))
 }
 
+/// Resolve `.` and `..` components without touching the filesystem, so the same file reached
+/// through different relative paths compares equal (and symlinks aren't expanded, unlike `canonicalize`).
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                // `..` at the root is the root
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                // leading `..`s can't be resolved without a base, so they're kept
+                _ => normalized.push(component),
+            },
+            _ => normalized.push(component),
+        }
+    }
+    if normalized.as_os_str().is_empty() {
+        normalized.push(Component::CurDir);
+    }
+    normalized
+}
+
 pub const PACKAGE_JSON_FILENAME: &str = "package.json";
 pub const TSCONFIG_FILENAME: &str = "tsconfig.json";
 pub const OUTPUTS_DIRECTORY: &str = "outputs";
+pub const HISTORY_DIRECTORY: &str = "history";
+pub const EXPERIMENTS_DIRECTORY: &str = "experiments";
 pub const CONFIG_FILENAME: &str = "typeslayer.toml";
+/// Default port for the MCP streamable HTTP transport served from the GUI process.
+pub const DEFAULT_MCP_HTTP_PORT: u16 = 4766;
 
 // Static list of known editors (cmd, human-readable name)
 pub const AVAILABLE_EDITORS: &[(&str, &str)] = &[
@@ -195,3 +224,40 @@ pub fn get_platform() -> String {
     )
     .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize(path: &str) -> PathBuf {
+        normalize_path(Path::new(path))
+    }
+
+    #[test]
+    fn normalize_path_resolves_dots() {
+        assert_eq!(normalize("/a/./b/../c"), PathBuf::from("/a/c"));
+        assert_eq!(normalize("a/b/../../c"), PathBuf::from("c"));
+        assert_eq!(normalize("./a/"), PathBuf::from("a"));
+    }
+
+    #[test]
+    fn normalize_path_keeps_leading_parent_dirs() {
+        assert_eq!(normalize(".."), PathBuf::from(".."));
+        assert_eq!(normalize("../.."), PathBuf::from("../.."));
+        assert_eq!(normalize("../../x"), PathBuf::from("../../x"));
+        assert_eq!(normalize("a/../../x"), PathBuf::from("../x"));
+        assert_eq!(normalize("../a/../b"), PathBuf::from("../b"));
+    }
+
+    #[test]
+    fn normalize_path_stops_at_the_root() {
+        assert_eq!(normalize("/.."), PathBuf::from("/"));
+        assert_eq!(normalize("/a/../../b"), PathBuf::from("/b"));
+    }
+
+    #[test]
+    fn normalize_path_of_nothing_is_the_current_dir() {
+        assert_eq!(normalize(""), PathBuf::from("."));
+        assert_eq!(normalize("a/.."), PathBuf::from("."));
+    }
+}
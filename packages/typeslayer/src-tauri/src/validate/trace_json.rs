@@ -1,7 +1,9 @@
 use std::{io::BufReader, path::PathBuf};
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tracing::{debug, warn};
 
 pub const TRACE_JSON_FILENAME: &str = "trace.json";
 
@@ -937,6 +939,21 @@ pub enum TraceEvent {
         ph: EventPhase,
         args: UpdateGraphArgs,
     },
+    /// An event this version doesn't know, e.g. one a newer TypeScript or tsgo added, or a
+    /// known one whose args changed shape. Kept so it still makes spans.
+    #[serde(untagged)]
+    Unknown {
+        name: String,
+        #[serde(flatten)]
+        common: EventCommon,
+        #[serde(default)]
+        cat: String,
+        ph: EventPhase,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dur: Option<f64>,
+        #[serde(default, skip_serializing_if = "Value::is_null")]
+        args: Value,
+    },
 }
 
 impl TraceEvent {
@@ -1001,11 +1018,12 @@ impl TraceEvent {
             TraceEvent::SuggestionCheck { common, .. } => common,
             TraceEvent::SyntacticCheck { common, .. } => common,
             TraceEvent::UpdateGraph { common, .. } => common,
+            TraceEvent::Unknown { common, .. } => common,
         }
     }
 
     /// Get the event name as a string
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &str {
         match self {
             TraceEvent::TracingStartedInBrowser { .. } => "TracingStartedInBrowser",
             TraceEvent::ProcessName { .. } => "process_name",
@@ -1083,6 +1101,7 @@ impl TraceEvent {
             TraceEvent::SuggestionCheck { .. } => "suggestionCheck",
             TraceEvent::SyntacticCheck { .. } => "syntacticCheck",
             TraceEvent::UpdateGraph { .. } => "updateGraph",
+            TraceEvent::Unknown { name, .. } => name,
         }
     }
 
@@ -1147,6 +1166,7 @@ impl TraceEvent {
             TraceEvent::SuggestionCheck { cat, .. } => cat,
             TraceEvent::SyntacticCheck { cat, .. } => cat,
             TraceEvent::UpdateGraph { cat, .. } => cat,
+            TraceEvent::Unknown { cat, .. } => cat,
         }
     }
 
@@ -1211,6 +1231,7 @@ impl TraceEvent {
             TraceEvent::SuggestionCheck { ph, .. } => ph,
             TraceEvent::SyntacticCheck { ph, .. } => ph,
             TraceEvent::UpdateGraph { ph, .. } => ph,
+            TraceEvent::Unknown { ph, .. } => ph,
         }
     }
 
@@ -1238,16 +1259,29 @@ impl TraceEvent {
             | TraceEvent::TransformNodes { dur, .. }
             | TraceEvent::GetPackageJsonAutoImportProvider { dur, .. }
             | TraceEvent::GetUnresolvedImports { dur, .. } => Some(*dur),
-            TraceEvent::EmitBuildInfo { dur, .. } => *dur,
+            TraceEvent::EmitBuildInfo { dur, .. } | TraceEvent::Unknown { dur, .. } => *dur,
             _ => None,
         }
     }
 }
 
+/// What parsing a trace.json couldn't fully read, by event name, most first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseWarnings {
+    /// Events this version doesn't know, kept as [`TraceEvent::Unknown`]
+    pub unknown: Vec<UnknownEventCount>,
+    /// Events that couldn't be read even as unknown (no `ts` or `ph`, say), which were dropped
+    pub skipped: Vec<UnknownEventCount>,
+}
+
+/// Parse a trace.json. Events this version doesn't know are kept as [`TraceEvent::Unknown`],
+/// and ones that aren't events at all (no `ts` or `ph`, say) are skipped, so a new TypeScript
+/// release doesn't stop the trace loading. Both are counted per name in the [`ParseWarnings`].
 pub fn parse_trace_json(
     path_label: PathBuf,
     reader: impl std::io::Read,
-) -> Result<Vec<TraceEvent>, String> {
+) -> Result<(Vec<TraceEvent>, ParseWarnings), String> {
     let raw: Value = serde_json::from_reader(reader)
         .map_err(|e| format!("Failed to parse {path_label:?}: {e}"))?;
     let arr = match raw {
@@ -1255,15 +1289,83 @@ pub fn parse_trace_json(
         _ => return Err("trace.json root is not an array".to_string()),
     };
     let mut events: Vec<TraceEvent> = Vec::with_capacity(arr.len());
+    let mut skipped: IndexMap<String, usize> = IndexMap::new();
     for (i, v) in arr.into_iter().enumerate() {
-        let ev: TraceEvent =
-            serde_json::from_value(v).map_err(|e| format!("trace.json event[{i}] error: {e}"))?;
-        events.push(ev);
+        let name = v
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string();
+        match serde_json::from_value(v) {
+            Ok(ev) => events.push(ev),
+            Err(e) => {
+                debug!("[parse_trace_json] skipping event[{i}] {name:?}: {e}");
+                *skipped.entry(name).or_default() += 1;
+            }
+        }
+    }
+
+    let unknown = count_unknown_events(&events);
+    for unknown in &unknown {
+        warn!(
+            "[parse_trace_json] {} {:?} events aren't known to this version, keeping them as unknown",
+            unknown.count, unknown.name
+        );
+    }
+    let mut skipped: Vec<UnknownEventCount> = skipped
+        .into_iter()
+        .map(|(name, count)| UnknownEventCount { name, count })
+        .collect();
+    skipped.sort_by_key(|skipped| std::cmp::Reverse(skipped.count));
+    for skipped in &skipped {
+        warn!(
+            "[parse_trace_json] skipped {} {:?} events that couldn't be read",
+            skipped.count, skipped.name
+        );
+    }
+    Ok((events, ParseWarnings { unknown, skipped }))
+}
+
+/// How many events of one name a trace has that this version couldn't fully read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnknownEventCount {
+    pub name: String,
+    pub count: usize,
+}
+
+/// The [`TraceEvent::Unknown`] events of a trace by name, most first.
+pub fn count_unknown_events(events: &[TraceEvent]) -> Vec<UnknownEventCount> {
+    let mut counts: IndexMap<&str, usize> = IndexMap::new();
+    for event in events {
+        if let TraceEvent::Unknown { name, .. } = event {
+            *counts.entry(name).or_default() += 1;
+        }
     }
-    Ok(events)
+    let mut counts: Vec<UnknownEventCount> = counts
+        .into_iter()
+        .map(|(name, count)| UnknownEventCount {
+            name: name.to_string(),
+            count,
+        })
+        .collect();
+    counts.sort_by_key(|unknown| std::cmp::Reverse(unknown.count));
+    counts
 }
 
-pub async fn load_trace_json(path: PathBuf) -> Result<Vec<TraceEvent>, String> {
+/// Parse what's there of a trace.json tsc never finished, e.g. because it crashed.
+///
+/// tsc writes one event per line and the closing `]` last, so every complete line is an
+/// event. Lines that don't parse, like one cut off partway, are skipped.
+pub fn parse_partial_trace_json(text: &str) -> Vec<TraceEvent> {
+    text.lines()
+        .map(|line| line.trim().trim_start_matches('[').trim_matches(','))
+        .filter(|line| line.starts_with('{'))
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+pub async fn load_trace_json(path: PathBuf) -> Result<(Vec<TraceEvent>, ParseWarnings), String> {
     tauri::async_runtime::spawn_blocking(move || {
         let file = std::fs::File::open(&path)
             .map_err(|e| format!("Failed to open trace.json at {path:?}: {e}"))?;
@@ -1272,3 +1374,84 @@ pub async fn load_trace_json(path: PathBuf) -> Result<Vec<TraceEvent>, String> {
     .await
     .map_err(|e| e.to_string())?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(events: Value) -> (Vec<TraceEvent>, ParseWarnings) {
+        parse_trace_json(PathBuf::from("trace.json"), events.to_string().as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn unknown_event_round_trips() {
+        let unknown = json!({
+            "name": "checkSomethingNew",
+            "cat": "check",
+            "ph": "X",
+            "pid": 1,
+            "tid": 1,
+            "ts": 10.0,
+            "dur": 5.0,
+            "args": { "path": "/a.ts", "kind": 3 },
+        });
+        let (events, _) = parse(json!([unknown.clone()]));
+        let [
+            TraceEvent::Unknown {
+                name, dur, args, ..
+            },
+        ] = events.as_slice()
+        else {
+            panic!("expected one unknown event, got {events:?}");
+        };
+        assert_eq!(name, "checkSomethingNew");
+        assert_eq!(*dur, Some(5.0));
+        assert_eq!(args["kind"], 3);
+        assert_eq!(events[0].name(), "checkSomethingNew");
+        assert_eq!(serde_json::to_value(&events[0]).unwrap(), unknown);
+    }
+
+    #[test]
+    fn known_event_with_unknown_args_is_kept_as_unknown() {
+        let (events, warnings) = parse(json!([
+            { "name": "checkSourceFile", "cat": "check", "ph": "B", "pid": 1, "tid": 1, "ts": 1.0, "args": { "renamed": "/a.ts" } },
+        ]));
+        assert!(
+            matches!(&events[0], TraceEvent::Unknown { name, .. } if name == "checkSourceFile")
+        );
+        assert_eq!(warnings.unknown.len(), 1);
+    }
+
+    #[test]
+    fn counts_unknown_and_skipped_events() {
+        let (events, warnings) = parse(json!([
+            { "name": "newThing", "ph": "I", "pid": 1, "tid": 1, "ts": 1.0 },
+            { "name": "newThing", "ph": "I", "pid": 1, "tid": 1, "ts": 2.0 },
+            { "name": "otherThing", "ph": "I", "pid": 1, "tid": 1, "ts": 3.0 },
+            // no `ts`, so not even an unknown event
+            { "name": "broken", "ph": "I", "pid": 1, "tid": 1 },
+            { "ph": "I" },
+        ]));
+        assert_eq!(events.len(), 3);
+        let counts = |counts: &[UnknownEventCount]| {
+            counts
+                .iter()
+                .map(|count| (count.name.clone(), count.count))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            counts(&warnings.unknown),
+            [("newThing".to_string(), 2), ("otherThing".to_string(), 1)]
+        );
+        assert_eq!(
+            counts(&warnings.skipped),
+            [("broken".to_string(), 1), (String::new(), 1)]
+        );
+    }
+
+    #[test]
+    fn rejects_a_root_that_is_not_an_array() {
+        assert!(parse_trace_json(PathBuf::from("trace.json"), "{}".as_bytes()).is_err());
+    }
+}
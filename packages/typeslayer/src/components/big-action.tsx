@@ -4,7 +4,12 @@ import Card from "@mui/material/Card";
 import CardContent from "@mui/material/CardContent";
 import Typography from "@mui/material/Typography";
 import { useEffect, useState } from "react";
-import { type TaskId, useTaskProgress } from "../hooks/tauri-hooks";
+import {
+  type TaskId,
+  useTaskOutput,
+  useTaskProgress,
+  useTaskTraceProgress,
+} from "../hooks/tauri-hooks";
 import { NAVIGATION, type NavigationItem } from "./navigation";
 
 type ItemsWithTitle<
@@ -35,6 +40,8 @@ export function BigAction({
   const theme = useTheme();
   const [now, setNow] = useState(Date.now());
   const { data: taskProgress } = useTaskProgress(taskId);
+  const { data: taskOutput } = useTaskOutput(taskId);
+  const { data: traceProgress } = useTaskTraceProgress(taskId);
 
   const isLoading = taskProgress && !taskProgress.done;
 
@@ -147,6 +154,33 @@ export function BigAction({
               })}
             </Stack>
           </Stack>
+
+          {isLoading && (traceProgress || taskOutput) && (
+            <Stack gap={0.5}>
+              {traceProgress && (
+                <Typography variant="body2" sx={{ color: "text.secondary" }}>
+                  {traceProgress.filesChecked.toLocaleString()} files checked,
+                  trace.json is{" "}
+                  {(traceProgress.traceJsonSize / 1024 / 1024).toFixed(1)} MB
+                </Typography>
+              )}
+              {taskOutput && (
+                <Typography
+                  variant="body2"
+                  noWrap
+                  sx={{
+                    fontFamily: "monospace",
+                    color:
+                      taskOutput.stream === "stderr"
+                        ? "error.main"
+                        : "text.secondary",
+                  }}
+                >
+                  {taskOutput.line}
+                </Typography>
+              )}
+            </Stack>
+          )}
         </Stack>
       </CardContent>
     </Card>
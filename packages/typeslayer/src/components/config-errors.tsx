@@ -0,0 +1,32 @@
+import { Alert, AlertTitle, Typography } from "@mui/material";
+import { InlineCode } from "@typeslayer/common";
+import { useConfigErrors } from "../hooks/tauri-hooks";
+
+/** Settings that were set but invalid, so a lower layer or the default was used instead. */
+export const ConfigErrors = () => {
+  const { data: errors } = useConfigErrors();
+
+  if (!errors || errors.length === 0) {
+    return null;
+  }
+
+  return (
+    <Alert severity="warning">
+      <AlertTitle>
+        {errors.length === 1
+          ? "1 setting was ignored"
+          : `${errors.length} settings were ignored`}
+      </AlertTitle>
+      {errors.map(error => (
+        <Typography
+          key={`${error.key}-${error.source}-${error.location}`}
+          variant="body2"
+        >
+          <InlineCode>{error.location}</InlineCode>{" "}
+          {error.value && <>= {JSON.stringify(error.value)} </>}({error.source}
+          ): {error.reason}
+        </Typography>
+      ))}
+    </Alert>
+  );
+};
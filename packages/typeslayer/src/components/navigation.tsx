@@ -1,16 +1,22 @@
 import AutoAwesome from "@mui/icons-material/AutoAwesome";
 import AutoStories from "@mui/icons-material/AutoStories";
 import Biotech from "@mui/icons-material/Biotech";
+import Construction from "@mui/icons-material/Construction";
 import Dashboard from "@mui/icons-material/Dashboard";
 import Description from "@mui/icons-material/Description";
 import Dvr from "@mui/icons-material/Dvr";
+import EditNote from "@mui/icons-material/EditNote";
 import EmojiEvents from "@mui/icons-material/EmojiEvents";
 import Hub from "@mui/icons-material/Hub";
+import Memory from "@mui/icons-material/Memory";
 import MilitaryTech from "@mui/icons-material/MilitaryTech";
+import Output from "@mui/icons-material/Output";
 import PlayCircle from "@mui/icons-material/PlayCircle";
+import Science from "@mui/icons-material/Science";
 import Search from "@mui/icons-material/Search";
 import Settings from "@mui/icons-material/Settings";
 import Speed from "@mui/icons-material/Speed";
+import Timeline from "@mui/icons-material/Timeline";
 import {
   Box,
   Divider,
@@ -91,12 +97,48 @@ export const NAVIGATION = [
     title: "Treemap",
     icon: <Dashboard />,
   },
+  {
+    kind: "segment",
+    segment: "history",
+    title: "History",
+    icon: <Timeline />,
+  },
+  {
+    kind: "segment",
+    segment: "experiments",
+    title: "Experiments",
+    icon: <Science />,
+  },
   {
     kind: "segment",
     segment: "perfetto",
     title: "Perfetto",
     icon: <Speed />,
   },
+  {
+    kind: "segment",
+    segment: "program-construction",
+    title: "Program Construction",
+    icon: <Construction />,
+  },
+  {
+    kind: "segment",
+    segment: "emit",
+    title: "Emit",
+    icon: <Output />,
+  },
+  {
+    kind: "segment",
+    segment: "cpu-profile",
+    title: "CPU Profile",
+    icon: <Memory />,
+  },
+  {
+    kind: "segment",
+    segment: "tsserver",
+    title: "Editor (tsserver)",
+    icon: <EditNote />,
+  },
   {
     kind: "segment",
     segment: "speedscope",
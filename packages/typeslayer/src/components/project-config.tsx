@@ -0,0 +1,67 @@
+import { Alert, AlertTitle, Stack, Typography } from "@mui/material";
+import { InlineCode } from "@typeslayer/common";
+import {
+  type BudgetCheck,
+  useBudgetChecks,
+  useProjectConfig,
+} from "../hooks/tauri-hooks";
+
+const formatActual = (actual: number | null) =>
+  actual === null ? "not generated yet" : Math.round(actual).toLocaleString();
+
+const formatSpread = ({ ciLow, ciHigh, singleSample }: BudgetCheck) => {
+  if (ciLow !== null && ciHigh !== null) {
+    return ` (95% CI ${Math.round(ciLow).toLocaleString()} to ${Math.round(ciHigh).toLocaleString()})`;
+  }
+  return singleSample ? " (one run, so noise can tip it either way)" : "";
+};
+
+/** The project's own typeslayer.toml: what it set, and how the outputs measure up to its budgets. */
+export const ProjectConfig = () => {
+  const { data: projectConfig } = useProjectConfig();
+  const { data: budgetChecks } = useBudgetChecks();
+
+  if (!projectConfig?.path) {
+    return null;
+  }
+
+  const exceeded = budgetChecks?.filter(check => check.exceeded) ?? [];
+
+  return (
+    <Alert severity={exceeded.length > 0 ? "warning" : "info"}>
+      <AlertTitle>
+        Using the project config <InlineCode>{projectConfig.path}</InlineCode>
+      </AlertTitle>
+      <Stack gap={1}>
+        <Typography variant="body2">
+          {projectConfig.applied.length === 0 ? (
+            "It doesn't set anything that isn't already set by an env var or flag."
+          ) : (
+            <>
+              It sets{" "}
+              {projectConfig.applied.map((key, index) => (
+                <span key={key}>
+                  {index > 0 && ", "}
+                  <InlineCode>{key}</InlineCode>
+                </span>
+              ))}
+              , which take precedence over your own settings.
+            </>
+          )}
+        </Typography>
+        {budgetChecks?.map(check => (
+          <Typography
+            key={check.budget}
+            variant="body2"
+            color={check.exceeded ? "error" : "textPrimary"}
+          >
+            <InlineCode>budgets.{check.budget}</InlineCode>{" "}
+            {formatActual(check.actual)} of {check.limit.toLocaleString()}
+            {formatSpread(check)}
+            {check.exceeded && " (over budget)"}
+          </Typography>
+        ))}
+      </Stack>
+    </Alert>
+  );
+};
@@ -8,7 +8,10 @@ import {
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import type { AnalyzeTraceResult } from "@typeslayer/analyze-trace/browser";
-import type { StrippedLinkKindData } from "@typeslayer/rust-types";
+import type {
+  DepthLimitKind,
+  StrippedLinkKindData,
+} from "@typeslayer/rust-types";
 import {
   extractPackageName,
   type Flag,
@@ -34,7 +37,10 @@ export type TaskId =
   | "generate_trace"
   | "generate_cpu_profile"
   | "generate_analyze_trace"
-  | "generate_type_graph";
+  | "generate_type_graph"
+  | "bisect"
+  | "experiment"
+  | "repeated_runs";
 
 export type TaskProgress = {
   taskId: TaskId;
@@ -42,18 +48,36 @@ export type TaskProgress = {
   done: boolean;
 };
 
+export type TaskOutputLine = {
+  taskId: TaskId;
+  stream: "stdout" | "stderr";
+  line: string;
+};
+
+export type TaskTraceProgress = {
+  taskId: TaskId;
+  traceJsonSize: number;
+  filesChecked: number;
+  elapsedMs: number;
+};
+
 export function useTaskProgressEvents() {
   const queryClient = useQueryClient();
 
   useEffect(() => {
     let unlisten: null | (() => void) = null;
 
+    let unlistenOutput: null | (() => void) = null;
+    let unlistenTraceProgress: null | (() => void) = null;
+
     (async () => {
       unlisten = await listen<TaskProgress>(
         "tasks",
         ({ payload: { taskId, start, done } }) => {
           if (done) {
             queryClient.invalidateQueries({ queryKey: ["task", taskId] });
+            queryClient.setQueryData(["task-output", taskId], null);
+            queryClient.setQueryData(["task-trace-progress", taskId], null);
           } else {
             queryClient.setQueryData(["task", taskId], {
               taskId,
@@ -63,10 +87,27 @@ export function useTaskProgressEvents() {
           }
         },
       );
+      unlistenOutput = await listen<TaskOutputLine>(
+        "task-output",
+        ({ payload }) => {
+          queryClient.setQueryData(["task-output", payload.taskId], payload);
+        },
+      );
+      unlistenTraceProgress = await listen<TaskTraceProgress>(
+        "task-trace-progress",
+        ({ payload }) => {
+          queryClient.setQueryData(
+            ["task-trace-progress", payload.taskId],
+            payload,
+          );
+        },
+      );
     })();
 
     return () => {
       unlisten?.();
+      unlistenOutput?.();
+      unlistenTraceProgress?.();
     };
   }, [queryClient]);
 }
@@ -80,6 +121,25 @@ export function useTaskProgress(taskId: TaskId) {
   });
 }
 
+/** the most recent line of output from a running task */
+export function useTaskOutput(taskId: TaskId) {
+  return useQuery<TaskOutputLine | null>({
+    queryKey: ["task-output", taskId],
+    queryFn: () => null,
+    initialData: null,
+    staleTime: Number.POSITIVE_INFINITY,
+  });
+}
+
+export function useTaskTraceProgress(taskId: TaskId) {
+  return useQuery<TaskTraceProgress | null>({
+    queryKey: ["task-trace-progress", taskId],
+    queryFn: () => null,
+    initialData: null,
+    staleTime: Number.POSITIVE_INFINITY,
+  });
+}
+
 export function useAllTaskProgress() {
   const taskIds: TaskId[] = [
     "generate_trace",
@@ -253,6 +313,7 @@ export function useApplyTscProjectFlag() {
       invoke<void>("set_apply_tsc_project_flag", { value }),
     onSettled: () => {
       queryClient.invalidateQueries({ queryKey: ["get_tsc_example_call"] });
+      queryClient.invalidateQueries({ queryKey: ["effective_tsconfig"] });
     },
     onSuccess: (_, value) => {
       queryClient.setQueryData(["apply_tsc_project_flag"], value);
@@ -314,6 +375,7 @@ export function useExtraTscFlags() {
       invoke<void>("set_extra_tsc_flags", { flags }),
     onSettled: () => {
       queryClient.invalidateQueries({ queryKey: ["get_tsc_example_call"] });
+      queryClient.invalidateQueries({ queryKey: ["effective_tsconfig"] });
     },
     onSuccess: (_, flags) => {
       queryClient.setQueryData(["extra_tsc_flags"], flags);
@@ -339,6 +401,421 @@ export function useAvailableEditors() {
   });
 }
 
+/** a setting that was invalid and fell back to the next layer (or the default) */
+export type ConfigError = {
+  /** named like the env var without its prefix, e.g. `MAX_NODES` */
+  key: string;
+  source: "env" | "flag" | "project" | "file";
+  /** the env var, flag, or `typeslayer.toml: key.path` */
+  location: string;
+  value: string;
+  reason: string;
+};
+
+export const useConfigErrors = () => {
+  return useQuery({
+    queryKey: ["config_errors"],
+    queryFn: () => invoke<ConfigError[]>("get_config_errors"),
+  });
+};
+
+export type ProjectConfig = {
+  /** the project's own typeslayer.toml, found in the project root or above it */
+  path: string | null;
+  /** key paths whose value came from that file */
+  applied: string[];
+  thresholds: {
+    forceMillis: number;
+    skipMillis: number;
+    expandTypes: boolean;
+    minSpanParentPercentage: number;
+    importExpressionThreshold: number;
+  };
+  budgets: {
+    totalCheckTimeMs: number | null;
+    typeCount: number | null;
+    hotSpotCount: number | null;
+    depthLimitHits: number | null;
+  };
+};
+
+export const useProjectConfig = () => {
+  return useQuery({
+    queryKey: ["project_config"],
+    queryFn: () => invoke<ProjectConfig>("get_project_config"),
+  });
+};
+
+export type BudgetCheck = {
+  /** the key in the `budgets` table, e.g. `typeCount` */
+  budget: string;
+  limit: number;
+  /** null when the output it's measured from hasn't been generated. for a timing with repeated runs, their mean */
+  actual: number | null;
+  /** 95% confidence interval of a timing over repeated runs, which has to be entirely over the limit to exceed it */
+  ciLow: number | null;
+  ciHigh: number | null;
+  /** a timing judged from the loaded trace alone because there are no repeated runs */
+  singleSample: boolean;
+  exceeded: boolean;
+};
+
+export const useBudgetChecks = () => {
+  return useQuery({
+    queryKey: ["budget_checks"],
+    queryFn: () => invoke<BudgetCheck[]>("get_budget_checks"),
+  });
+};
+
+/** Times are in milliseconds */
+export type FunctionTime = {
+  functionName: string;
+  url: string;
+  lineNumber: number;
+  columnNumber: number;
+  selfTime: number;
+  totalTime: number;
+};
+
+export type CheckerGroupTime = {
+  group: string;
+  functions: string[];
+  selfTime: number;
+  totalTime: number;
+  /** totalTime as a fraction of the whole profile */
+  share: number;
+};
+
+export type CpuProfileSummary = {
+  totalTime: number;
+  sampleCount: number;
+  sampleInterval: number;
+  checkerGroups: CheckerGroupTime[];
+  functions: FunctionTime[];
+};
+
+export type CallTreeNode = {
+  functionName: string;
+  url: string;
+  lineNumber: number;
+  selfTime: number;
+  totalTime: number;
+  prunedChildren: number;
+  children: CallTreeNode[];
+};
+
+export type CallTreeOptions = {
+  maxDepth: number;
+  /** leave out nodes under this fraction of the whole profile */
+  minShare: number;
+};
+
+export const useCpuProfileSummary = (limit: number) => {
+  return useQuery({
+    queryKey: ["cpu_profile_summary", limit],
+    queryFn: () =>
+      invoke<CpuProfileSummary>("get_cpu_profile_summary", { limit }),
+    retry: false,
+  });
+};
+
+export const useCpuProfileCallTree = (
+  direction: "top_down" | "bottom_up",
+  options: CallTreeOptions,
+) => {
+  return useQuery({
+    queryKey: ["cpu_profile_call_tree", direction, options],
+    queryFn: () =>
+      invoke<CallTreeNode[]>(`get_cpu_profile_${direction}`, { options }),
+    retry: false,
+  });
+};
+
+export type SpanFunctionTime = {
+  functionName: string;
+  url: string;
+  lineNumber: number;
+  selfTime: number;
+  share: number;
+};
+
+export type HotSpotProfile = {
+  description: string;
+  path?: string;
+  types?: number[];
+  duration: number;
+  sampledTime: number;
+  sampleCount: number;
+  functions: SpanFunctionTime[];
+  checkerGroups: { group: string; time: number; share: number }[];
+  gcShare: number;
+  summary: string;
+  children: HotSpotProfile[];
+};
+
+export type TraceProfileCorrelation = {
+  offset: number;
+  alignment: number;
+  warning?: string;
+  hotSpots: HotSpotProfile[];
+};
+
+export const useHotSpotProfiles = () => {
+  return useQuery({
+    queryKey: ["hot_spot_profiles"],
+    queryFn: () => invoke<TraceProfileCorrelation>("get_hot_spot_profiles"),
+    retry: false,
+  });
+};
+
+/** times in milliseconds */
+export type PhaseTimes = {
+  parse: number;
+  bind: number;
+  resolve: number;
+  total: number;
+};
+
+export type ProgramConstructionReport = {
+  programs: { configFilePath: string; duration: number }[];
+  createProgram: number;
+  totals: PhaseTimes;
+  project: PhaseTimes;
+  fileCount: number;
+  rootFiles: number;
+  files: {
+    path: string;
+    package: string | null;
+    includeKind: string | null;
+    times: PhaseTimes;
+  }[];
+  packages: { package: string; files: number; times: PhaseTimes }[];
+  includeKinds: { includeKind: string; files: number }[];
+  typeReferenceDirectiveFiles: number;
+  libraryFiles: number;
+  typeReferenceDirectives: {
+    directive: string;
+    count: number;
+    resolved: boolean;
+    time: number;
+  }[];
+  unresolvedTypeReferenceDirectives: number;
+  resolveLibraryCount: number;
+  resolveLibraryTime: number;
+};
+
+export type IncludedFile = {
+  file: string;
+  reason: string;
+  referencedBy: string | null;
+  directive: string | null;
+  referencingFiles: number;
+  pulledIn: number;
+  resolutionTime: number;
+};
+
+export type InclusionExplanation = {
+  file: IncludedFile;
+  chain: IncludedFile[];
+  referencedBy: string[];
+};
+
+/** why `file` (or a path ending in it) is in the program */
+export const useWhyIsFileIncluded = (file: string) => {
+  return useQuery({
+    queryKey: ["why_is_file_included", file],
+    queryFn: () =>
+      invoke<InclusionExplanation>("why_is_file_included", { file }),
+    enabled: file !== "",
+    retry: false,
+  });
+};
+
+export const useProgramConstruction = () => {
+  return useQuery({
+    queryKey: ["program_construction"],
+    queryFn: () =>
+      invoke<ProgramConstructionReport>("get_program_construction"),
+    retry: false,
+  });
+};
+
+export type CrashDiagnosis = {
+  killed: string | null;
+  failures: {
+    kind: "heapOutOfMemory" | "stackOverflow" | "excessivelyDeep";
+    line: string;
+  }[];
+  /** outermost first. `openFor` is in milliseconds */
+  openStack: { event: TraceEvent; openFor: number }[];
+  checkingFile: string | null;
+  lastChecked: CheckSite | null;
+  suggestions: {
+    setting: "maxOldSpaceSize" | "maxStackSize";
+    current: number | null;
+    suggested: number;
+    reason: string;
+  }[];
+  traceEvents: number;
+};
+
+/** what tsc was doing when the last trace generation crashed */
+export const useCrashDiagnosis = () => {
+  return useQuery({
+    queryKey: ["crash_diagnosis"],
+    queryFn: () => invoke<CrashDiagnosis | null>("get_crash_diagnosis"),
+  });
+};
+
+/** a node the checker was on. `start` is 1-based, `null` when the file can't be read */
+export type CheckSite = {
+  path: string;
+  pos: number | null;
+  end: number | null;
+  start: { line: number; character: number } | null;
+};
+
+export type DepthLimitType = {
+  id: number;
+  name: string | null;
+  declaration: {
+    path: string;
+    start: { line: number; character: number };
+    end: { line: number; character: number };
+  } | null;
+};
+
+/** depth limit events on the same types, merged */
+export type ResolvedDepthLimit = {
+  kind: DepthLimitKind;
+  /** the biggest of the merged events */
+  event: TraceEvent;
+  magnitude: number;
+  count: number;
+  types: DepthLimitType[];
+  sites: CheckSite[];
+};
+
+/** biggest first within each kind */
+export const useDepthLimitReport = () => {
+  return useQuery({
+    queryKey: ["depth_limit_report"],
+    queryFn: () =>
+      invoke<Record<DepthLimitKind, ResolvedDepthLimit[]>>(
+        "get_depth_limit_report",
+      ),
+    retry: false,
+  });
+};
+
+/** times in milliseconds */
+export type EmitReport = {
+  total: number;
+  declaration: number;
+  js: number;
+  buildInfo: number;
+  transform: number;
+  declarationChecker: number;
+  declarationFileCount: number;
+  jsFileCount: number;
+  declarationFiles: {
+    declarationFilePath: string;
+    duration: number;
+    transform: number;
+    checker: number;
+    typeIds: number[];
+  }[];
+  jsFiles: {
+    jsFilePath: string | null;
+    duration: number;
+    transform: number;
+  }[];
+  transforms: {
+    path: string;
+    js: number;
+    declaration: number;
+    total: number;
+  }[];
+  types: {
+    typeId: number;
+    name: string | null;
+    time: number;
+    count: number;
+    declarationFiles: string[];
+  }[];
+};
+
+export const useEmitReport = () => {
+  return useQuery({
+    queryKey: ["emit_report"],
+    queryFn: () => invoke<EmitReport>("get_emit_report"),
+    retry: false,
+  });
+};
+
+/** times in milliseconds */
+export type Latency = {
+  count: number;
+  total: number;
+  mean: number;
+  p95: number;
+  max: number;
+};
+
+export type TsserverAnalysis = {
+  traceDir: string;
+  traceFiles: string[];
+  skipped: { path: string; error: string }[];
+  commands: {
+    command: string;
+    latency: Latency;
+    failed: number;
+    canceled: number;
+    slowest: {
+      seq: number;
+      duration: number;
+      start: number;
+      traceFile: string;
+    }[];
+  }[];
+  unansweredRequests: number;
+  updateGraph: { project: string; latency: Latency }[];
+  semanticChecks: {
+    file: string;
+    configFilePath: string;
+    latency: Latency;
+  }[];
+};
+
+/** analyze the traces tsserver wrote to `traceDir` (from `--traceDirectory`) */
+export const useTsserverAnalysis = (traceDir: string | null) => {
+  return useQuery({
+    queryKey: ["tsserver_analysis", traceDir],
+    queryFn: () =>
+      invoke<TsserverAnalysis>("analyze_tsserver_trace", { traceDir }),
+    enabled: traceDir !== null,
+    retry: false,
+  });
+};
+
+export type DetectedPackageManager = {
+  kind: "bun" | "deno" | "npm" | "pnpm" | "yarn";
+  /** from the `packageManager` field */
+  version: string | null;
+  yarnBerry: boolean;
+  yarnPnp: boolean;
+  /** where the deciding `packageManager` field or lockfile is */
+  root: string | null;
+  evidence: { path: string; finding: string }[];
+};
+
+export const usePackageManager = () => {
+  return useQuery({
+    queryKey: ["package_manager"],
+    queryFn: () => invoke<DetectedPackageManager>("get_package_manager"),
+  });
+};
+
 export function useProjectRoot() {
   const queryClient = useQueryClient();
 
@@ -352,9 +829,8 @@ export function useProjectRoot() {
     mutationFn: (projectRoot: string) =>
       invoke<void>("set_project_root", { projectRoot }),
     onSettled: () => {
-      queryClient.invalidateQueries({ queryKey: ["tsconfig_paths"] });
-      queryClient.invalidateQueries({ queryKey: ["selected_tsconfig"] });
-      queryClient.invalidateQueries({ queryKey: ["get_tsc_example_call"] });
+      // each project has its own workspace, so every output may have changed
+      queryClient.invalidateQueries();
     },
     onSuccess: (_, projectRoot) => {
       queryClient.setQueryData(["project_root"], projectRoot);
@@ -457,10 +933,89 @@ export function useTsconfigPaths() {
   });
 }
 
+export type OwningPackage = {
+  name: string | null;
+  dir: string;
+  workspaceMember: boolean;
+};
+
+export type TsconfigNode = {
+  path: string;
+  package: OwningPackage | null;
+  /** every config this one inherits from, nearest first */
+  extends: string[];
+  references: TsconfigNode[];
+};
+
+export function useTsconfigTree() {
+  return useQuery({
+    queryKey: ["tsconfig_tree"],
+    queryFn: () => invoke<TsconfigNode[]>("get_tsconfig_tree"),
+    staleTime: Number.POSITIVE_INFINITY,
+  });
+}
+
+export type PerformanceOptions = {
+  strict: boolean;
+  skipLibCheck: boolean;
+  isolatedDeclarations: boolean;
+  incremental: boolean;
+  /** `null` means every package under `node_modules/@types` is included */
+  types: string[] | null;
+};
+
+export type EffectiveTsconfig = {
+  path: string | null;
+  extends: string[];
+  references: string[];
+  files: string[] | null;
+  include: string[] | null;
+  exclude: string[] | null;
+  compilerOptions: Record<string, unknown>;
+  performance: PerformanceOptions;
+  rootFileCount: number;
+};
+
+/** the config tsc would read right now, with the current selection and flags */
+export function useEffectiveTsconfig() {
+  return useQuery({
+    queryKey: ["effective_tsconfig"],
+    queryFn: () => invoke<EffectiveTsconfig>("resolve_effective_tsconfig"),
+    staleTime: Number.POSITIVE_INFINITY,
+  });
+}
+
+export type RunMetadata = {
+  startedAt: string;
+  /** milliseconds from starting tsc until it exited */
+  duration: number;
+  tscVersion: string | null;
+  compilerVariant: string;
+  flags: string[];
+  extraTscFlags: string;
+  tsconfig: string | null;
+  maxOldSpaceSize: number | null;
+  maxStackSize: number | null;
+  command: string;
+  /** the files this run wrote */
+  outputs: string[];
+};
+
+/** the tsc run the loaded outputs came from */
+export function useRunMetadata() {
+  return useQuery({
+    queryKey: ["run_metadata"],
+    queryFn: () => invoke<RunMetadata | null>("get_run_metadata"),
+  });
+}
+
 const refreshGenerateTraceInvalidations = new Set([
   "analyze_trace",
   "bug_report_files",
   "bug_report_files",
+  "crash_diagnosis",
+  "depth_limit_report",
+  "emit_report",
   "get_analyze_trace_preview",
   "get_app_stats",
   "get_links_to_type_id",
@@ -468,11 +1023,15 @@ const refreshGenerateTraceInvalidations = new Set([
   "get_trace_json_preview",
   "get_type_graph_preview",
   "get_types_json_preview",
+  "program_construction",
   "resolved_type",
+  "run_metadata",
   "trace_json",
   "type_graph_node_and_link_stats",
   "type_graph_nodes_and_links",
   "type_graph_stats",
+  "unknown_trace_events",
+  "why_is_file_included",
 ]);
 
 const refreshGenerateTrace = (queryClient: QueryClient) => async () => {
@@ -512,6 +1071,7 @@ export function useUploadTypes() {
 
 const refreshCpuProfileInvalidations = new Set([
   "cpu_profile",
+  "run_metadata",
   "get_cpu_profile_preview",
   "get_output_file_sizes",
   "bug_report_files",
@@ -547,6 +1107,9 @@ const refreshAnalyzeTraceInvalidations = new Set([
   "get_analyze_trace_preview",
   "get_output_file_sizes",
   "bug_report_files",
+  // a fresh analysis is archived as a snapshot
+  "snapshots",
+  "snapshot_time_series",
 ]);
 
 const refreshAnalyzeTrace = (queryClient: QueryClient) => async () => {
@@ -608,6 +1171,12 @@ export const useGenerateAll = () => {
     onMutate: async () => {
       queryClient.invalidateQueries();
     },
+    onSettled: () => {
+      // every run is archived as a snapshot
+      queryClient.invalidateQueries({ queryKey: ["snapshots"] });
+      queryClient.invalidateQueries({ queryKey: ["snapshot_time_series"] });
+      queryClient.invalidateQueries({ queryKey: ["run_metadata"] });
+    },
   });
 };
 
@@ -634,7 +1203,8 @@ export function useSelectedTsconfig() {
     mutationFn: (tsconfigPath: string) =>
       invoke<void>("set_selected_tsconfig", { tsconfigPath }),
     onSettled: () => {
-      queryClient.invalidateQueries({ queryKey: ["get_tsc_example_call"] });
+      // each tsconfig has its own workspace, so every output may have changed
+      queryClient.invalidateQueries();
     },
     onSuccess: (_, tsconfigPath) => {
       queryClient.setQueryData(["selected_tsconfig"], tsconfigPath || null);
@@ -790,6 +1360,13 @@ export const useDataDir = () => {
   });
 };
 
+export const useOutputsDir = () => {
+  return useQuery({
+    queryKey: ["outputs_dir"],
+    queryFn: () => invoke<string>("get_outputs_dir"),
+  });
+};
+
 export const useMaxOldSpaceSize = () => {
   const queryClient = useQueryClient();
 
@@ -916,6 +1493,24 @@ export const useValidateAnalyzeTrace = () => {
   });
 };
 
+export type TraceEventCount = { name: string; count: number };
+
+/** what parsing the loaded trace couldn't fully read, by event name, most first */
+export type TraceParseWarnings = {
+  /** events this version doesn't know, kept without their details */
+  unknown: TraceEventCount[];
+  /** events that couldn't be read at all, which were dropped */
+  skipped: TraceEventCount[];
+};
+
+/** events in the loaded trace this version doesn't know or couldn't read */
+export const useUnknownTraceEvents = () => {
+  return useQuery({
+    queryKey: ["unknown_trace_events"],
+    queryFn: () => invoke<TraceParseWarnings>("get_unknown_trace_events"),
+  });
+};
+
 export const useValidateTraceJson = () => {
   const queryClient = useQueryClient();
 
@@ -962,7 +1557,9 @@ export const useValidateCpuProfile = () => {
 
 export const useCancelGeneration = () => {
   return useMutation({
-    mutationFn: async () => invoke<void>("cancel_generation"),
+    /** cancels the given task, or every running task when omitted */
+    mutationFn: async (taskId?: TaskId) =>
+      invoke<void>("cancel_generation", { taskId: taskId ?? null }),
   });
 };
 
@@ -1127,3 +1724,385 @@ export const useVersion = () => {
     staleTime: Number.POSITIVE_INFINITY,
   });
 };
+
+export type WorkspaceInfo = {
+  id: string;
+  projectRoot: string;
+  tsconfig: string | null;
+  createdAt: string;
+  lastUsedAt: string;
+  version: string;
+  active: boolean;
+  outputsSize: number;
+};
+
+/** every known workspace, most recently used first */
+export const useWorkspaces = () => {
+  return useQuery({
+    queryKey: ["workspaces"],
+    queryFn: () => invoke<WorkspaceInfo[]>("get_workspaces"),
+  });
+};
+
+export const useSwitchWorkspace = () => {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => invoke<void>("switch_workspace", { id }),
+    onSettled: () => {
+      queryClient.invalidateQueries();
+    },
+  });
+};
+
+export const useDeleteWorkspace = () => {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => invoke<void>("delete_workspace", { id }),
+    onSettled: () => {
+      queryClient.invalidateQueries();
+    },
+  });
+};
+
+export const usePruneWorkspaces = () => {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: () => invoke<string[]>("prune_workspaces"),
+    onSettled: () => {
+      queryClient.invalidateQueries({ queryKey: ["workspaces"] });
+    },
+  });
+};
+
+export const useMaxWorkspaces = () => {
+  const queryClient = useQueryClient();
+
+  const query = useQuery({
+    queryKey: ["max_workspaces"],
+    queryFn: () => invoke<number>("get_max_workspaces"),
+    staleTime: Number.POSITIVE_INFINITY,
+  });
+
+  const mutation = useMutation({
+    mutationFn: (maxWorkspaces: number) =>
+      invoke<void>("set_max_workspaces", { maxWorkspaces }),
+    onSuccess: (_, maxWorkspaces) => {
+      queryClient.setQueryData(["max_workspaces"], maxWorkspaces);
+    },
+  });
+
+  return {
+    data: query.data,
+    isLoading: query.isLoading,
+    error: query.error,
+    set: mutation.mutateAsync,
+    isSettingValue: mutation.isPending,
+  };
+};
+
+export const useWorkspaceRetentionDays = () => {
+  const queryClient = useQueryClient();
+
+  const query = useQuery({
+    queryKey: ["workspace_retention_days"],
+    queryFn: () => invoke<number>("get_workspace_retention_days"),
+    staleTime: Number.POSITIVE_INFINITY,
+  });
+
+  const mutation = useMutation({
+    mutationFn: (days: number) =>
+      invoke<void>("set_workspace_retention_days", { days }),
+    onSuccess: (_, days) => {
+      queryClient.setQueryData(["workspace_retention_days"], days);
+    },
+  });
+
+  return {
+    data: query.data,
+    isLoading: query.isLoading,
+    error: query.error,
+    set: mutation.mutateAsync,
+    isSettingValue: mutation.isPending,
+  };
+};
+
+export const useMaxSnapshots = () => {
+  const queryClient = useQueryClient();
+
+  const query = useQuery({
+    queryKey: ["max_snapshots"],
+    queryFn: () => invoke<number>("get_max_snapshots"),
+    staleTime: Number.POSITIVE_INFINITY,
+  });
+
+  const mutation = useMutation({
+    mutationFn: (maxSnapshots: number) =>
+      invoke<void>("set_max_snapshots", { maxSnapshots }),
+    onSuccess: (_, maxSnapshots) => {
+      queryClient.setQueryData(["max_snapshots"], maxSnapshots);
+    },
+  });
+
+  return {
+    data: query.data,
+    isLoading: query.isLoading,
+    error: query.error,
+    set: mutation.mutateAsync,
+    isSettingValue: mutation.isPending,
+  };
+};
+
+export type SnapshotSummary = {
+  id: string;
+  createdAt: string;
+  workspaceId: string;
+  projectRoot: string;
+  tsconfig: string | null;
+  gitCommit: string | null;
+  gitDirty: boolean;
+  version: string;
+  compilerVariant: string;
+  /** microseconds */
+  totalCheckTime: number;
+  depthLimitCounts: Partial<Record<DepthLimitKind, number>>;
+  duplicatePackages: number;
+  nodeCount: number | null;
+  linkCount: number | null;
+  effectiveTsconfig: EffectiveTsconfig | null;
+  tscVersion: string | null;
+};
+
+export type SnapshotTimeSeries = {
+  ids: string[];
+  timestamps: string[];
+  gitCommits: (string | null)[];
+  totalCheckTime: number[];
+  depthLimitCounts: Partial<Record<DepthLimitKind, number[]>>;
+  duplicatePackages: number[];
+  nodeCount: (number | null)[];
+  linkCount: (number | null)[];
+};
+
+const refreshSnapshots = (queryClient: QueryClient) => async () => {
+  queryClient.invalidateQueries({ queryKey: ["snapshots"] });
+  queryClient.invalidateQueries({ queryKey: ["snapshot_time_series"] });
+};
+
+/** snapshots of the active workspace, oldest first */
+export const useSnapshots = () => {
+  return useQuery({
+    queryKey: ["snapshots"],
+    queryFn: () => invoke<SnapshotSummary[]>("get_snapshots"),
+  });
+};
+
+export const useSnapshotTimeSeries = (workspaceId?: string) => {
+  return useQuery({
+    queryKey: ["snapshot_time_series", workspaceId],
+    queryFn: () =>
+      invoke<SnapshotTimeSeries>("get_snapshot_time_series", {
+        workspaceId: workspaceId ?? null,
+      }),
+  });
+};
+
+export const useCreateSnapshot = () => {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: () => invoke<SnapshotSummary>("create_snapshot"),
+    onSettled: refreshSnapshots(queryClient),
+  });
+};
+
+export const useRestoreSnapshot = () => {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (snapshotId: string) =>
+      invoke<void>("restore_snapshot", { snapshotId }),
+    onSettled: () => {
+      // the restored run replaces every output
+      queryClient.invalidateQueries();
+    },
+  });
+};
+
+export const useDeleteSnapshot = () => {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (snapshotId: string) =>
+      invoke<void>("delete_snapshot", { snapshotId }),
+    onSettled: refreshSnapshots(queryClient),
+  });
+};
+
+/** repeated measurements of one quantity */
+export type SampleStats = {
+  samples: number[];
+  mean: number;
+  median: number;
+  stddev: number;
+  /** 95% confidence interval of the mean, null with a single sample */
+  ciLow: number | null;
+  ciHigh: number | null;
+};
+
+export type BisectMetric =
+  | { kind: "totalCheckTime" }
+  | { kind: "fileCheckTime"; path: string }
+  | { kind: "depthLimitCount"; depthLimit: DepthLimitKind }
+  | { kind: "typeCount" };
+
+export type BisectRequest = {
+  good: string;
+  bad: string;
+  metric: BisectMetric;
+  /** milliseconds for check times, a plain count otherwise */
+  threshold: number;
+  /** times to measure each commit; a commit is bad when its whole confidence interval is over the threshold */
+  repetitions: number;
+};
+
+export type BisectStep = {
+  commit: string;
+  subject: string;
+  value: number;
+  stats: SampleStats;
+  bad: boolean;
+};
+
+export type BisectResult = {
+  firstBadCommit: string;
+  firstBadSubject: string;
+  candidates: number;
+  steps: BisectStep[];
+};
+
+export const useBisect = () => {
+  return useMutation({
+    mutationFn: (request: BisectRequest) =>
+      invoke<BisectResult>("bisect", { request }),
+  });
+};
+
+export type ExperimentVariant = {
+  /** column header in the comparison */
+  name: string;
+  /** appended to the extra tsc flags, e.g. `--skipLibCheck` */
+  flags: string;
+  /** run with this compiler instead of the configured one */
+  compilerVariant: TypeScriptCompilerVariant | null;
+};
+
+/** times are in milliseconds */
+export type VariantResult = {
+  name: string;
+  extraTscFlags: string;
+  compilerVariant: TypeScriptCompilerVariant;
+  error: string | null;
+  exitCode: number | null;
+  wallTime: number;
+  totalCheckTime: number;
+  wallTimeStats: SampleStats;
+  totalCheckTimeStats: SampleStats;
+  typeCount: number;
+  hotSpotCount: number;
+  duplicatePackages: number;
+  depthLimitCounts: Partial<Record<DepthLimitKind, number>>;
+  rootFileCount: number | null;
+  performance: PerformanceOptions | null;
+};
+
+export type ComparisonRow = {
+  metric: string;
+  values: (number | null)[];
+  /** percent change relative to the first variant */
+  deltas: (number | null)[];
+  /** whether the change from the baseline is beyond run-to-run noise */
+  significant: (boolean | null)[];
+};
+
+export type ExperimentResult = {
+  id: string;
+  createdAt: string;
+  workspaceId: string;
+  variants: VariantResult[];
+  comparison: { columns: string[]; rows: ComparisonRow[] };
+};
+
+export const useExperiments = () => {
+  return useQuery({
+    queryKey: ["experiments"],
+    queryFn: () => invoke<ExperimentResult[]>("get_experiments"),
+  });
+};
+
+export const useRunExperiment = () => {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (request: {
+      variants: ExperimentVariant[];
+      repetitions: number;
+      warmup: number;
+    }) => invoke<ExperimentResult>("run_compiler_experiment", { request }),
+    onSettled: () => {
+      queryClient.invalidateQueries({ queryKey: ["experiments"] });
+    },
+  });
+};
+
+export const useDeleteExperiment = () => {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (experimentId: string) =>
+      invoke<void>("delete_experiment", { experimentId }),
+    onSettled: () => {
+      queryClient.invalidateQueries({ queryKey: ["experiments"] });
+    },
+  });
+};
+
+export type RepeatedRunsRequest = {
+  runs: number;
+  warmup: number;
+};
+
+/** times are in milliseconds */
+export type RepeatedRunsResult = {
+  createdAt: string;
+  runs: number;
+  warmup: number;
+  extraTscFlags: string;
+  wallTime: SampleStats;
+  totalCheckTime: SampleStats;
+  files: { path: string; checkTime: SampleStats }[];
+  hotSpots: {
+    description: string;
+    path: string | null;
+    startLine: number | null;
+    startChar: number | null;
+    /** only from the runs it was over the analysis threshold in, with no CI unless that's all of them */
+    duration: SampleStats;
+    /** runs it was over the analysis threshold in */
+    appearedIn: number;
+  }[];
+};
+
+export const useRepeatedRuns = () => {
+  return useQuery({
+    queryKey: ["repeated_runs"],
+    queryFn: () => invoke<RepeatedRunsResult | null>("get_repeated_runs"),
+  });
+};
+
+export const useRunRepeatedTraces = () => {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (request: RepeatedRunsRequest) =>
+      invoke<RepeatedRunsResult>("run_repeated_traces", { request }),
+    onSettled: () => {
+      queryClient.invalidateQueries({ queryKey: ["repeated_runs"] });
+      // timing budgets are checked against the repeated runs
+      queryClient.invalidateQueries({ queryKey: ["budget_checks"] });
+    },
+  });
+};
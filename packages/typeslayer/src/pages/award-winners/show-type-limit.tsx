@@ -35,7 +35,9 @@ import {
 } from "../../components/type-summary";
 import { extractPath } from "../../components/utils";
 import {
-  useAnalyzeTrace,
+  type CheckSite,
+  type ResolvedDepthLimit,
+  useDepthLimitReport,
   useGetResolvedTypeById,
   useProjectRoot,
   useRelativePaths,
@@ -76,14 +78,14 @@ export const ShowTypeLimit = <L extends LimitType>({
   const relativePaths = useRelativePaths();
   const projectRoot = useProjectRoot();
   const [selectedTab, setSelectedTab] = useState<
-    keyof ReturnType<typeof tabs> | "json"
+    keyof ReturnType<typeof tabs> | "json" | "sites"
   >("json");
 
   const { icon: Icon } = awards[awardId];
 
   const { title, notFound } = depthLimitInfo[awardId];
 
-  const { data: analyzeTrace } = useAnalyzeTrace();
+  const { data: report } = useDepthLimitReport();
 
   const handleTypeClick = useCallback((index: number) => {
     setSelectedIndex(index);
@@ -94,7 +96,8 @@ export const ShowTypeLimit = <L extends LimitType>({
 
   const isLoading = relativePaths.isLoading || projectRoot.isLoading;
 
-  const data = (analyzeTrace?.depthLimits[awardId] || []) as L[];
+  const limits = report?.[awardId] ?? [];
+  const data = limits.map(limit => limit.event) as L[];
   const first: L | undefined = data[0];
 
   const hasItems = data.length > 0;
@@ -102,7 +105,7 @@ export const ShowTypeLimit = <L extends LimitType>({
   const hasData =
     relativePaths.data !== undefined &&
     projectRoot.data !== undefined &&
-    analyzeTrace;
+    report;
 
   const nonePresent = (
     <Alert severity="success" sx={{ mx: 1 }}>
@@ -118,6 +121,7 @@ export const ShowTypeLimit = <L extends LimitType>({
           typeId={getListItemTypeId(current)}
           key={getKey(current)}
           inlineBarGraph={inlineBarGraph(current, first)}
+          count={limits[index].count}
           onClick={() => handleTypeClick(index)}
           selected={index === selectedIndex}
         />
@@ -194,6 +198,15 @@ export const ShowTypeLimit = <L extends LimitType>({
               label={<TabLabel label="Trace Event" count={null} />}
               value="json"
             />
+            <Tab
+              label={
+                <TabLabel
+                  label="Checked At"
+                  count={limits[selectedIndex]?.sites.length ?? null}
+                />
+              }
+              value="sites"
+            />
             {currentItem
               ? tabs(currentItem).map(({ tabName }) => (
                   <Tab
@@ -207,6 +220,8 @@ export const ShowTypeLimit = <L extends LimitType>({
 
           {selectedTab === "json" ? (
             <Code lang="json" value={JSON.stringify(currentItem, null, 2)} />
+          ) : selectedTab === "sites" ? (
+            <ShowCheckSites limit={limits[selectedIndex]} />
           ) : (
             <ShowMaybeMany content={currentTabContents} />
           )}
@@ -216,6 +231,67 @@ export const ShowTypeLimit = <L extends LimitType>({
   );
 };
 
+const formatSite = ({ path, start }: CheckSite) =>
+  start ? `${path}:${start.line}:${start.character}` : path;
+
+const ShowCheckSites = ({
+  limit,
+}: {
+  limit: ResolvedDepthLimit | undefined;
+}) => {
+  if (!limit) {
+    return <Typography>something's wrong. no data for this tab.</Typography>;
+  }
+
+  return (
+    <Stack gap={2}>
+      <Typography variant="body2" color="textSecondary">
+        Hit {limit.count.toLocaleString()}{" "}
+        {limit.count === 1 ? "time" : "times"} on these types, while checking:
+      </Typography>
+      {limit.sites.length === 0 ? (
+        <Typography>
+          Not inside a checked node, e.g. during declaration emit.
+        </Typography>
+      ) : (
+        <List dense>
+          {limit.sites.map(site => (
+            <ListItemText
+              key={`${site.path}:${site.pos}`}
+              primary={formatSite(site)}
+              slotProps={{
+                primary: {
+                  sx: { fontFamily: "monospace", wordBreak: "break-all" },
+                },
+              }}
+            />
+          ))}
+        </List>
+      )}
+      {limit.types.length > 0 && (
+        <List dense>
+          {limit.types.map((type, index) => (
+            <ListItemText
+              // biome-ignore lint/suspicious/noArrayIndexKey: types can repeat
+              key={index}
+              primary={`${type.id} ${type.name ?? ""}`}
+              secondary={
+                type.declaration
+                  ? `declared at ${type.declaration.path}:${type.declaration.start.line}:${type.declaration.start.character}`
+                  : undefined
+              }
+              slotProps={{
+                primary: { sx: { fontFamily: "monospace" } },
+                secondary: { sx: { wordBreak: "break-all" } },
+              }}
+            />
+          ))}
+        </List>
+      )}
+    </Stack>
+  );
+};
+
 const ShowMaybeMany = ({
   content,
 }: {
@@ -264,11 +340,13 @@ const ResolveTypeSummary = ({
 const LimitListItem = ({
   typeId,
   inlineBarGraph,
+  count,
   onClick,
   selected,
 }: {
   typeId: TypeId;
   inlineBarGraph: ReactNode;
+  count: number;
   onClick: () => void;
   selected: boolean;
 }) => {
@@ -305,6 +383,11 @@ const LimitListItem = ({
         <Stack gap={0.5}>
           <MaybePathCaption maybePath={extractPath(resolvedType)} />
           {inlineBarGraph}
+          {count > 1 && (
+            <Typography variant="caption" color="textSecondary">
+              hit {count.toLocaleString()} times
+            </Typography>
+          )}
         </Stack>
       </ListItemText>
     </ListItemButton>
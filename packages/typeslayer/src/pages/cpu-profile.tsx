@@ -0,0 +1,284 @@
+import {
+  Alert,
+  Box,
+  Stack,
+  Tab,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableRow,
+  Tabs,
+  Typography,
+} from "@mui/material";
+import { useState } from "react";
+import {
+  type CallTreeNode,
+  type CallTreeOptions,
+  type HotSpotProfile,
+  useCpuProfileCallTree,
+  useCpuProfileSummary,
+  useHotSpotProfiles,
+} from "../hooks/tauri-hooks";
+
+const TREE_OPTIONS: CallTreeOptions = { maxDepth: 12, minShare: 0.005 };
+
+const formatMs = (ms: number) => `${ms.toFixed(1)}ms`;
+
+const formatShare = (ms: number, total: number) =>
+  total > 0 ? `${((ms / total) * 100).toFixed(1)}%` : "";
+
+const fileName = (url: string) => url.split("/").pop() ?? url;
+
+export const CpuProfilePage = () => {
+  const [tab, setTab] = useState<
+    "functions" | "top_down" | "bottom_up" | "hot_spots"
+  >("functions");
+  const { data: summary, error } = useCpuProfileSummary(100);
+
+  if (error) {
+    return (
+      <Box sx={{ p: 4 }}>
+        <Alert severity="info">{String(error)}</Alert>
+      </Box>
+    );
+  }
+
+  if (!summary) {
+    return null;
+  }
+
+  return (
+    <Stack sx={{ p: 4, gap: 3, overflow: "auto", maxHeight: "100%" }}>
+      <Stack sx={{ flexDirection: "row", alignItems: "flex-end", gap: 2 }}>
+        <Typography variant="h2">CPU Profile</Typography>
+        <Typography variant="body1" color="textSecondary">
+          {formatMs(summary.totalTime)} over{" "}
+          {summary.sampleCount.toLocaleString()} samples (one every{" "}
+          {formatMs(summary.sampleInterval)})
+        </Typography>
+      </Stack>
+
+      <Table size="small">
+        <TableHead>
+          <TableRow>
+            <TableCell>checker function</TableCell>
+            <TableCell align="right">self</TableCell>
+            <TableCell align="right">total</TableCell>
+            <TableCell align="right">of profile</TableCell>
+          </TableRow>
+        </TableHead>
+        <TableBody>
+          {summary.checkerGroups.map(group => (
+            <TableRow key={group.group}>
+              <TableCell
+                sx={{ fontFamily: "monospace" }}
+                title={group.functions.join(", ")}
+              >
+                {group.group}
+              </TableCell>
+              <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                {formatMs(group.selfTime)}
+              </TableCell>
+              <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                {formatMs(group.totalTime)}
+              </TableCell>
+              <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                {(group.share * 100).toFixed(1)}%
+              </TableCell>
+            </TableRow>
+          ))}
+        </TableBody>
+      </Table>
+
+      <Tabs value={tab} onChange={(_, value) => setTab(value)}>
+        <Tab value="functions" label="Functions" />
+        <Tab value="bottom_up" label="Bottom-up" />
+        <Tab value="top_down" label="Top-down" />
+        <Tab value="hot_spots" label="Hot spots" />
+      </Tabs>
+
+      {tab === "functions" ? (
+        <Table size="small">
+          <TableHead>
+            <TableRow>
+              <TableCell>function</TableCell>
+              <TableCell>location</TableCell>
+              <TableCell align="right">self</TableCell>
+              <TableCell align="right">total</TableCell>
+            </TableRow>
+          </TableHead>
+          <TableBody>
+            {summary.functions.map((fn, i) => (
+              // biome-ignore lint/suspicious/noArrayIndexKey: names can repeat across files
+              <TableRow key={i}>
+                <TableCell sx={{ fontFamily: "monospace" }}>
+                  {fn.functionName}
+                </TableCell>
+                <TableCell
+                  sx={{ fontFamily: "monospace", wordBreak: "break-all" }}
+                  title={fn.url}
+                >
+                  {fn.url && `${fileName(fn.url)}:${fn.lineNumber + 1}`}
+                </TableCell>
+                <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                  {formatMs(fn.selfTime)}{" "}
+                  ({formatShare(fn.selfTime, summary.totalTime)})
+                </TableCell>
+                <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                  {formatMs(fn.totalTime)}{" "}
+                  ({formatShare(fn.totalTime, summary.totalTime)})
+                </TableCell>
+              </TableRow>
+            ))}
+          </TableBody>
+        </Table>
+      ) : tab === "hot_spots" ? (
+        <HotSpotProfiles />
+      ) : (
+        <CallTree direction={tab} totalTime={summary.totalTime} />
+      )}
+    </Stack>
+  );
+};
+
+const CallTree = ({
+  direction,
+  totalTime,
+}: {
+  direction: "top_down" | "bottom_up";
+  totalTime: number;
+}) => {
+  const { data: roots } = useCpuProfileCallTree(direction, TREE_OPTIONS);
+  if (!roots) {
+    return null;
+  }
+  return (
+    <Box sx={{ fontFamily: "monospace", fontSize: "0.85rem" }}>
+      <Typography variant="body2" color="textSecondary" sx={{ mb: 1 }}>
+        {direction === "top_down"
+          ? "Each function with what it called, by total time."
+          : "Each function's self time, split by who called it."}{" "}
+        Entries under {TREE_OPTIONS.minShare * 100}% of the profile are left
+        out.
+      </Typography>
+      {roots.map((node, i) => (
+        // biome-ignore lint/suspicious/noArrayIndexKey: trees are positional
+        <CallTreeItem key={i} node={node} totalTime={totalTime} depth={0} />
+      ))}
+    </Box>
+  );
+};
+
+const CallTreeItem = ({
+  node,
+  totalTime,
+  depth,
+}: {
+  node: CallTreeNode;
+  totalTime: number;
+  depth: number;
+}) => {
+  const [open, setOpen] = useState(depth < 1);
+  const expandable = node.children.length > 0;
+  return (
+    <Box sx={{ pl: depth === 0 ? 0 : 2 }}>
+      <Box
+        component="button"
+        type="button"
+        onClick={() => setOpen(!open)}
+        disabled={!expandable}
+        sx={{
+          all: "unset",
+          cursor: expandable ? "pointer" : "default",
+          display: "block",
+          whiteSpace: "nowrap",
+        }}
+        title={node.url}
+      >
+        {expandable ? (open ? "▾ " : "▸ ") : "  "}
+        {formatMs(node.totalTime)} ({formatShare(node.totalTime, totalTime)}){" "}
+        {node.functionName}
+        {node.url && ` ${fileName(node.url)}:${node.lineNumber + 1}`}
+        {node.prunedChildren > 0 && ` (+${node.prunedChildren} more)`}
+      </Box>
+      {open &&
+        node.children.map((child, i) => (
+          <CallTreeItem
+            // biome-ignore lint/suspicious/noArrayIndexKey: trees are positional
+            key={i}
+            node={child}
+            totalTime={totalTime}
+            depth={depth + 1}
+          />
+        ))}
+    </Box>
+  );
+};
+
+const HotSpotProfiles = () => {
+  const { data: correlation, error } = useHotSpotProfiles();
+  if (error) {
+    return <Alert severity="info">{String(error)}</Alert>;
+  }
+  if (!correlation) {
+    return null;
+  }
+  return (
+    <Box sx={{ fontFamily: "monospace", fontSize: "0.85rem" }}>
+      {correlation.warning && (
+        <Alert severity="warning" sx={{ mb: 1 }}>
+          {correlation.warning}
+        </Alert>
+      )}
+      <Typography variant="body2" color="textSecondary" sx={{ mb: 1 }}>
+        Each hot spot in the trace, with the functions the profile caught
+        running during it. Mostly GC means raising maxOldSpaceSize helps;
+        mostly checker functions means the type itself is expensive.
+      </Typography>
+      {correlation.hotSpots.map((hotSpot, i) => (
+        // biome-ignore lint/suspicious/noArrayIndexKey: trees are positional
+        <HotSpotProfileItem key={i} hotSpot={hotSpot} />
+      ))}
+    </Box>
+  );
+};
+
+const HotSpotProfileItem = ({ hotSpot }: { hotSpot: HotSpotProfile }) => {
+  const [open, setOpen] = useState(false);
+  return (
+    <Box>
+      <Box
+        component="button"
+        type="button"
+        onClick={() => setOpen(!open)}
+        sx={{
+          all: "unset",
+          cursor: "pointer",
+          display: "block",
+          whiteSpace: "nowrap",
+        }}
+        title={hotSpot.path}
+      >
+        {open ? "▾ " : "▸ "}
+        {hotSpot.description}: {hotSpot.summary}
+      </Box>
+      {open && (
+        <Box sx={{ pl: 2 }}>
+          {hotSpot.functions.map((fn, i) => (
+            // biome-ignore lint/suspicious/noArrayIndexKey: names can repeat across files
+            <Box key={i} sx={{ color: "text.secondary" }} title={fn.url}>
+              {formatMs(fn.selfTime)} ({(fn.share * 100).toFixed(1)}%){" "}
+              {fn.functionName}
+              {fn.url && ` ${fileName(fn.url)}:${fn.lineNumber + 1}`}
+            </Box>
+          ))}
+          {hotSpot.children.map((child, i) => (
+            // biome-ignore lint/suspicious/noArrayIndexKey: trees are positional
+            <HotSpotProfileItem key={i} hotSpot={child} />
+          ))}
+        </Box>
+      )}
+    </Box>
+  );
+};
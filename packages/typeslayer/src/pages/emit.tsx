@@ -0,0 +1,175 @@
+import {
+  Alert,
+  Stack,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableRow,
+  Typography,
+} from "@mui/material";
+import { useEmitReport } from "../hooks/tauri-hooks";
+
+const formatMs = (ms: number) => `${ms.toFixed(1)}ms`;
+
+const MsCell = ({ ms }: { ms: number }) => (
+  <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+    {formatMs(ms)}
+  </TableCell>
+);
+
+export const EmitPage = () => {
+  const { data: report, error } = useEmitReport();
+
+  return (
+    <Stack sx={{ p: 4, gap: 3, overflow: "auto", maxHeight: "100%" }}>
+      <Stack sx={{ flexDirection: "row", alignItems: "flex-end", gap: 2 }}>
+        <Typography variant="h2">Emit</Typography>
+        <Typography variant="body1" color="textSecondary">
+          writing .js and .d.ts files, after checking
+        </Typography>
+      </Stack>
+
+      {error && <Alert severity="error">{String(error)}</Alert>}
+
+      {report && report.total === 0 && (
+        <Alert severity="info">
+          This trace has no emit. Take <code>--noEmit</code> out of the extra
+          tsc flags in settings to include it.
+        </Alert>
+      )}
+
+      {report && report.total > 0 && (
+        <>
+          <Typography variant="body2" color="textSecondary">
+            {formatMs(report.total)} in emit:{" "}
+            {formatMs(report.declaration)} writing{" "}
+            {report.declarationFileCount.toLocaleString()} declaration files (
+            {formatMs(report.declarationChecker)} of it in the checker),{" "}
+            {formatMs(report.js)} writing{" "}
+            {report.jsFileCount.toLocaleString()} JS files and{" "}
+            {formatMs(report.buildInfo)} writing build info.
+          </Typography>
+
+          <Typography variant="h5">Slowest declaration files</Typography>
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>file</TableCell>
+                <TableCell align="right">duration</TableCell>
+                <TableCell align="right">transform</TableCell>
+                <TableCell align="right">checker</TableCell>
+                <TableCell>types</TableCell>
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {report.declarationFiles.map(file => (
+                <TableRow key={file.declarationFilePath}>
+                  <TableCell
+                    sx={{ fontFamily: "monospace", wordBreak: "break-all" }}
+                  >
+                    {file.declarationFilePath}
+                  </TableCell>
+                  <MsCell ms={file.duration} />
+                  <MsCell ms={file.transform} />
+                  <MsCell ms={file.checker} />
+                  <TableCell sx={{ fontFamily: "monospace" }}>
+                    {file.typeIds.join(", ")}
+                  </TableCell>
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+
+          <Typography variant="h5">
+            Types the checker worked on during declaration emit
+          </Typography>
+          <Typography variant="body2" color="textSecondary">
+            Usually inferred types being printed into .d.ts files. Annotating
+            where they're inferred saves the work.
+          </Typography>
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>type</TableCell>
+                <TableCell align="right">time</TableCell>
+                <TableCell align="right">count</TableCell>
+                <TableCell>declaration files</TableCell>
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {report.types.map(type => (
+                <TableRow key={type.typeId}>
+                  <TableCell sx={{ fontFamily: "monospace" }}>
+                    {type.typeId}
+                    {type.name && ` ${type.name}`}
+                  </TableCell>
+                  <MsCell ms={type.time} />
+                  <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                    {type.count.toLocaleString()}
+                  </TableCell>
+                  <TableCell
+                    sx={{ fontFamily: "monospace", wordBreak: "break-all" }}
+                  >
+                    {type.declarationFiles.join(", ")}
+                  </TableCell>
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+
+          <Typography variant="h5">Transform time by source file</Typography>
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>file</TableCell>
+                <TableCell align="right">js</TableCell>
+                <TableCell align="right">declaration</TableCell>
+                <TableCell align="right">total</TableCell>
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {report.transforms.map(transform => (
+                <TableRow key={transform.path}>
+                  <TableCell
+                    sx={{ fontFamily: "monospace", wordBreak: "break-all" }}
+                  >
+                    {transform.path}
+                  </TableCell>
+                  <MsCell ms={transform.js} />
+                  <MsCell ms={transform.declaration} />
+                  <MsCell ms={transform.total} />
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+
+          <Typography variant="h5">Slowest JS files</Typography>
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>file</TableCell>
+                <TableCell align="right">duration</TableCell>
+                <TableCell align="right">transform</TableCell>
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {report.jsFiles.map((file, i) => (
+                // biome-ignore lint/suspicious/noArrayIndexKey: bundles have no path
+                <TableRow key={i}>
+                  <TableCell
+                    sx={{ fontFamily: "monospace", wordBreak: "break-all" }}
+                  >
+                    {file.jsFilePath ?? "(bundle)"}
+                  </TableCell>
+                  <MsCell ms={file.duration} />
+                  <MsCell ms={file.transform} />
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+        </>
+      )}
+    </Stack>
+  );
+};
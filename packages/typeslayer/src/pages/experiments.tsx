@@ -0,0 +1,456 @@
+import Add from "@mui/icons-material/Add";
+import Delete from "@mui/icons-material/Delete";
+import {
+  Alert,
+  Box,
+  Button,
+  CircularProgress,
+  IconButton,
+  MenuItem,
+  Stack,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableRow,
+  TextField,
+  Tooltip,
+  Typography,
+} from "@mui/material";
+import { useState } from "react";
+import {
+  type TypeScriptCompilerVariant,
+  typeScriptCompilerVariants,
+} from "../components/utils";
+import {
+  type ExperimentResult,
+  type ExperimentVariant,
+  type SampleStats,
+  useCancelGeneration,
+  useDeleteExperiment,
+  useExperiments,
+  useRepeatedRuns,
+  useRunExperiment,
+  useRunRepeatedTraces,
+  useTaskOutput,
+} from "../hooks/tauri-hooks";
+
+const PRESETS: ExperimentVariant[] = [
+  { name: "skipLibCheck", flags: "--skipLibCheck", compilerVariant: null },
+  {
+    name: "no strictFunctionTypes",
+    flags: "--strictFunctionTypes false",
+    compilerVariant: null,
+  },
+  { name: "types: node", flags: "--types node", compilerVariant: null },
+  { name: "tsgo", flags: "", compilerVariant: "tsgo" },
+];
+
+const BASELINE: ExperimentVariant = {
+  name: "baseline",
+  flags: "",
+  compilerVariant: null,
+};
+
+const formatStats = ({ mean, ciLow, ciHigh }: SampleStats) =>
+  ciLow === null || ciHigh === null
+    ? `${mean.toFixed(1)}`
+    : `${mean.toFixed(1)} ± ${((ciHigh - ciLow) / 2).toFixed(1)}`;
+
+const formatDelta = (delta: number | null) => {
+  if (delta === null || delta === 0) {
+    return "";
+  }
+  return ` (${delta > 0 ? "+" : ""}${delta.toFixed(1)}%)`;
+};
+
+export const ExperimentsPage = () => {
+  const experiments = useExperiments();
+  const runExperiment = useRunExperiment();
+  const deleteExperiment = useDeleteExperiment();
+  const { mutate: cancel } = useCancelGeneration();
+  const { data: lastLine } = useTaskOutput("experiment");
+  const [variants, setVariants] = useState<ExperimentVariant[]>([
+    BASELINE,
+    PRESETS[0],
+  ]);
+  const [selectedId, setSelectedId] = useState<string | null>(null);
+  const [repetitions, setRepetitions] = useState("1");
+  const [warmup, setWarmup] = useState("0");
+
+  const updateVariant = (index: number, update: Partial<ExperimentVariant>) =>
+    setVariants(current =>
+      current.map((variant, i) =>
+        i === index ? { ...variant, ...update } : variant,
+      ),
+    );
+
+  const selected =
+    experiments.data?.find(experiment => experiment.id === selectedId) ??
+    experiments.data?.[0];
+
+  return (
+    <Stack sx={{ mx: 4, my: 2, gap: 2 }}>
+      <Typography variant="h4">Experiments</Typography>
+      <Typography color="text.secondary">
+        runs the project under each variant, with the variant's flags appended
+        to your extra tsc flags, and compares the trace analyses side by side.
+        the first variant is the baseline. with two or more runs per variant,
+        timing changes beyond run-to-run noise are marked with *.
+      </Typography>
+
+      {variants.map((variant, index) => (
+        <Stack
+          // biome-ignore lint/suspicious/noArrayIndexKey: variants have no stable id
+          key={index}
+          direction="row"
+          sx={{ gap: 2, alignItems: "center" }}
+        >
+          <TextField
+            label="name"
+            size="small"
+            value={variant.name}
+            onChange={event =>
+              updateVariant(index, { name: event.target.value })
+            }
+          />
+          <TextField
+            label="flags"
+            size="small"
+            value={variant.flags}
+            placeholder="--skipLibCheck"
+            onChange={event =>
+              updateVariant(index, { flags: event.target.value })
+            }
+            sx={{ flexGrow: 1, fontFamily: "monospace" }}
+          />
+          <TextField
+            select
+            label="compiler"
+            size="small"
+            value={variant.compilerVariant ?? ""}
+            onChange={event =>
+              updateVariant(index, {
+                compilerVariant:
+                  (event.target.value as TypeScriptCompilerVariant) || null,
+              })
+            }
+            sx={{ minWidth: 140 }}
+          >
+            <MenuItem value="">configured</MenuItem>
+            {typeScriptCompilerVariants.map(compiler => (
+              <MenuItem key={compiler} value={compiler}>
+                {compiler}
+              </MenuItem>
+            ))}
+          </TextField>
+          <IconButton
+            size="small"
+            disabled={variants.length === 1}
+            onClick={() =>
+              setVariants(current => current.filter((_, i) => i !== index))
+            }
+          >
+            <Delete fontSize="small" />
+          </IconButton>
+        </Stack>
+      ))}
+
+      <Stack direction="row" sx={{ gap: 1, flexWrap: "wrap" }}>
+        <Button
+          startIcon={<Add />}
+          onClick={() =>
+            setVariants(current => [
+              ...current,
+              { ...BASELINE, name: `variant ${current.length}` },
+            ])
+          }
+        >
+          Variant
+        </Button>
+        {PRESETS.map(preset => (
+          <Button
+            key={preset.name}
+            startIcon={<Add />}
+            onClick={() => setVariants(current => [...current, preset])}
+          >
+            {preset.name}
+          </Button>
+        ))}
+      </Stack>
+
+      <Stack direction="row" sx={{ gap: 2, alignItems: "center" }}>
+        <TextField
+          label="runs per variant"
+          type="number"
+          size="small"
+          value={repetitions}
+          onChange={event => setRepetitions(event.target.value)}
+          sx={{ maxWidth: 140 }}
+        />
+        <TextField
+          label="warmup runs"
+          type="number"
+          size="small"
+          value={warmup}
+          onChange={event => setWarmup(event.target.value)}
+          sx={{ maxWidth: 140 }}
+        />
+        <Button
+          variant="contained"
+          disabled={runExperiment.isPending || variants.length === 0}
+          onClick={() =>
+            runExperiment.mutate(
+              {
+                variants,
+                repetitions: Math.max(1, Number(repetitions) || 1),
+                warmup: Math.max(0, Number(warmup) || 0),
+              },
+              { onSuccess: result => setSelectedId(result.id) },
+            )
+          }
+        >
+          Run experiment
+        </Button>
+        {runExperiment.isPending && (
+          <Button color="error" onClick={() => cancel("experiment")}>
+            Cancel
+          </Button>
+        )}
+      </Stack>
+      {runExperiment.isPending && lastLine && (
+        <Typography variant="caption" fontFamily="monospace">
+          {lastLine.line}
+        </Typography>
+      )}
+      {runExperiment.error && (
+        <Alert severity="error">{String(runExperiment.error)}</Alert>
+      )}
+
+      {experiments.isLoading ? (
+        <Box sx={{ display: "flex", justifyContent: "center", mt: 4 }}>
+          <CircularProgress />
+        </Box>
+      ) : (
+        selected && (
+          <>
+            <Stack direction="row" sx={{ gap: 2, alignItems: "center" }}>
+              <TextField
+                select
+                label="experiment"
+                size="small"
+                value={selected.id}
+                onChange={event => setSelectedId(event.target.value)}
+                sx={{ minWidth: 260 }}
+              >
+                {experiments.data?.map(experiment => (
+                  <MenuItem key={experiment.id} value={experiment.id}>
+                    {new Date(experiment.createdAt).toLocaleString()} (
+                    {experiment.variants.length} variants)
+                  </MenuItem>
+                ))}
+              </TextField>
+              <Tooltip title="delete experiment">
+                <IconButton
+                  size="small"
+                  onClick={() => deleteExperiment.mutate(selected.id)}
+                >
+                  <Delete fontSize="small" />
+                </IconButton>
+              </Tooltip>
+            </Stack>
+            <ComparisonTable experiment={selected} />
+          </>
+        )
+      )}
+      <RepeatedRuns />
+    </Stack>
+  );
+};
+
+const RepeatedRuns = () => {
+  const repeatedRuns = useRepeatedRuns();
+  const runRepeatedTraces = useRunRepeatedTraces();
+  const { mutate: cancel } = useCancelGeneration();
+  const { data: lastLine } = useTaskOutput("repeated_runs");
+  const [runs, setRuns] = useState("5");
+  const [warmup, setWarmup] = useState("1");
+  const result = repeatedRuns.data;
+
+  return (
+    <Stack sx={{ gap: 2, mt: 4 }}>
+      <Typography variant="h5">Repeated runs</Typography>
+      <Typography color="text.secondary">
+        generates the trace several times with your current settings and
+        reports the mean and 95% confidence interval of every timing, so you can
+        tell a real change from a cold cache. warmup runs are thrown away.
+      </Typography>
+      <Stack direction="row" sx={{ gap: 2, alignItems: "center" }}>
+        <TextField
+          label="runs"
+          type="number"
+          size="small"
+          value={runs}
+          onChange={event => setRuns(event.target.value)}
+          sx={{ maxWidth: 140 }}
+        />
+        <TextField
+          label="warmup runs"
+          type="number"
+          size="small"
+          value={warmup}
+          onChange={event => setWarmup(event.target.value)}
+          sx={{ maxWidth: 140 }}
+        />
+        <Button
+          variant="contained"
+          disabled={runRepeatedTraces.isPending}
+          onClick={() =>
+            runRepeatedTraces.mutate({
+              runs: Math.max(1, Number(runs) || 1),
+              warmup: Math.max(0, Number(warmup) || 0),
+            })
+          }
+        >
+          Run
+        </Button>
+        {runRepeatedTraces.isPending && (
+          <Button color="error" onClick={() => cancel("repeated_runs")}>
+            Cancel
+          </Button>
+        )}
+      </Stack>
+      {runRepeatedTraces.isPending && lastLine && (
+        <Typography variant="caption" fontFamily="monospace">
+          {lastLine.line}
+        </Typography>
+      )}
+      {runRepeatedTraces.error && (
+        <Alert severity="error">{String(runRepeatedTraces.error)}</Alert>
+      )}
+      {result && (
+        <>
+          <Typography color="text.secondary">
+            {new Date(result.createdAt).toLocaleString()}: {result.runs} runs
+            after {result.warmup} warmup, wall time{" "}
+            {formatStats(result.wallTime)} ms, total check time{" "}
+            {formatStats(result.totalCheckTime)} ms
+          </Typography>
+          <StatsTable
+            title="file"
+            rows={result.files.slice(0, 20).map(file => ({
+              label: file.path,
+              stats: file.checkTime,
+            }))}
+          />
+          <StatsTable
+            title="hot spot"
+            rows={result.hotSpots.slice(0, 20).map(hotSpot => {
+              const label = hotSpot.path
+                ? `${hotSpot.description} (${hotSpot.path}:${hotSpot.startLine ?? 0})`
+                : hotSpot.description;
+              return {
+                label:
+                  hotSpot.appearedIn > 0 && hotSpot.appearedIn < result.runs
+                    ? `${label}, only in ${hotSpot.appearedIn} of ${result.runs} runs`
+                    : label,
+                stats: hotSpot.duration,
+              };
+            })}
+          />
+        </>
+      )}
+    </Stack>
+  );
+};
+
+const StatsTable = ({
+  title,
+  rows,
+}: {
+  title: string;
+  rows: { label: string; stats: SampleStats }[];
+}) => (
+  <Table size="small">
+    <TableHead>
+      <TableRow>
+        <TableCell>{title}</TableCell>
+        <TableCell align="right">mean ± 95% CI (ms)</TableCell>
+        <TableCell align="right">median</TableCell>
+        <TableCell align="right">stddev</TableCell>
+        <TableCell align="right">samples</TableCell>
+      </TableRow>
+    </TableHead>
+    <TableBody>
+      {rows.map(({ label, stats }, i) => (
+        // biome-ignore lint/suspicious/noArrayIndexKey: labels can repeat
+        <TableRow key={i}>
+          <TableCell sx={{ fontFamily: "monospace", wordBreak: "break-all" }}>
+            {label}
+          </TableCell>
+          <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+            {formatStats(stats)}
+          </TableCell>
+          <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+            {stats.median.toFixed(1)}
+          </TableCell>
+          <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+            {stats.stddev.toFixed(1)}
+          </TableCell>
+          <TableCell align="right">{stats.samples.length}</TableCell>
+        </TableRow>
+      ))}
+    </TableBody>
+  </Table>
+);
+
+const ComparisonTable = ({ experiment }: { experiment: ExperimentResult }) => (
+  <>
+    <Table size="small">
+      <TableHead>
+        <TableRow>
+          <TableCell>metric</TableCell>
+          {experiment.variants.map((variant, i) => (
+            // biome-ignore lint/suspicious/noArrayIndexKey: columns are positional
+            <TableCell key={i} align="right">
+              <Tooltip
+                title={`${variant.compilerVariant} ${variant.extraTscFlags}`}
+              >
+                <span>{variant.name}</span>
+              </Tooltip>
+            </TableCell>
+          ))}
+        </TableRow>
+      </TableHead>
+      <TableBody>
+        {experiment.comparison.rows.map(row => (
+          <TableRow key={row.metric}>
+            <TableCell>{row.metric}</TableCell>
+            {row.values.map((value, i) => (
+              <TableCell
+                // biome-ignore lint/suspicious/noArrayIndexKey: columns are positional
+                key={i}
+                align="right"
+                sx={{ fontFamily: "monospace" }}
+              >
+                {value === null
+                  ? "-"
+                  : `${Math.round(value).toLocaleString()}${formatDelta(row.deltas[i])}${row.significant?.[i] ? " *" : ""}`}
+              </TableCell>
+            ))}
+          </TableRow>
+        ))}
+      </TableBody>
+    </Table>
+    {experiment.variants
+      .filter(variant => variant.error)
+      .map(variant => (
+        <Alert
+          key={`${variant.name}-${variant.extraTscFlags}`}
+          severity="warning"
+        >
+          {variant.name}: {variant.error}
+        </Alert>
+      ))}
+  </>
+);
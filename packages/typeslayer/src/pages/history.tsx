@@ -0,0 +1,343 @@
+import Delete from "@mui/icons-material/Delete";
+import Restore from "@mui/icons-material/Restore";
+import {
+  Alert,
+  Box,
+  Button,
+  CircularProgress,
+  IconButton,
+  MenuItem,
+  Stack,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableRow,
+  TextField,
+  Tooltip,
+  Typography,
+} from "@mui/material";
+import type { DepthLimitKind } from "@typeslayer/rust-types";
+import type { EChartsOption } from "echarts";
+import ReactECharts from "echarts-for-react";
+import { useState } from "react";
+import { useToast } from "../contexts/toast-context";
+import {
+  type BisectMetric,
+  useBisect,
+  useCancelGeneration,
+  useCreateSnapshot,
+  useDeleteSnapshot,
+  useRestoreSnapshot,
+  useSnapshots,
+  useSnapshotTimeSeries,
+  useTaskOutput,
+} from "../hooks/tauri-hooks";
+
+const shortCommit = (commit: string | null) => commit?.slice(0, 8) ?? "-";
+
+const toMilliseconds = (microseconds: number) =>
+  Math.round(microseconds / 1000);
+
+export const HistoryPage = () => {
+  const { showToast } = useToast();
+  const snapshots = useSnapshots();
+  const timeSeries = useSnapshotTimeSeries();
+  const createSnapshot = useCreateSnapshot();
+  const restoreSnapshot = useRestoreSnapshot();
+  const deleteSnapshot = useDeleteSnapshot();
+
+  if (snapshots.isLoading || timeSeries.isLoading) {
+    return (
+      <Box sx={{ display: "flex", justifyContent: "center", mt: 8 }}>
+        <CircularProgress />
+      </Box>
+    );
+  }
+
+  const series = timeSeries.data;
+  const labels =
+    series?.timestamps.map(
+      (timestamp, i) =>
+        `${new Date(timestamp).toLocaleString()}\n${shortCommit(series.gitCommits[i])}`,
+    ) ?? [];
+
+  const checkTimeOption: EChartsOption = {
+    backgroundColor: "transparent",
+    tooltip: { trigger: "axis" },
+    xAxis: { type: "category", data: labels },
+    yAxis: { type: "value", name: "total check time (ms)" },
+    series: [
+      {
+        name: "total check time (ms)",
+        type: "line",
+        data: series?.totalCheckTime.map(toMilliseconds) ?? [],
+      },
+    ],
+  };
+
+  const depthLimitOption: EChartsOption = {
+    backgroundColor: "transparent",
+    tooltip: { trigger: "axis" },
+    legend: { type: "scroll", top: 0 },
+    xAxis: { type: "category", data: labels },
+    yAxis: { type: "value", name: "depth limits hit" },
+    series: Object.entries(series?.depthLimitCounts ?? {}).map(
+      ([kind, counts]) => ({
+        name: kind,
+        type: "line",
+        stack: "depthLimits",
+        areaStyle: {},
+        data: counts ?? [],
+      }),
+    ),
+  };
+
+  const onCreateSnapshot = async () => {
+    try {
+      await createSnapshot.mutateAsync();
+    } catch (error) {
+      showToast({ message: String(error), severity: "error" });
+    }
+  };
+
+  return (
+    <Stack sx={{ mx: 4, my: 2, gap: 2 }}>
+      <Stack direction="row" sx={{ alignItems: "center", gap: 2 }}>
+        <Typography variant="h4">History</Typography>
+        <Button
+          variant="outlined"
+          onClick={onCreateSnapshot}
+          disabled={createSnapshot.isPending}
+        >
+          Snapshot current analysis
+        </Button>
+      </Stack>
+      <Typography color="text.secondary">
+        every run's analysis is archived for this project and tsconfig, so you
+        can watch type-check cost trend over time and spot the commit where it
+        regressed.
+      </Typography>
+
+      {!snapshots.data?.length ? (
+        <Typography color="text.secondary">
+          no snapshots yet. they're taken automatically each time you run
+          diagnostics.
+        </Typography>
+      ) : (
+        <>
+          <ReactECharts option={checkTimeOption} style={{ height: 320 }} />
+          <ReactECharts option={depthLimitOption} style={{ height: 320 }} />
+
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>taken</TableCell>
+                <TableCell>commit</TableCell>
+                <TableCell align="right">check time (ms)</TableCell>
+                <TableCell align="right">duplicate packages</TableCell>
+                <TableCell align="right">types</TableCell>
+                <TableCell align="right">links</TableCell>
+                <TableCell />
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {[...snapshots.data].reverse().map(snapshot => (
+                <TableRow key={snapshot.id}>
+                  <TableCell>
+                    {new Date(snapshot.createdAt).toLocaleString()}
+                  </TableCell>
+                  <TableCell sx={{ fontFamily: "monospace" }}>
+                    {shortCommit(snapshot.gitCommit)}
+                    {snapshot.gitDirty ? "*" : ""}
+                  </TableCell>
+                  <TableCell align="right">
+                    {toMilliseconds(snapshot.totalCheckTime).toLocaleString()}
+                  </TableCell>
+                  <TableCell align="right">
+                    {snapshot.duplicatePackages}
+                  </TableCell>
+                  <TableCell align="right">
+                    {snapshot.nodeCount?.toLocaleString() ?? "-"}
+                  </TableCell>
+                  <TableCell align="right">
+                    {snapshot.linkCount?.toLocaleString() ?? "-"}
+                  </TableCell>
+                  <TableCell align="right">
+                    <Tooltip title="restore this analysis">
+                      <IconButton
+                        size="small"
+                        onClick={() => restoreSnapshot.mutate(snapshot.id)}
+                      >
+                        <Restore fontSize="small" />
+                      </IconButton>
+                    </Tooltip>
+                    <Tooltip title="delete snapshot">
+                      <IconButton
+                        size="small"
+                        onClick={() => deleteSnapshot.mutate(snapshot.id)}
+                      >
+                        <Delete fontSize="small" />
+                      </IconButton>
+                    </Tooltip>
+                  </TableCell>
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+        </>
+      )}
+
+      <Bisect />
+    </Stack>
+  );
+};
+
+const DEPTH_LIMIT_KINDS: DepthLimitKind[] = [
+  "checkCrossProductUnion_DepthLimit",
+  "checkTypeRelatedTo_DepthLimit",
+  "getTypeAtFlowNode_DepthLimit",
+  "instantiateType_DepthLimit",
+  "recursiveTypeRelatedTo_DepthLimit",
+  "removeSubtypes_DepthLimit",
+  "traceUnionsOrIntersectionsTooLarge_DepthLimit",
+  "typeRelatedToDiscriminatedType_DepthLimit",
+];
+
+type MetricKind = BisectMetric["kind"];
+
+const Bisect = () => {
+  const bisect = useBisect();
+  const { mutate: cancel } = useCancelGeneration();
+  const { data: lastLine } = useTaskOutput("bisect");
+  const [good, setGood] = useState("");
+  const [bad, setBad] = useState("HEAD");
+  const [metricKind, setMetricKind] = useState<MetricKind>("totalCheckTime");
+  const [filePath, setFilePath] = useState("");
+  const [depthLimit, setDepthLimit] = useState<DepthLimitKind>(
+    "instantiateType_DepthLimit",
+  );
+  const [threshold, setThreshold] = useState("");
+  const [repetitions, setRepetitions] = useState("1");
+
+  const metric: BisectMetric =
+    metricKind === "fileCheckTime"
+      ? { kind: metricKind, path: filePath }
+      : metricKind === "depthLimitCount"
+        ? { kind: metricKind, depthLimit }
+        : { kind: metricKind };
+
+  const isTime =
+    metricKind === "totalCheckTime" || metricKind === "fileCheckTime";
+
+  return (
+    <Stack sx={{ gap: 2, mt: 4 }}>
+      <Typography variant="h5">Bisect a regression</Typography>
+      <Typography color="text.secondary">
+        checks out each commit between a good and a bad revision in a temporary
+        worktree, installs dependencies, runs tsc, and finds the first commit
+        where the metric went over the threshold. your own checkout isn't
+        touched.
+      </Typography>
+      <Stack direction="row" sx={{ gap: 2, flexWrap: "wrap" }}>
+        <TextField
+          label="good revision"
+          value={good}
+          onChange={event => setGood(event.target.value)}
+        />
+        <TextField
+          label="bad revision"
+          value={bad}
+          onChange={event => setBad(event.target.value)}
+        />
+        <TextField
+          select
+          label="metric"
+          value={metricKind}
+          onChange={event => setMetricKind(event.target.value as MetricKind)}
+          sx={{ minWidth: 200 }}
+        >
+          <MenuItem value="totalCheckTime">total check time</MenuItem>
+          <MenuItem value="fileCheckTime">a file's check time</MenuItem>
+          <MenuItem value="depthLimitCount">depth limit count</MenuItem>
+          <MenuItem value="typeCount">type count</MenuItem>
+        </TextField>
+        {metricKind === "fileCheckTime" && (
+          <TextField
+            label="file path (suffix)"
+            value={filePath}
+            onChange={event => setFilePath(event.target.value)}
+          />
+        )}
+        {metricKind === "depthLimitCount" && (
+          <TextField
+            select
+            label="depth limit"
+            value={depthLimit}
+            onChange={event =>
+              setDepthLimit(event.target.value as DepthLimitKind)
+            }
+          >
+            {DEPTH_LIMIT_KINDS.map(kind => (
+              <MenuItem key={kind} value={kind}>
+                {kind}
+              </MenuItem>
+            ))}
+          </TextField>
+        )}
+        <TextField
+          label={isTime ? "threshold (ms)" : "threshold"}
+          type="number"
+          value={threshold}
+          onChange={event => setThreshold(event.target.value)}
+        />
+        {isTime && (
+          <TextField
+            label="runs per commit"
+            type="number"
+            value={repetitions}
+            onChange={event => setRepetitions(event.target.value)}
+            helperText="with 2+, a commit is bad only when its 95% CI is over the threshold"
+            sx={{ maxWidth: 260 }}
+          />
+        )}
+      </Stack>
+      <Stack direction="row" sx={{ gap: 2 }}>
+        <Button
+          variant="contained"
+          disabled={bisect.isPending || !good || !bad || threshold === ""}
+          onClick={() =>
+            bisect.mutate({
+              good,
+              bad,
+              metric,
+              threshold: Number(threshold),
+              repetitions: isTime ? Math.max(1, Number(repetitions) || 1) : 1,
+            })
+          }
+        >
+          Bisect
+        </Button>
+        {bisect.isPending && (
+          <Button color="error" onClick={() => cancel("bisect")}>
+            Cancel
+          </Button>
+        )}
+      </Stack>
+      {bisect.isPending && lastLine && (
+        <Typography variant="caption" fontFamily="monospace">
+          {lastLine.line}
+        </Typography>
+      )}
+      {bisect.error && <Alert severity="error">{String(bisect.error)}</Alert>}
+      {bisect.data && (
+        <Alert severity="success">
+          first bad commit:{" "}
+          <code>{bisect.data.firstBadCommit.slice(0, 10)}</code>{" "}
+          {bisect.data.firstBadSubject} ({bisect.data.steps.length} of{" "}
+          {bisect.data.candidates} commits measured)
+        </Alert>
+      )}
+    </Stack>
+  );
+};
@@ -0,0 +1,256 @@
+import {
+  Alert,
+  Stack,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableRow,
+  TextField,
+  Typography,
+} from "@mui/material";
+import { useState } from "react";
+import {
+  type IncludedFile,
+  type PhaseTimes,
+  useProgramConstruction,
+  useWhyIsFileIncluded,
+} from "../hooks/tauri-hooks";
+
+const formatMs = (ms: number) => `${ms.toFixed(1)}ms`;
+
+const PhaseHeadings = () => (
+  <>
+    <TableCell align="right">parse</TableCell>
+    <TableCell align="right">bind</TableCell>
+    <TableCell align="right">resolve</TableCell>
+    <TableCell align="right">total</TableCell>
+  </>
+);
+
+const PhaseCells = ({ times }: { times: PhaseTimes }) => (
+  <>
+    {[times.parse, times.bind, times.resolve, times.total].map((ms, i) => (
+      // biome-ignore lint/suspicious/noArrayIndexKey: fixed columns
+      <TableCell key={i} align="right" sx={{ fontFamily: "monospace" }}>
+        {formatMs(ms)}
+      </TableCell>
+    ))}
+  </>
+);
+
+const describeInclusion = (included: IncludedFile) =>
+  [
+    included.reason,
+    included.directive && `"${included.directive}"`,
+    included.pulledIn > 0 &&
+      `pulled in ${included.pulledIn.toLocaleString()} files`,
+  ]
+    .filter(Boolean)
+    .join(", ");
+
+const WhyIsFileIncluded = ({
+  file,
+  setFile,
+}: {
+  file: string;
+  setFile: (file: string) => void;
+}) => {
+  const { data: explanation, error } = useWhyIsFileIncluded(file);
+
+  return (
+    <>
+      <Typography variant="h5">Why is this file included?</Typography>
+      <TextField
+        label="file"
+        size="small"
+        value={file}
+        placeholder="node_modules/@types/node/index.d.ts"
+        onChange={event => setFile(event.target.value)}
+        sx={{ fontFamily: "monospace" }}
+      />
+      {file !== "" && error && <Alert severity="info">{String(error)}</Alert>}
+      {explanation && (
+        <Table size="small">
+          <TableBody>
+            {[explanation.file, ...explanation.chain].map(included => (
+              <TableRow key={included.file}>
+                <TableCell
+                  sx={{ fontFamily: "monospace", wordBreak: "break-all" }}
+                >
+                  {included.file}
+                </TableCell>
+                <TableCell>{describeInclusion(included)}</TableCell>
+              </TableRow>
+            ))}
+          </TableBody>
+        </Table>
+      )}
+      {explanation && explanation.referencedBy.length > 1 && (
+        <Typography variant="body2" color="textSecondary">
+          Also referenced by{" "}
+          {explanation.referencedBy
+            .filter(from => from !== explanation.file.referencedBy)
+            .join(", ")}
+        </Typography>
+      )}
+    </>
+  );
+};
+
+export const ProgramConstructionPage = () => {
+  const { data: report, error } = useProgramConstruction();
+  const [includedFile, setIncludedFile] = useState("");
+
+  return (
+    <Stack sx={{ p: 4, gap: 3, overflow: "auto", maxHeight: "100%" }}>
+      <Stack sx={{ flexDirection: "row", alignItems: "flex-end", gap: 2 }}>
+        <Typography variant="h2">Program Construction</Typography>
+        <Typography variant="body1" color="textSecondary">
+          parsing, binding and resolving modules, before checking starts
+        </Typography>
+      </Stack>
+
+      {error && <Alert severity="error">{String(error)}</Alert>}
+
+      {report && (
+        <>
+          <Typography variant="body2" color="textSecondary">
+            {formatMs(report.createProgram)} in createProgram over{" "}
+            {report.programs.length.toLocaleString()} program(s) and{" "}
+            {report.fileCount.toLocaleString()} files (
+            {report.rootFiles.toLocaleString()} root files,{" "}
+            {report.typeReferenceDirectiveFiles.toLocaleString()} from type
+            reference directives, {report.libraryFiles.toLocaleString()} lib
+            files). {report.resolveLibraryCount.toLocaleString()} resolveLibrary
+            calls took {formatMs(report.resolveLibraryTime)}.
+          </Typography>
+
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell />
+                <PhaseHeadings />
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              <TableRow>
+                <TableCell>all files</TableCell>
+                <PhaseCells times={report.totals} />
+              </TableRow>
+              <TableRow>
+                <TableCell>outside node_modules</TableCell>
+                <PhaseCells times={report.project} />
+              </TableRow>
+            </TableBody>
+          </Table>
+
+          <Typography variant="h5">By package</Typography>
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>package</TableCell>
+                <TableCell align="right">files</TableCell>
+                <PhaseHeadings />
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {report.packages.map(pkg => (
+                <TableRow key={pkg.package}>
+                  <TableCell sx={{ fontFamily: "monospace" }}>
+                    {pkg.package}
+                  </TableCell>
+                  <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                    {pkg.files.toLocaleString()}
+                  </TableCell>
+                  <PhaseCells times={pkg.times} />
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+
+          <WhyIsFileIncluded
+            file={includedFile}
+            setFile={setIncludedFile}
+          />
+
+          <Typography variant="h5">By file</Typography>
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>file</TableCell>
+                <TableCell>included as</TableCell>
+                <PhaseHeadings />
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {report.files.map(file => (
+                <TableRow
+                  key={file.path}
+                  hover
+                  onClick={() => setIncludedFile(file.path)}
+                  sx={{ cursor: "pointer" }}
+                >
+                  <TableCell
+                    sx={{ fontFamily: "monospace", wordBreak: "break-all" }}
+                  >
+                    {file.path}
+                  </TableCell>
+                  <TableCell>{file.includeKind}</TableCell>
+                  <PhaseCells times={file.times} />
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+
+          <Typography variant="h5">Files by why they were included</Typography>
+          <Table size="small">
+            <TableBody>
+              {report.includeKinds.map(kind => (
+                <TableRow key={kind.includeKind}>
+                  <TableCell>{kind.includeKind}</TableCell>
+                  <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                    {kind.files.toLocaleString()}
+                  </TableCell>
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+
+          <Typography variant="h5">Type reference directives</Typography>
+          {report.unresolvedTypeReferenceDirectives > 0 && (
+            <Alert severity="warning">
+              {report.unresolvedTypeReferenceDirectives.toLocaleString()} type
+              reference directives didn't resolve
+            </Alert>
+          )}
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>directive</TableCell>
+                <TableCell align="right">count</TableCell>
+                <TableCell align="right">time</TableCell>
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {report.typeReferenceDirectives.map(directive => (
+                <TableRow key={directive.directive}>
+                  <TableCell sx={{ fontFamily: "monospace" }}>
+                    {directive.directive}
+                    {!directive.resolved && " (unresolved)"}
+                  </TableCell>
+                  <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                    {directive.count.toLocaleString()}
+                  </TableCell>
+                  <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                    {formatMs(directive.time)}
+                  </TableCell>
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+        </>
+      )}
+    </Stack>
+  );
+};
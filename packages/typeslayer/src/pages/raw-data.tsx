@@ -40,7 +40,7 @@ import {
 } from "../components/utils";
 import { type ToastData, useToast } from "../contexts/toast-context";
 import {
-  useDataDir,
+  type TraceEventCount,
   useGenerateAnalyzeTrace,
   useGenerateCpuProfile,
   useGenerateTrace,
@@ -51,6 +51,8 @@ import {
   useGetTypeGraphPreview,
   useGetTypesJsonPreview,
   useOutputFileSizes,
+  useOutputsDir,
+  useUnknownTraceEvents,
   useUploadAnalyzeTrace,
   useUploadTrace,
   useUploadTypeGraph,
@@ -188,6 +190,40 @@ export const RawData = () => {
   );
 };
 
+const EventCounts = ({ counts }: { counts: TraceEventCount[] }) =>
+  counts.map(({ name, count }, index) => (
+    <span key={name}>
+      {index > 0 && ", "}
+      <InlineCode>{name || "(no name)"}</InlineCode> ({count.toLocaleString()})
+    </span>
+  ));
+
+const UnknownTraceEvents = () => {
+  const { data: warnings } = useUnknownTraceEvents();
+  if (!warnings) {
+    return null;
+  }
+  const { unknown, skipped } = warnings;
+
+  return (
+    <>
+      {unknown.length > 0 && (
+        <Alert severity="warning">
+          This trace has events TypeSlayer doesn't know yet, probably from a
+          newer TypeScript. They still count towards hot spots, but their
+          details are ignored: <EventCounts counts={unknown} />
+        </Alert>
+      )}
+      {skipped.length > 0 && (
+        <Alert severity="warning">
+          Some events in this trace couldn't be read at all, so they were left
+          out: <EventCounts counts={skipped} />
+        </Alert>
+      )}
+    </>
+  );
+};
+
 const RawDataPane = ({ itemKey }: { itemKey: RawKey }) => {
   const {
     filename,
@@ -213,7 +249,7 @@ const RawDataPane = ({ itemKey }: { itemKey: RawKey }) => {
     },
     [showToastOriginal],
   );
-  const dataDir = useDataDir();
+  const outputsDir = useOutputsDir();
 
   const onCopy = useCallback(async () => {
     if (!preview) {
@@ -287,7 +323,7 @@ const RawDataPane = ({ itemKey }: { itemKey: RawKey }) => {
     }
   }, [upload, showToast, filename]);
 
-  const absolutePath = `${dataDir.data ?? ""}/outputs/${filename}`;
+  const absolutePath = `${outputsDir.data ?? ""}/${filename}`;
 
   return (
     <Stack
@@ -312,6 +348,8 @@ const RawDataPane = ({ itemKey }: { itemKey: RawKey }) => {
         <Typography>{description}</Typography>
       </Stack>
 
+      {itemKey === "trace" && <UnknownTraceEvents />}
+
       <Stack sx={{ gap: 1, flexDirection: "row", flexWrap: "wrap" }}>
         <Button
           variant="outlined"
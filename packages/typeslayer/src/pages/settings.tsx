@@ -26,6 +26,8 @@ import { invoke } from "@tauri-apps/api/core";
 import { InlineCode } from "@typeslayer/common";
 import { type ReactNode, useCallback, useMemo, useState } from "react";
 import { Code } from "../components/code";
+import { ConfigErrors } from "../components/config-errors";
+import { ProjectConfig } from "../components/project-config";
 import { detectPlatformSlash } from "../components/utils";
 import {
   type AnalyticsConsentResult,
@@ -131,6 +133,8 @@ export const SettingsPage = () => {
           v{version}
         </Typography>
       </Stack>
+      <ConfigErrors />
+      <ProjectConfig />
 
       <Setting title="Path Display">
         <FormGroup>
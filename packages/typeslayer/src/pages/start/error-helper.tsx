@@ -1,6 +1,19 @@
-import { Alert, AlertTitle, Divider, Stack, Typography } from "@mui/material";
+import {
+  Alert,
+  AlertTitle,
+  Button,
+  Divider,
+  Stack,
+  Typography,
+} from "@mui/material";
 import { InlineCode } from "@typeslayer/common";
 import { Code } from "../../components/code";
+import {
+  type CrashDiagnosis,
+  useCrashDiagnosis,
+  useMaxOldSpaceSize,
+  useMaxStackSize,
+} from "../../hooks/tauri-hooks";
 
 const maxWidth = 700;
 
@@ -156,6 +169,101 @@ export const BlewYerLoad = ({
   );
 };
 
+const SettingSuggestion = ({
+  suggestion,
+}: {
+  suggestion: CrashDiagnosis["suggestions"][number];
+}) => {
+  const maxOldSpaceSize = useMaxOldSpaceSize();
+  const maxStackSize = useMaxStackSize();
+  const { set, isSettingValue, data } =
+    suggestion.setting === "maxOldSpaceSize" ? maxOldSpaceSize : maxStackSize;
+
+  return (
+    <Stack sx={{ flexDirection: "row", alignItems: "center", gap: 2 }}>
+      <Typography>
+        set <InlineCode>{suggestion.setting}</InlineCode> to{" "}
+        <InlineCode>{suggestion.suggested}</InlineCode>
+        {suggestion.current !== null && ` (it's ${suggestion.current})`}.{" "}
+        {suggestion.reason}
+      </Typography>
+      <Button
+        size="small"
+        variant="outlined"
+        color="inherit"
+        loading={isSettingValue}
+        disabled={data === suggestion.suggested}
+        onClick={() => set(suggestion.suggested)}
+      >
+        {data === suggestion.suggested ? "Set" : "Apply"}
+      </Button>
+    </Stack>
+  );
+};
+
+const CrashDiagnosisHelper = () => {
+  const { data: diagnosis } = useCrashDiagnosis();
+  if (!diagnosis) {
+    return null;
+  }
+
+  const { lastChecked, checkingFile, openStack } = diagnosis;
+  const start = lastChecked?.start;
+
+  return (
+    <Alert severity="warning" sx={{ maxWidth: maxWidth + 100 }}>
+      <AlertTitle>where tsc was when it stopped</AlertTitle>
+      <Stack sx={{ gap: 1 }}>
+        {diagnosis.failures.map(failure => (
+          <Typography key={failure.kind}>
+            <InlineCode>{failure.line}</InlineCode>
+          </Typography>
+        ))}
+        {checkingFile ? (
+          <Typography>
+            checking <InlineCode>{checkingFile}</InlineCode>
+            {lastChecked && start && (
+              <>
+                , just after the node at{" "}
+                <InlineCode>{`${lastChecked.path}:${start.line}:${start.character}`}</InlineCode>{" "}
+                (tsc traces a node when it's done with it, so the one it was
+                stuck on comes at or after this one)
+              </>
+            )}
+          </Typography>
+        ) : (
+          <Typography>
+            it wasn't checking a file. {diagnosis.traceEvents.toLocaleString()}{" "}
+            events made it into the trace.
+          </Typography>
+        )}
+        {diagnosis.suggestions.map(suggestion => (
+          <SettingSuggestion key={suggestion.setting} suggestion={suggestion} />
+        ))}
+        {openStack.length > 0 && (
+          <>
+            <Divider />
+            <Typography variant="body2">
+              events that never ended, outermost first
+            </Typography>
+            {openStack.map(({ event, openFor }, index) => (
+              <Typography
+                // biome-ignore lint/suspicious/noArrayIndexKey: it's a stack
+                key={index}
+                variant="body2"
+                sx={{ fontFamily: "monospace", wordBreak: "break-all" }}
+              >
+                {event.name} ({openFor.toFixed(0)}ms){" "}
+                {"args" in event && JSON.stringify(event.args)}
+              </Typography>
+            ))}
+          </>
+        )}
+      </Stack>
+    </Alert>
+  );
+};
+
 export const ErrorHelper = ({
   processingError: _,
   processingErrorStderr,
@@ -170,6 +278,7 @@ export const ErrorHelper = ({
       <CompositeProjectsError processingErrorStdout={processingErrorStdout} />
       <OOMError processingErrorStderr={processingErrorStderr} />
       <BlewYerLoad processingErrorStderr={processingErrorStderr} />
+      <CrashDiagnosisHelper />
     </>
   );
 };
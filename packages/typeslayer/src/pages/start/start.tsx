@@ -1,6 +1,7 @@
 import { CircularProgress, Stack } from "@mui/material";
 import Box from "@mui/material/Box";
 import Typography from "@mui/material/Typography";
+import { ConfigErrors } from "../../components/config-errors";
 import { useAllTaskProgress } from "../../hooks/tauri-hooks";
 import { Step0Prerequisites } from "./step-0-prerequisites";
 import { Step1PackageJson } from "./step-1-packagejson";
@@ -23,6 +24,7 @@ export function Start() {
     >
       <Stack sx={{ gap: 3, mb: 4 }}>
         <Typography variant="h2">Start</Typography>
+        <ConfigErrors />
         <Step0Prerequisites />
         <Step1PackageJson />
         <Step2Tsconfig />
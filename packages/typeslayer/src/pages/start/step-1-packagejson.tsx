@@ -1,13 +1,14 @@
-import { Button, Stack, TextField, Typography } from "@mui/material";
+import { Button, Stack, TextField, Tooltip, Typography } from "@mui/material";
 import { open } from "@tauri-apps/plugin-dialog";
 import { InlineCode } from "@typeslayer/common";
 import { useCallback, useEffect, useState } from "react";
 import { PACKAGE_JSON } from "../../components/constants";
-import { useProjectRoot } from "../../hooks/tauri-hooks";
+import { usePackageManager, useProjectRoot } from "../../hooks/tauri-hooks";
 import { Step } from "./step";
 
 export const Step1PackageJson = () => {
   const projectRoot = useProjectRoot();
+  const { data: packageManager } = usePackageManager();
   const [localProjectRoot, setLocalProjectRoot] = useState<string>("");
 
   // handle update from server on mount
@@ -77,6 +78,29 @@ export const Step1PackageJson = () => {
             fullWidth
           />
         </Stack>
+        {packageManager && (
+          <Tooltip
+            title={
+              <Stack>
+                {packageManager.evidence.map(({ path, finding }) => (
+                  <Typography key={`${path}-${finding}`} variant="caption">
+                    {path}: {finding}
+                  </Typography>
+                ))}
+              </Stack>
+            }
+          >
+            <Typography variant="caption" color="text.secondary">
+              tsc will run through{" "}
+              <InlineCode>
+                {packageManager.kind}
+                {packageManager.version ? `@${packageManager.version}` : ""}
+              </InlineCode>
+              {packageManager.kind === "yarn" &&
+                ` (${packageManager.yarnBerry ? "berry" : "classic"}${packageManager.yarnPnp ? ", Plug'n'Play" : ""})`}
+            </Typography>
+          </Tooltip>
+        )}
       </Stack>
     </Step>
   );
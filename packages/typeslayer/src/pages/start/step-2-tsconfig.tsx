@@ -1,6 +1,8 @@
 import Flag from "@mui/icons-material/Flag";
 import {
+  Alert,
   Button,
+  Chip,
   MenuItem,
   Select,
   type SelectChangeEvent,
@@ -11,12 +13,82 @@ import { InlineCode } from "@typeslayer/common";
 import { useCallback, useState } from "react";
 import { FlagsCustomizationDialog } from "../../components/flags-customization-dialog";
 import { detectPlatformSlash } from "../../components/utils";
-import { useSelectedTsconfig, useTsconfigPaths } from "../../hooks/tauri-hooks";
+import {
+  type EffectiveTsconfig,
+  type TsconfigNode,
+  useEffectiveTsconfig,
+  useSelectedTsconfig,
+  useTsconfigPaths,
+  useTsconfigTree,
+} from "../../hooks/tauri-hooks";
 import { Step } from "./step";
 
+/** the owning package of every config in the tree, keyed by path */
+const packagesByPath = (
+  nodes: TsconfigNode[],
+  packages = new Map<string, string>(),
+) => {
+  for (const node of nodes) {
+    if (node.package?.name) {
+      packages.set(node.path, node.package.name);
+    }
+    packagesByPath(node.references, packages);
+  }
+  return packages;
+};
+
+const EffectiveTsconfigSummary = ({
+  tsconfig,
+}: {
+  tsconfig: EffectiveTsconfig;
+}) => {
+  const { strict, skipLibCheck, isolatedDeclarations, incremental, types } =
+    tsconfig.performance;
+  const flags: [string, boolean][] = [
+    ["strict", strict],
+    ["skipLibCheck", skipLibCheck],
+    ["isolatedDeclarations", isolatedDeclarations],
+    ["incremental", incremental],
+  ];
+  return (
+    <Stack sx={{ flexDirection: "row", gap: 1, flexWrap: "wrap" }}>
+      {flags.map(([flag, enabled]) => (
+        <Chip
+          key={flag}
+          size="small"
+          label={`${flag}: ${enabled}`}
+          color={enabled ? "primary" : "default"}
+          variant={enabled ? "filled" : "outlined"}
+        />
+      ))}
+      <Chip
+        size="small"
+        variant="outlined"
+        label={`types: ${types ? `[${types.join(", ")}]` : "all @types"}`}
+      />
+      <Chip
+        size="small"
+        variant="outlined"
+        label={`${tsconfig.rootFileCount.toLocaleString()} root files`}
+      />
+      {tsconfig.extends.length > 0 && (
+        <Chip
+          size="small"
+          variant="outlined"
+          label={`extends ${tsconfig.extends.length} config${tsconfig.extends.length === 1 ? "" : "s"}`}
+          title={tsconfig.extends.join("\n")}
+        />
+      )}
+    </Stack>
+  );
+};
+
 export const Step2Tsconfig = () => {
   const selectedTsconfig = useSelectedTsconfig();
   const tsconfigPaths = useTsconfigPaths();
+  const tsconfigTree = useTsconfigTree();
+  const effectiveTsconfig = useEffectiveTsconfig();
+  const packages = packagesByPath(tsconfigTree.data ?? []);
   const [isFlagsDialogOpen, setIsFlagsDialogOpen] = useState(false);
 
   const onTsconfigChange = useCallback(
@@ -91,10 +163,19 @@ export const Step2Tsconfig = () => {
               </MenuItem>
               {(tsconfigPaths.data ?? []).map(path => {
                 const filename = path.split(platformSlash).pop() || path;
+                const packageName = packages.get(path);
                 return (
                   <MenuItem key={path} value={path}>
                     <Stack>
-                      <Typography>{filename}</Typography>
+                      <Typography>
+                        {filename}
+                        {packageName && (
+                          <Typography component="span" color="textSecondary">
+                            {" "}
+                            ({packageName})
+                          </Typography>
+                        )}
+                      </Typography>
                       <Typography
                         variant="caption"
                         fontFamily="monospace"
@@ -121,6 +202,14 @@ export const Step2Tsconfig = () => {
               onClose={() => setIsFlagsDialogOpen(false)}
             />
           </Stack>
+          {effectiveTsconfig.data && (
+            <EffectiveTsconfigSummary tsconfig={effectiveTsconfig.data} />
+          )}
+          {effectiveTsconfig.error && (
+            <Alert severity="warning">
+              couldn't resolve the tsconfig: {String(effectiveTsconfig.error)}
+            </Alert>
+          )}
         </Stack>
       </Stack>
     </Step>
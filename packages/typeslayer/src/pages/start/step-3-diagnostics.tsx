@@ -1,9 +1,13 @@
 import Insights from "@mui/icons-material/Insights";
-import { Alert, Button, Stack } from "@mui/material";
+import { Alert, Button, Stack, Typography } from "@mui/material";
 import { useCallback, useEffect, useState } from "react";
 import { BigAction } from "../../components/big-action";
 import { useLogoFade } from "../../contexts/logo-fade-context";
-import { useCancelGeneration, useClearOutputs } from "../../hooks/tauri-hooks";
+import {
+  useCancelGeneration,
+  useClearOutputs,
+  useRunMetadata,
+} from "../../hooks/tauri-hooks";
 import { ErrorDialog } from "./error-dialog";
 import { Step } from "./step";
 
@@ -12,6 +16,7 @@ export const Step3Diagnostics = () => {
 
   const { mutateAsync: clearOutputs } = useClearOutputs();
   const { mutateAsync: cancelGeneration } = useCancelGeneration();
+  const { data: runMetadata } = useRunMetadata();
 
   // Processing state
   const [processingError, setProcessingError] = useState<string | null>(null);
@@ -172,6 +177,19 @@ export const Step3Diagnostics = () => {
               {isProcessing ? "Cancel" : "Clear"}
             </Button>
           </Stack>
+          {runMetadata && (
+            <Typography
+              variant="body2"
+              color="textSecondary"
+              title={runMetadata.command}
+            >
+              Last run {new Date(runMetadata.startedAt).toLocaleString()} with{" "}
+              {runMetadata.compilerVariant}
+              {runMetadata.tscVersion && ` ${runMetadata.tscVersion}`}, took{" "}
+              {(runMetadata.duration / 1000).toFixed(1)}s and wrote{" "}
+              {runMetadata.outputs.join(", ")}
+            </Typography>
+          )}
         </Stack>
       </Step>
       <ErrorDialog
@@ -0,0 +1,181 @@
+import FolderOpen from "@mui/icons-material/FolderOpen";
+import {
+  Alert,
+  Button,
+  Stack,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableRow,
+  Typography,
+} from "@mui/material";
+import { open } from "@tauri-apps/plugin-dialog";
+import { useCallback, useState } from "react";
+import { type Latency, useTsserverAnalysis } from "../hooks/tauri-hooks";
+
+const formatMs = (ms: number) => `${ms.toFixed(1)}ms`;
+
+const LatencyHeadings = () => (
+  <>
+    <TableCell align="right">count</TableCell>
+    <TableCell align="right">total</TableCell>
+    <TableCell align="right">mean</TableCell>
+    <TableCell align="right">p95</TableCell>
+    <TableCell align="right">max</TableCell>
+  </>
+);
+
+const LatencyCells = ({ latency }: { latency: Latency }) => (
+  <>
+    <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+      {latency.count.toLocaleString()}
+    </TableCell>
+    {[latency.total, latency.mean, latency.p95, latency.max].map((ms, i) => (
+      // biome-ignore lint/suspicious/noArrayIndexKey: fixed columns
+      <TableCell key={i} align="right" sx={{ fontFamily: "monospace" }}>
+        {formatMs(ms)}
+      </TableCell>
+    ))}
+  </>
+);
+
+export const TsserverPage = () => {
+  const [traceDir, setTraceDir] = useState<string | null>(null);
+  const { data: analysis, error, isFetching } = useTsserverAnalysis(traceDir);
+
+  const chooseTraceDir = useCallback(async () => {
+    const dir = await open({ directory: true, multiple: false });
+    if (dir && typeof dir === "string") {
+      setTraceDir(dir);
+    }
+  }, []);
+
+  return (
+    <Stack sx={{ p: 4, gap: 3, overflow: "auto", maxHeight: "100%" }}>
+      <Stack sx={{ flexDirection: "row", alignItems: "flex-end", gap: 2 }}>
+        <Typography variant="h2">Editor (tsserver)</Typography>
+        <Typography variant="body1" color="textSecondary">
+          where the language server spent its time while you typed
+        </Typography>
+      </Stack>
+
+      <Typography variant="body2" color="textSecondary">
+        Start tsserver with <code>--traceDirectory</code> (in VS Code, turn on{" "}
+        <code>typescript.tsserver.enableTracing</code>), use the editor until
+        it feels slow, then choose the directory it wrote{" "}
+        <code>trace.&lt;pid&gt;.json</code> files to.
+      </Typography>
+
+      <Button
+        variant="outlined"
+        startIcon={<FolderOpen />}
+        onClick={chooseTraceDir}
+        loading={isFetching}
+        loadingPosition="start"
+        sx={{ alignSelf: "start" }}
+      >
+        {traceDir ?? "Choose trace directory"}
+      </Button>
+
+      {error && <Alert severity="error">{String(error)}</Alert>}
+
+      {analysis && (
+        <>
+          {analysis.skipped.map(skipped => (
+            <Alert key={skipped.path} severity="warning">
+              Skipped {skipped.path}: {skipped.error}
+            </Alert>
+          ))}
+          {analysis.unansweredRequests > 0 && (
+            <Alert severity="info">
+              {analysis.unansweredRequests.toLocaleString()} requests never got
+              a response, e.g. because the server was restarted
+            </Alert>
+          )}
+
+          <Typography variant="h5">Requests by command</Typography>
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>command</TableCell>
+                <LatencyHeadings />
+                <TableCell align="right">failed</TableCell>
+                <TableCell align="right">canceled</TableCell>
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {analysis.commands.map(command => (
+                <TableRow
+                  key={command.command}
+                  title={command.slowest
+                    .map(
+                      request =>
+                        `seq ${request.seq}: ${formatMs(request.duration)}`,
+                    )
+                    .join("\n")}
+                >
+                  <TableCell sx={{ fontFamily: "monospace" }}>
+                    {command.command}
+                  </TableCell>
+                  <LatencyCells latency={command.latency} />
+                  <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                    {command.failed}
+                  </TableCell>
+                  <TableCell align="right" sx={{ fontFamily: "monospace" }}>
+                    {command.canceled}
+                  </TableCell>
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+
+          <Typography variant="h5">updateGraph by project</Typography>
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>project</TableCell>
+                <LatencyHeadings />
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {analysis.updateGraph.map(project => (
+                <TableRow key={project.project}>
+                  <TableCell
+                    sx={{ fontFamily: "monospace", wordBreak: "break-all" }}
+                  >
+                    {project.project}
+                  </TableCell>
+                  <LatencyCells latency={project.latency} />
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+
+          <Typography variant="h5">Semantic checks by file</Typography>
+          <Table size="small">
+            <TableHead>
+              <TableRow>
+                <TableCell>file</TableCell>
+                <LatencyHeadings />
+              </TableRow>
+            </TableHead>
+            <TableBody>
+              {analysis.semanticChecks.map(check => (
+                <TableRow key={`${check.configFilePath}:${check.file}`}>
+                  <TableCell
+                    sx={{ fontFamily: "monospace", wordBreak: "break-all" }}
+                    title={check.configFilePath}
+                  >
+                    {check.file}
+                  </TableCell>
+                  <LatencyCells latency={check.latency} />
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+        </>
+      )}
+    </Stack>
+  );
+};
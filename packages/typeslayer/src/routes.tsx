@@ -8,10 +8,15 @@ import { App } from "./App";
 import { AboutPage } from "./pages/about";
 import { AwardWinners } from "./pages/award-winners/award-winners";
 import { CiCdIntegration } from "./pages/cicd-integration";
+import { CpuProfilePage } from "./pages/cpu-profile";
 import { DocsPage } from "./pages/docs";
+import { EmitPage } from "./pages/emit";
+import { ExperimentsPage } from "./pages/experiments";
+import { HistoryPage } from "./pages/history";
 import { LeaderboardPage } from "./pages/leaderboard";
 import { Mcp } from "./pages/mcp";
 import { Perfetto } from "./pages/perfetto";
+import { ProgramConstructionPage } from "./pages/program-construction";
 // analyze-trace page remains but is no longer directly routed
 import { RawData } from "./pages/raw-data";
 import { SearchTypes } from "./pages/search";
@@ -20,6 +25,7 @@ import { SpeedScope } from "./pages/speedscope";
 import { LogoFade } from "./pages/start/logo-fade";
 import { Start } from "./pages/start/start";
 import { Treemap } from "./pages/treemap";
+import { TsserverPage } from "./pages/tsserver";
 import { TypeGraph } from "./pages/type-graph";
 
 // Root route – renders App which contains DashboardLayout and Outlet
@@ -82,12 +88,48 @@ const treemapRoute = createRoute({
   component: Treemap,
 });
 
+const historyRoute = createRoute({
+  getParentRoute: () => rootRoute,
+  path: "/history",
+  component: HistoryPage,
+});
+
+const experimentsRoute = createRoute({
+  getParentRoute: () => rootRoute,
+  path: "/experiments",
+  component: ExperimentsPage,
+});
+
 const perfettoRoute = createRoute({
   getParentRoute: () => rootRoute,
   path: "/perfetto",
   component: Perfetto,
 });
 
+const programConstructionRoute = createRoute({
+  getParentRoute: () => rootRoute,
+  path: "/program-construction",
+  component: ProgramConstructionPage,
+});
+
+const emitRoute = createRoute({
+  getParentRoute: () => rootRoute,
+  path: "/emit",
+  component: EmitPage,
+});
+
+const cpuProfileRoute = createRoute({
+  getParentRoute: () => rootRoute,
+  path: "/cpu-profile",
+  component: CpuProfilePage,
+});
+
+const tsserverRoute = createRoute({
+  getParentRoute: () => rootRoute,
+  path: "/tsserver",
+  component: TsserverPage,
+});
+
 const speedscopeRoute = createRoute({
   getParentRoute: () => rootRoute,
   path: "/speedscope",
@@ -173,7 +215,13 @@ const routeTree = rootRoute.addChildren([
   TypeGraphRoute,
   TypeGraphChildRoute,
   treemapRoute,
+  historyRoute,
+  experimentsRoute,
   perfettoRoute,
+  programConstructionRoute,
+  emitRoute,
+  cpuProfileRoute,
+  tsserverRoute,
   speedscopeRoute,
   rawDataRoute,
   rawDataChildRoute,